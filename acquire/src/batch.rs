@@ -1,8 +1,13 @@
 use std::{sync::Arc, future::Future};
-use tokio::sync::{mpsc, RwLock};
-use entities::prelude::fetch;
-use sea_orm::DatabaseConnection as Db;
-use crate::{StrategyList, strategy_list::RunIdError};
+use tokio::{sync::{mpsc, RwLock}, task::JoinSet};
+use entities::prelude::{fetch, feed};
+use sea_orm::{DatabaseConnection as Db, EntityTrait};
+use crate::{
+	StrategyList,
+	strategy::RunContext,
+	strategy_list::RunIdError,
+	limiter::Limiter,
+};
 
 
 #[trait_variant::make(Listener: Send)]
@@ -26,6 +31,10 @@ pub type FetchResult = Result<fetch::Model, RunIdError>;
 pub struct Batch {
 	pub total: usize,
 	pub finished: Vec<FetchResult>,
+	///What happened with the feeds whose fetch finished, cancelled ones aren't in here
+	pub fetched: Vec<FetchSummary>,
+	///Whether it got cancelled before all fetches were finished
+	pub cancelled: bool,
 }
 
 impl Batch {
@@ -33,11 +42,28 @@ impl Batch {
 		Self {
 			total,
 			finished: Vec::new(),
+			fetched: Vec::new(),
+			cancelled: false,
 		}
 	}
 	
-	pub fn add_done(&mut self, fetch_result: FetchResult) {
+	///Returns the summary of the fetch
+	pub fn add_done(&mut self, feed_id: i32, fetch_result: FetchResult) -> FetchSummary {
+		let summary = FetchSummary::new(feed_id, &fetch_result);
+		self.fetched.push(summary);
 		self.finished.push(fetch_result);
+		summary
+	}
+	
+	///Marks all fetches that haven't finished yet as cancelled
+	pub fn cancel_remaining(&mut self) {
+		if self.is_done() {
+			return;
+		}
+		self.cancelled = true;
+		while !self.is_done() {
+			self.finished.push(Err(RunIdError::Cancelled));
+		}
 	}
 	
 	pub fn is_done(&self) -> bool {
@@ -48,53 +74,110 @@ impl Batch {
 		BatchStatusUpdate {
 			total: self.total,
 			done: self.finished.len(),
+			cancelled: self.cancelled,
+			latest: None,
+		}
+	}
+	
+	///The status, plus everything [BatchStatusUpdate::latest] said so far
+	pub fn snapshot(&self) -> BatchSnapshot {
+		BatchSnapshot {
+			status: self.status(),
+			fetched: self.fetched.clone(),
+		}
+	}
+}
+
+///What happened with a single feed in a batch
+#[derive(Debug, Clone,Copy,PartialEq,Eq, serde::Serialize)]
+pub struct FetchSummary {
+	pub feed_id: i32,
+	///Missing if the fetch couldn't be saved
+	pub fetch_id: Option<i32>,
+	pub status: Option<fetch::Status>,
+}
+
+impl FetchSummary {
+	pub fn new(feed_id: i32, result: &FetchResult) -> Self {
+		let fetch = result.as_ref().ok();
+		Self {
+			feed_id,
+			fetch_id: fetch.map(|fetch| fetch.id),
+			status: fetch.map(|fetch| fetch.status),
 		}
 	}
 }
 
 #[non_exhaustive]
-#[derive(Debug, Clone,Copy,PartialEq,Eq)]
+#[derive(Debug, Clone,Copy,PartialEq,Eq, serde::Serialize)]
 pub struct BatchStatusUpdate {
 	pub total: usize,
 	pub done: usize,
+	pub cancelled: bool,
+	///The fetch that caused this update, if any
+	pub latest: Option<FetchSummary>,
+}
+
+impl BatchStatusUpdate {
+	pub fn is_done(&self) -> bool {
+		self.total == self.done
+	}
+}
+
+///The complete state of a batch, for listeners that missed [BatchStatusUpdate]s
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct BatchSnapshot {
+	#[serde(flatten)]
+	pub status: BatchStatusUpdate,
+	pub fetched: Vec<FetchSummary>,
 }
 
 enum BatchMessage {
-	Done(FetchResult)
+	Done(i32, FetchResult)
 }
 
 //TODO: this fetches every feed again, while making the list of ids requires fetching all of them in the first place
 /**
-Fetches all the feeds with the given ids in parallel (every feed gets spawned a new task).
+Fetches all the feeds with the given ids in parallel (every feed gets spawned a new task),
+as far as the [Limiter] allows.
+Dropping the returned future aborts the fetches that are still running.
 
 The returned results are probably in a different order then the feed ids. Check fetch.feed_id to get the corresponding feed.
+
+The fetches get linked to the batch with `batch_id` in the database, if given.
 */
 pub fn fetch_batch(
 	feeds: Vec<i32>,
 	listener: impl Listener,
 	strats: StrategyList,
-	db: Db
+	db: Db,
+	limiter: Limiter,
+	batch_id: Option<i32>,
 ) -> (Arc<RwLock<Batch>>, impl Future<Output=()>) {
 	let batch_sync = Arc::new(RwLock::new(Batch::new(feeds.len())));
-	let future = run_fetch_batch(feeds, batch_sync.clone(), listener, strats, db);
+	let future = run_fetch_batch(feeds, batch_sync.clone(), listener, strats, db, limiter, batch_id);
 	
 	(batch_sync, future)
 }
 
-#[tracing::instrument(skip(listener))]
+#[tracing::instrument(skip(listener, limiter))]
 pub async fn run_fetch_batch(
 	feeds: Vec<i32>,
 	batch_sync: Arc<RwLock<Batch>>,
 	mut listener: impl Listener,
 	strats: StrategyList,
 	db: Db,
+	limiter: Limiter,
+	batch_id: Option<i32>,
 ) {
 	tracing::info!("starting batch fetch");
 	
 	let (send, mut receive) = mpsc::channel(16);
+	//Aborts all the fetches when dropped, which happens when this future gets cancelled
+	let mut tasks = JoinSet::new();
 	
 	for id in feeds {
-		emit_fetch(id, send.clone(), &strats, db.clone());
+		emit_fetch(&mut tasks, id, send.clone(), &strats, db.clone(), &limiter, batch_id);
 	}
 	
 	loop {
@@ -105,14 +188,16 @@ pub async fn run_fetch_batch(
 		
 		let mut batch_lock = batch_sync.write().await;
 		
-		match mes {
-			BatchMessage::Done(result) => {
-				batch_lock.add_done(result);
-			}
-		}
+		let latest = match mes {
+			BatchMessage::Done(feed_id, result) => batch_lock.add_done(feed_id, result),
+		};
 		
+		let update = BatchStatusUpdate {
+			latest: Some(latest),
+			..batch_lock.status()
+		};
 		//Don't care if nobody's listening
-		listener.fetch_finished(batch_lock.status()).await;
+		listener.fetch_finished(update).await;
 		
 		if batch_lock.is_done() {
 			break;
@@ -122,12 +207,37 @@ pub async fn run_fetch_batch(
 	tracing::info!("finished batch fetch");
 }
 
-///Spawns a new task that fetches the feed, while sending update(s) along the channel
-fn emit_fetch(feed_id: i32, channel: mpsc::Sender<BatchMessage>, strats: &StrategyList, db: Db) {
+///Runs the fetch with every attempt waiting for the [Limiter]
+async fn limited_fetch(feed_id: i32, strats: &StrategyList, db: &Db, limiter: &Limiter, batch_id: Option<i32>) -> FetchResult {
+	let maybe_feed = feed::Entity::find_by_id(feed_id)
+		.one(db)
+		.await?;
+	let Some(feed) = maybe_feed else {
+		return Err(RunIdError::NoSuchFeed(feed_id))
+	};
+	
+	let context = RunContext {
+		batch_id,
+		limiter: Some(limiter.clone()),
+	};
+	Ok(strats.run_with_context(db, feed, &context).await?)
+}
+
+///Spawns a new task in `tasks` that fetches the feed, while sending update(s) along the channel
+fn emit_fetch(
+	tasks: &mut JoinSet<()>,
+	feed_id: i32,
+	channel: mpsc::Sender<BatchMessage>,
+	strats: &StrategyList,
+	db: Db,
+	limiter: &Limiter,
+	batch_id: Option<i32>,
+) {
 	let strats = strats.clone();
-	tokio::spawn(async move {
-		let result = strats.run_id(feed_id, &db).await;
-		channel.send(BatchMessage::Done(result)).await.expect("someone should be listening");
+	let limiter = limiter.clone();
+	tasks.spawn(async move {
+		let result = limited_fetch(feed_id, &strats, &db, &limiter, batch_id).await;
+		channel.send(BatchMessage::Done(feed_id, result)).await.expect("someone should be listening");
 	});
 		
 }
\ No newline at end of file
@@ -1,14 +1,16 @@
 use thiserror::Error;
 use tokio::{
 	sync::{broadcast, RwLock},
-	task::{JoinHandle, JoinError}
+	task::{JoinHandle, JoinError, AbortHandle}
 };
-use sea_orm::DatabaseConnection as Db;
+use sea_orm::{DatabaseConnection as Db, DbErr, ActiveModelTrait, ActiveModelBehavior, EntityTrait, QueryFilter, ColumnTrait, Set};
+use entities::prelude::batch;
 use tracing::Instrument;
-use std::sync::Arc;
+use std::{sync::Arc, collections::HashMap};
 use crate::{
 	StrategyList,
-	batch::{Batch, FetchResult, BatchStatusUpdate, fetch_batch, Listener}
+	batch::{Batch, FetchResult, BatchStatusUpdate, fetch_batch, Listener},
+	limiter::Limiter,
 };
 
 type Receiver = broadcast::Receiver<BatchStatusUpdate>;
@@ -43,32 +45,56 @@ pub struct TrackedBatch {
 	pub listener: Receiver,
 	///handle of the fetching task
 	pub fetch_handle: Sync<Option<JoinHandle<()>>>,
+	///Also works while the fetch_handle is being awaited
+	abort_handle: AbortHandle,
+	sender: broadcast::Sender<BatchStatusUpdate>,
+	db: Db,
 }
 
 impl TrackedBatch {
-	///`log_identifier` gets added to to the [tracing::Span] of the underlying [Future](std::future::Future) as "id"
+	///`id` is the id of the batch in the database, the fetches get linked to it
 	pub fn new_fetch(
 		feeds: Vec<i32>,
 		strats: StrategyList,
 		db: Db,
-		log_identifier: impl tracing::Value,
+		limiter: Limiter,
+		id: i32,
 	) -> Self {
 		let (send, recv) = broadcast::channel(16);
-		let listener = BroadcastListener::from_sender(send);
+		let listener = BroadcastListener::from_sender(send.clone());
 		
-		let (batch, future) = fetch_batch(feeds, listener, strats, db);
-		let future = future.instrument(tracing::info_span!("tracked batch", id=log_identifier));
+		let (batch, future) = fetch_batch(feeds, listener, strats, db.clone(), limiter, Some(id));
+		let finish_db = db.clone();
+		let future = async move {
+			future.await;
+			if let Err(err) = save_status(&finish_db, id, batch::Status::Finished).await {
+				tracing::error!(?err, "Error saving finished batch");
+			}
+		};
+		let future = future.instrument(tracing::info_span!("tracked batch", id));
 		
 		let fetch_handle = tokio::spawn(future);
+		let abort_handle = fetch_handle.abort_handle();
 		
 		Self {
 			status: batch,
 			listener: recv,
 			fetch_handle: Arc::new(RwLock::new(Some(fetch_handle))),
+			abort_handle,
+			sender: send,
+			db,
 		}
 	}
 }
 
+async fn save_status(db: &Db, id: i32, status: batch::Status) -> Result<(), DbErr> {
+	let mut batch = batch::ActiveModel::new();
+	batch.id = Set(id);
+	batch.status = Set(status);
+	batch.update(db).await?;
+	Ok(())
+}
+
 #[derive(Debug, Default)]
 pub enum BatchStatus {
 	#[default]
@@ -78,60 +104,105 @@ pub enum BatchStatus {
 }
 
 #[derive(Debug, Error)]
-#[error("Could not find batch at index {0}")]
-pub struct BatchNotFoundError(usize);
+#[error("Could not find running batch with id {0}")]
+pub struct BatchNotFoundError(i32);
 
 #[derive(Debug,Error)]
 pub enum AwaitFetchError {
-	#[error("Could not find batch at index {0}")]
-	NotFound(usize),
+	#[error("Could not find running batch with id {0}")]
+	NotFound(i32),
 	#[error(transparent)]
 	JoinError(#[from] JoinError),
 	#[error("There's no JoinHandle to await, presumably because it is already being awaited somewhere else")]
 	NoJoinHandle,
 }
 
+#[derive(Debug,Error)]
+pub enum CancelError {
+	#[error(transparent)]
+	NotFound(#[from] BatchNotFoundError),
+	#[error("Database error")]
+	Db(#[from] DbErr),
+}
+
+/**
+Keeps track of the running batches, indexed by their id in the database.
+
+The database keeps the history of batches, this only has the things that can't be stored in there.
+Finished batches stick around until the next batch gets queued, so they can still be awaited and inspected right after.
+*/
 #[derive(Default, Debug, Clone)]
 pub struct BatchTracker {
-	batches: Sync<Vec<TrackedBatch>>,
+	batches: Sync<HashMap<i32, TrackedBatch>>,
+	///Shared by all batches, so the limits hold while multiple batches are running
+	limiter: Limiter,
 }
 
 impl BatchTracker {
-	pub async fn queue_fetches(&self, feeds: Vec<i32>, db: Db, strats: StrategyList) -> usize {
+	pub fn new(limiter: Limiter) -> Self {
+		Self {
+			batches: Arc::default(),
+			limiter,
+		}
+	}
+	
+	pub fn limiter(&self) -> &Limiter {
+		&self.limiter
+	}
+	
+	///Batches that were still running when the server stopped can't continue, so get marked as interrupted
+	pub async fn mark_interrupted(db: &Db) -> Result<u64, DbErr> {
+		let res = batch::Entity::update_many()
+			.col_expr(batch::Column::Status, batch::Status::Interrupted.into())
+			.filter(batch::Column::Status.eq(batch::Status::Running))
+			.exec(db)
+			.await?;
+		Ok(res.rows_affected)
+	}
+	
+	///Returns the id of the batch in the database
+	pub async fn queue_fetches(&self, feeds: Vec<i32>, db: Db, strats: StrategyList) -> Result<i32, DbErr> {
+		let mut batch = batch::ActiveModel::new();
+		batch.status = Set(batch::Status::Running);
+		batch.total = Set(feeds.len() as i32);
+		let batch = batch.insert(&db).await?;
+		let id = batch.id;
 		
 		//Scope to reduce lock time
 		{
 			let mut batches_lock = self.batches.write().await;
+			//The fetching task finishes after saving the batch, so the database has everything that's left
+			batches_lock.retain(|_id, batch| !batch.abort_handle.is_finished());
 			
-			let id = batches_lock.len();
-			let tracker = TrackedBatch::new_fetch(feeds, strats, db, id);
-			batches_lock.push(tracker);
-			id
+			let tracker = TrackedBatch::new_fetch(feeds, strats, db, self.limiter.clone(), id);
+			batches_lock.insert(id, tracker);
 		}
+		
+		Ok(id)
 	}
 	
-	pub async fn get_status(&self, index: usize) -> Result<Sync<Batch>, BatchNotFoundError> {
+	pub async fn get_status(&self, id: i32) -> Result<Sync<Batch>, BatchNotFoundError> {
 		let lock = self.batches.read().await;
-		let Some(batch) = lock.get(index) else {
-			return Err(BatchNotFoundError(index));
+		let Some(batch) = lock.get(&id) else {
+			return Err(BatchNotFoundError(id));
 		};
 		Ok(batch.status.clone())
 	}
 	
-	pub async fn subscribe(&self, index: usize) -> Result<broadcast::Receiver<BatchStatusUpdate>, BatchNotFoundError> {
+	pub async fn subscribe(&self, id: i32) -> Result<broadcast::Receiver<BatchStatusUpdate>, BatchNotFoundError> {
 		let lock = self.batches.read().await;
-		let Some(batch) = lock.get(index) else {
-			return Err(BatchNotFoundError(index));
+		let Some(batch) = lock.get(&id) else {
+			return Err(BatchNotFoundError(id));
 		};
 		Ok(batch.listener.resubscribe())
 	}
 	
-	pub async fn await_fetch(&self, index: usize) -> Result<(), AwaitFetchError> {
+	pub async fn await_fetch(&self, id: i32) -> Result<(), AwaitFetchError> {
 		//Scope to reduce lock time
 		let synced_maybe_handle = {
 			let lock = self.batches.read().await;
-			let Some(batch) = lock.get(index) else {
-				return Err(AwaitFetchError::NotFound(index));
+			let Some(batch) = lock.get(&id) else {
+				return Err(AwaitFetchError::NotFound(id));
 			};
 			batch.fetch_handle.clone()
 		};
@@ -146,8 +217,45 @@ impl BatchTracker {
 			return Err(AwaitFetchError::NoJoinHandle);
 		};
 		
-		handle.await?;
+		match handle.await {
+			//Getting cancelled is just another way to finish
+			Err(err) if err.is_cancelled() => Ok(()),
+			res => Ok(res?),
+		}
+	}
+	
+	///Stops all the fetches of the batch that are still running, and marks them as cancelled.
+	///Does nothing if the batch is already finished.
+	pub async fn cancel(&self, id: i32) -> Result<(), CancelError> {
+		//Scope to reduce lock time
+		let (status, abort_handle, sender, db) = {
+			let lock = self.batches.read().await;
+			let Some(batch) = lock.get(&id) else {
+				return Err(BatchNotFoundError(id).into());
+			};
+			(batch.status.clone(), batch.abort_handle.clone(), batch.sender.clone(), batch.db.clone())
+		};
+		
+		//Dropping the batch future also aborts the individual fetches
+		abort_handle.abort();
+		
+		//Scope to reduce lock time
+		let update = {
+			let mut lock = status.write().await;
+			if lock.is_done() {
+				//The abort might have happened right before it got saved
+				save_status(&db, id, batch::Status::Finished).await?;
+				return Ok(());
+			}
+			lock.cancel_remaining();
+			lock.status()
+		};
+		save_status(&db, id, batch::Status::Cancelled).await?;
+		tracing::info!(id, "Cancelled batch");
+		
+		//don't care if nobody is listening
+		let _ = sender.send(update);
 		
 		Ok(())
 	}
-}
\ No newline at end of file
+}
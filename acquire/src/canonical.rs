@@ -0,0 +1,90 @@
+use reqwest::Url;
+
+///Query parameters that get added to links to any site to track where they came from
+const TRACKING_PARAMETERS: &[&str] = &["fbclid", "gclid"];
+
+///Query parameters that only track where a link came from on these hosts, elsewhere they might mean something
+const HOST_TRACKING_PARAMETERS: &[(&str, &[&str])] = &[
+	("youtube.com", &["si", "feature", "pp"]),
+	("m.youtube.com", &["si", "feature", "pp"]),
+	("music.youtube.com", &["si", "feature"]),
+	("youtu.be", &["si", "feature"]),
+	("open.spotify.com", &["si"]),
+	("twitter.com", &["s", "t", "ref_src"]),
+	("x.com", &["s", "t", "ref_src"]),
+	("instagram.com", &["igsh", "igshid"]),
+];
+
+fn is_tracking_parameter(host: &str, name: &str) -> bool {
+	if name.starts_with("utm_") || TRACKING_PARAMETERS.contains(&name) {
+		return true;
+	}
+	HOST_TRACKING_PARAMETERS.iter()
+		.any(|(tracking_host, parameters)| *tracking_host == host && parameters.contains(&name))
+}
+
+///The id of a YouTube video, which can be linked to in a bunch of different ways
+fn youtube_video_id(url: &Url, host: &str) -> Option<String> {
+	let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+	let id = match host {
+		"youtu.be" => segments.next()?.to_owned(),
+		"youtube.com" | "m.youtube.com" | "youtube-nocookie.com" => match segments.next()? {
+			"watch" => url.query_pairs().find(|(name, _)| name == "v")?.1.into_owned(),
+			"shorts" | "embed" | "live" => segments.next()?.to_owned(),
+			_ => return None,
+		},
+		_ => return None,
+	};
+	Some(id)
+}
+
+/**
+Identifies the content behind `url`, so entries linking to the same thing in slightly different ways can be recognised as the same.
+
+Ignores the scheme, a leading "www.", trailing slashes and the tracking parameters of the host.
+The fragment stays, since some sites route their pages with it.
+YouTube videos become "youtube:" followed by the video id.
+Urls that can't be parsed are only trimmed.
+*/
+pub fn canonical_url(url: &str) -> String {
+	let url = url.trim();
+	let parsed = if url.contains("://") {
+		Url::parse(url)
+	} else {
+		Url::parse(&format!("https://{url}"))
+	};
+	let Ok(parsed) = parsed else {
+		return url.to_owned();
+	};
+	let Some(host) = parsed.host_str() else {
+		return url.to_owned();
+	};
+	let host = host.to_lowercase();
+	let host = host.strip_prefix("www.").unwrap_or(&host);
+	
+	if let Some(id) = youtube_video_id(&parsed, host) {
+		return format!("youtube:{id}");
+	}
+	
+	let mut canonical = host.to_owned();
+	if let Some(port) = parsed.port() {
+		canonical.push_str(&format!(":{port}"));
+	}
+	canonical.push_str(parsed.path().trim_end_matches('/'));
+	
+	let mut query = parsed.query_pairs()
+		.filter(|(name, _)| !is_tracking_parameter(host, name))
+		.map(|(name, value)| format!("{name}={value}"))
+		.collect::<Vec<_>>();
+	if !query.is_empty() {
+		query.sort();
+		canonical.push('?');
+		canonical.push_str(&query.join("&"));
+	}
+	if let Some(fragment) = parsed.fragment().filter(|fragment| !fragment.is_empty()) {
+		canonical.push('#');
+		canonical.push_str(fragment);
+	}
+	
+	canonical
+}
@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use entities::prelude::*;
+use sea_orm::*;
+use reqwest::{header, Url};
+use scraper::{Html, Selector};
+use super::strategy::*;
+
+/*
+For sites without a feed, like most webcomics.
+Fetching walks from page to page by following the next (or previous) link, and keeps the html of every new page.
+Parsing then picks the title and image out of each page.
+*/
+
+
+///Which link to follow
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Direction {
+	///Follow the next link, starting from the last known entry, or the feed url if there is none
+	#[default]
+	Forward,
+	///Follow the previous link to backfill older pages, starting from the last known entry, or the feed url if there is none
+	Backward,
+}
+
+///What [ClickNextStrategy::fetch] got
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Crawl {
+	///Pages don't say when they got made, so this is used as the produced date instead
+	crawled_at: time::PrimitiveDateTime,
+	///When going backward, the produced date of the oldest entry the feed already had, the new pages have to come before it
+	#[serde(default)]
+	oldest_known: Option<time::PrimitiveDateTime>,
+	///In the order they got crawled
+	pages: Vec<Page>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Page {
+	url: String,
+	html: String,
+}
+
+fn selector(selector: &str) -> anyhow::Result<Selector> {
+	Selector::parse(selector).map_err(|e| anyhow::anyhow!("Invalid selector \"{selector}\": {e}"))
+}
+
+///Resolves the first match for `selector` with `attribute` against `base`
+fn link(html: &Html, base: &Url, selector: &Selector, attribute: &str) -> Option<Url> {
+	let element = html.select(selector).next()?;
+	let href = element.value().attr(attribute)?.trim();
+	match base.join(href) {
+		Ok(url) => Some(url),
+		Err(err) => {
+			tracing::warn!(href, %err, "Could not resolve link");
+			None
+		}
+	}
+}
+
+
+///Crawls a site by repeatedly clicking the next or previous link, configured with CSS selectors
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClickNextStrategy {
+	direction: Direction,
+	///Link to the next page, used when going [Direction::Forward]
+	next: String,
+	///Link to the previous page, used when going [Direction::Backward]
+	previous: String,
+	///The text of the first match becomes the title, the page url is used if nothing matches
+	title: String,
+	///The src of the first match becomes the embed url
+	image: Option<String>,
+	///How many new pages a single fetch gets at most
+	max_pages: u16,
+	user_agent: String,
+	#[serde(skip)]
+	client: reqwest::Client,
+}
+
+impl Default for ClickNextStrategy {
+	fn default() -> Self {
+		Self {
+			direction: Direction::default(),
+			next: "a[rel~=\"next\"]".into(),
+			previous: "a[rel~=\"prev\"]".into(),
+			title: "title".into(),
+			image: None,
+			max_pages: 20,
+			user_agent: "Fusta Femas".into(),
+			client: reqwest::Client::default(),
+		}
+	}
+}
+
+impl ClickNextStrategy {
+	pub fn direction(&mut self, direction: Direction) -> &mut Self {
+		self.direction = direction;
+		self
+	}
+	
+	pub fn next(&mut self, selector: impl Into<String>) -> &mut Self {
+		self.next = selector.into();
+		self
+	}
+	
+	pub fn previous(&mut self, selector: impl Into<String>) -> &mut Self {
+		self.previous = selector.into();
+		self
+	}
+	
+	pub fn title(&mut self, selector: impl Into<String>) -> &mut Self {
+		self.title = selector.into();
+		self
+	}
+	
+	pub fn image(&mut self, selector: impl Into<String>) -> &mut Self {
+		self.image = Some(selector.into());
+		self
+	}
+	
+	pub fn max_pages(&mut self, max_pages: u16) -> &mut Self {
+		self.max_pages = max_pages;
+		self
+	}
+	
+	fn link_selector(&self) -> anyhow::Result<Selector> {
+		match self.direction {
+			Direction::Forward => selector(&self.next),
+			Direction::Backward => selector(&self.previous),
+		}
+	}
+	
+	///Returns the url the page ended up at after redirects, and its html
+	async fn get(&self, url: Url) -> anyhow::Result<(Url, String)> {
+		tracing::info!(%url, "Requesting page");
+		let response = self.client.get(url)
+			.header(header::USER_AGENT, &self.user_agent)
+			.send().await?
+			.error_for_status()?;
+		let url = response.url().clone();
+		Ok((url, response.text().await?))
+	}
+	
+	fn entry(&self, page: Page, produced: time::PrimitiveDateTime) -> anyhow::Result<EntryInfo> {
+		let html = Html::parse_document(&page.html);
+		let base = Url::parse(&page.url)?;
+		
+		let title = html.select(&selector(&self.title)?)
+			.next()
+			.map(|element| element.text().collect::<String>().trim().to_owned())
+			.filter(|title| !title.is_empty())
+			.unwrap_or_else(|| page.url.clone());
+		
+		let mut entry = EntryInfo::new(page.url.clone(), title, page.url, produced.date());
+		entry.produced_time(produced.time());
+		if let Some(image) = &self.image {
+			if let Some(src) = link(&html, &base, &selector(image)?, "src") {
+				entry.embed_url(src.into());
+			}
+		}
+		Ok(entry)
+	}
+}
+
+#[async_trait::async_trait]
+impl Strategy for ClickNextStrategy {
+	fn name(&self) -> &'static str {
+		"click-next"
+	}
+	
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
+		let link_selector = self.link_selector()?;
+		
+		let mut url = feed.url.to_owned();
+		if !url.contains("://") {
+			url = format!("https://{url}");
+			tracing::info!(url, "Expanded url:")
+		}
+		
+		//The last inserted entry is the furthest one reached in the crawling direction
+		let maybe_last_entry = feed.find_related(entry::Entity)
+			.order_by_desc(entry::Column::Id)
+			.one(conn).await?;
+		//The page to start from is already known then, it's only needed for its link
+		let (mut url, mut skip) = match maybe_last_entry {
+			Some(last_entry) => {
+				tracing::info!(url = last_entry.view_url, "Resuming from last known entry");
+				(Url::parse(&last_entry.view_url)?, true)
+			},
+			None => (Url::parse(&url)?, false),
+		};
+		
+		//Backfilled pages are older than everything from earlier fetches, so have to be dated before them
+		let oldest_known = match self.direction {
+			Direction::Forward => None,
+			Direction::Backward => feed.find_related(entry::Entity)
+				.order_by_asc(entry::Column::ProducedDate)
+				.order_by_asc(entry::Column::ProducedTime)
+				.one(conn).await?
+				.map(|oldest| time::PrimitiveDateTime::new(
+					oldest.produced_date.0,
+					oldest.produced_time.0.unwrap_or(time::Time::MIDNIGHT),
+				)),
+		};
+		
+		let crawled_at = time::OffsetDateTime::now_utc();
+		let mut pages = Vec::new();
+		let mut visited = HashSet::new();
+		
+		while pages.len() < self.max_pages.into() {
+			if !visited.insert(url.clone()) {
+				tracing::info!(%url, "Link leads to an already visited page, stopping");
+				break;
+			}
+			
+			let (page_url, html) = match self.get(url).await {
+				Ok(got) => got,
+				//Keep what we've got, the next fetch resumes from there
+				Err(err) if !pages.is_empty() => {
+					tracing::warn!("Stopping early: {err:?}");
+					break;
+				},
+				Err(err) => return Err(err),
+			};
+			let next = link(&Html::parse_document(&html), &page_url, &link_selector, "href");
+			
+			if skip {
+				skip = false;
+			} else {
+				pages.push(Page {
+					url: page_url.into(),
+					html,
+				});
+			}
+			
+			let Some(next) = next else {
+				tracing::info!("No link found, stopping");
+				break;
+			};
+			url = next;
+		}
+		
+		tracing::info!(pages = pages.len(), "Crawled new pages");
+		
+		let crawl = Crawl {
+			crawled_at: time::PrimitiveDateTime::new(crawled_at.date(), crawled_at.time()),
+			oldest_known,
+			pages,
+		};
+		Ok(serde_json::to_string(&crawl)?.into())
+	}
+	
+	fn describe_options(&self) -> Vec<OptionDesc> {
+		vec![
+			describe_option("direction", OptionType::Choice(vec!["Forward".into(), "Backward".into()]), "Follow the next link, or the previous one to backfill"),
+			describe_option("next", OptionType::String, "CSS selector for the link to the next page"),
+			describe_option("previous", OptionType::String, "CSS selector for the link to the previous page"),
+			describe_option("title", OptionType::String, "CSS selector for the title"),
+			describe_option("image", OptionType::String, "CSS selector for the image to embed"),
+			describe_option("max_pages", OptionType::Integer, "How many new pages a single fetch gets at most"),
+		]
+	}
+	
+	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
+		let crawl = serde_json::from_str::<Crawl>(data)?;
+		let amount = crawl.pages.len();
+		crawl.pages.into_iter()
+			.enumerate()
+			.map(|(index, page)| {
+				//A second apart, so sorting by produced date keeps the reading order
+				let (latest, seconds_before) = match (self.direction, crawl.oldest_known) {
+					(Direction::Forward, _) => (crawl.crawled_at, amount - 1 - index),
+					(Direction::Backward, None) => (crawl.crawled_at, index),
+					(Direction::Backward, Some(oldest_known)) => (oldest_known, index + 1),
+				};
+				let produced = latest - time::Duration::seconds(seconds_before as i64);
+				let url = page.url.clone();
+				self.entry(page, produced).map_err(|e| e.context(format!("While parsing page \"{url}\"")))
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,190 @@
+use std::process::Stdio;
+
+use entities::prelude::*;
+use sea_orm::*;
+use tokio::io::AsyncWriteExt;
+use super::strategy::*;
+
+const DATE_FORMAT: &[time::format_description::FormatItem<'_>] = time::macros::format_description!("[year]-[month]-[day]");
+const TIME_FORMAT: &[time::format_description::FormatItem<'_>] = time::macros::format_description!("[hour]:[minute]:[second]");
+
+time::serde::format_description!(date_format, Date, DATE_FORMAT);
+time::serde::format_description!(time_format, Time, TIME_FORMAT);
+
+
+///What the command gets on stdin
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Request {
+	feed_url: String,
+	last_entry: Option<LastEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct LastEntry {
+	id: String,
+	view_url: String,
+	#[serde(with = "date_format")]
+	produced_date: time::Date,
+	#[serde(with = "time_format::option")]
+	produced_time: Option<time::Time>,
+}
+
+impl From<entry::Model> for LastEntry {
+	fn from(entry: entry::Model) -> Self {
+		Self {
+			id: entry.feed_entry_id,
+			view_url: entry.view_url,
+			produced_date: entry.produced_date.into(),
+			produced_time: entry.produced_time.into(),
+		}
+	}
+}
+
+///A line the command prints on stdout
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Line {
+	id: String,
+	title: String,
+	view_url: String,
+	#[serde(default)]
+	embed_url: Option<String>,
+	#[serde(default)]
+	canonical_id: Option<String>,
+	#[serde(with = "date_format")]
+	produced_date: time::Date,
+	#[serde(default, with = "time_format::option")]
+	produced_time: Option<time::Time>,
+}
+
+impl From<Line> for EntryInfo {
+	fn from(line: Line) -> EntryInfo {
+		let mut entry = EntryInfo::new(line.id, line.title, line.view_url, line.produced_date);
+		if let Some(embed_url) = line.embed_url {
+			entry.embed_url(embed_url);
+		}
+		if let Some(canonical_id) = line.canonical_id {
+			entry.canonical_id(canonical_id);
+		}
+		if let Some(time) = line.produced_time {
+			entry.produced_time(time);
+		}
+		entry
+	}
+}
+
+
+/**
+Runs an external command to fetch entries, so scrapers can be written in any language.
+
+The command gets run as `command [args...] <feed url>`, and gets a single line of JSON on stdin:
+```json
+{"feed_url": "https://example.com", "last_entry": null}
+```
+`last_entry` is the most recently produced entry of the feed if there is one:
+```json
+{"id": "3", "view_url": "https://example.com/3", "produced_date": "2024-01-03", "produced_time": "10:00:00"}
+```
+with `produced_time` being `null` when unknown.
+
+It should print one JSON object per line on stdout for every entry it found:
+```json
+{"id": "4", "title": "Four", "view_url": "https://example.com/4", "produced_date": "2024-01-04"}
+```
+with the optional fields `embed_url`, `canonical_id` and `produced_time` (`"HH:MM:SS"`, UTC).
+Empty lines get ignored. Stderr ends up in the fetch log, and exiting with a non-zero code makes the fetch fail.
+
+To run different commands, add more instances with [Named](crate::strategy::Named), e.g. one for every `command.<name>.ron` in the configuration folder.
+*/
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CommandStrategy {
+	///Executable to run, looked up in the PATH if it isn't a path
+	command: String,
+	///Passed before the feed url
+	args: Vec<String>,
+}
+
+impl CommandStrategy {
+	pub fn command(&mut self, command: impl Into<String>) -> &mut Self {
+		self.command = command.into();
+		self
+	}
+	
+	pub fn args(&mut self, args: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+		self.args = args.into_iter().map(Into::into).collect();
+		self
+	}
+}
+
+#[async_trait::async_trait]
+impl Strategy for CommandStrategy {
+	fn name(&self) -> &'static str {
+		"command"
+	}
+	
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
+		if self.command.is_empty() {
+			anyhow::bail!("No command configured");
+		}
+		
+		let last_entry = feed.find_related(entry::Entity)
+			.order_by_desc(entry::Column::ProducedDate)
+			.order_by_desc(entry::Column::ProducedTime)
+			.one(conn).await?;
+		let request = Request {
+			feed_url: feed.url.clone(),
+			last_entry: last_entry.map(LastEntry::from),
+		};
+		let mut request = serde_json::to_string(&request)?;
+		request.push('\n');
+		
+		let mut cmd = tokio::process::Command::new(&self.command);
+		cmd
+			.args(&self.args)
+			.arg(&feed.url)
+			.kill_on_drop(true)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+		;
+		
+		tracing::info!(?cmd, request, "Running command to fetch");
+		
+		let mut child = cmd.spawn()?;
+		let mut stdin = child.stdin.take().expect("stdin should be piped");
+		//Commands that don't read it can exit before it's written, that's fine
+		if let Err(err) = stdin.write_all(request.as_bytes()).await {
+			tracing::warn!(%err, "Could not write request to stdin");
+		}
+		drop(stdin);
+		
+		let out = child.wait_with_output().await?;
+		
+		//Formatted like this to get to print out the newlines instead of escaping them
+		tracing::info!("Command stderr:\n{}", String::from_utf8_lossy(&out.stderr));
+		
+		if !out.status.success() {
+			anyhow::bail!("Process returned non-successful exit code: {}", out.status);
+		}
+		
+		Ok(String::from_utf8(out.stdout)?.into())
+	}
+	
+	fn describe_options(&self) -> Vec<OptionDesc> {
+		vec![
+			describe_option("args", OptionType::Json, "Passed before the feed url, e.g. [\"--full\"]"),
+		]
+	}
+	
+	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
+		data
+			.lines()
+			.filter(|line| !line.trim().is_empty())
+			.map(|line| -> anyhow::Result<EntryInfo> {
+				let parsed = serde_json::from_str::<Line>(line).map_err(|e| {
+					anyhow::Error::from(e).context(format!("While parsing: \"{line}\""))
+				})?;
+				Ok(parsed.into())
+			})
+			.collect()
+	}
+}
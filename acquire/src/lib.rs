@@ -1,12 +1,33 @@
 ///A way to fetch entries for a single feed
 pub mod strategy;
 pub mod strategy_list;
+///Storing the configuration of strategies as RON files
+pub mod strategy_config;
+///Trying fetches again when they fail
+pub mod retry;
 pub mod yt_dlp;
+pub mod rss;
+///Crawling sites by following their next or previous links
+pub mod click_next;
+///Running external commands that print entries as JSON lines
+pub mod command;
 pub mod mock;
+///Recognising entries for the same content
+pub mod canonical;
+///Importing and exporting feeds in the OPML format
+pub mod opml;
 ///System for fetching a list of feeds
 pub mod batch;
+///Limits on how many fetches run at the same time
+pub mod limiter;
 ///System for keeping track of multiple fetch batches
 pub mod batch_tracker;
+///Automatically fetching feeds when they're due
+pub mod scheduler;
+///Finding feeds that stopped working or producing entries
+pub mod report;
+///How many entries get produced and consumed over time
+pub mod stats;
 
 pub use strategy_list::StrategyList;
 pub use strategy_list::RunError;
\ No newline at end of file
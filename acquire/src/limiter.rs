@@ -0,0 +1,112 @@
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+use tokio::sync::{Semaphore, OwnedSemaphorePermit};
+use entities::prelude::*;
+
+///How many fetches may run at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+	///In total, across all batches using the same [Limiter]
+	pub concurrency: usize,
+	///For the same host, or strategy when the feed url doesn't have a host
+	pub per_host: usize,
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Self {
+			concurrency: 8,
+			per_host: 2,
+		}
+	}
+}
+
+/**
+What the per host limit of `feed` gets applied to: the host of its url without "www.", or its strategy if that can't be found.
+Urls without a scheme get expanded like the strategies do, if they start with something that looks like a host.
+*/
+pub fn limit_key(feed: &feed::Model) -> String {
+	let url = feed.url.trim();
+	let has_scheme = url.contains("://");
+	let expanded = if has_scheme {
+		url.to_owned()
+	} else {
+		format!("https://{url}")
+	};
+	let host = reqwest::Url::parse(&expanded)
+		.ok()
+		.and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+		//Things like video ids aren't hosts
+		.filter(|host| has_scheme || host.contains('.'));
+	match host {
+		Some(host) => match host.strip_prefix("www.") {
+			Some(stripped) => stripped.to_owned(),
+			None => host,
+		},
+		None => format!("strategy {}", feed.strategy),
+	}
+}
+
+///Allows a fetch to happen while kept around
+#[derive(Debug)]
+pub struct Permit {
+	_key: OwnedSemaphorePermit,
+	_total: OwnedSemaphorePermit,
+}
+
+///Enforces [Limits], clones share the same limits
+#[derive(Debug, Clone)]
+pub struct Limiter {
+	limits: Limits,
+	total: Arc<Semaphore>,
+	per_key: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl Limiter {
+	///Limits of 0 get treated as 1, so things can still get fetched
+	pub fn new(limits: Limits) -> Self {
+		let limits = Limits {
+			concurrency: limits.concurrency.max(1),
+			per_host: limits.per_host.max(1),
+		};
+		Self {
+			limits,
+			total: Arc::new(Semaphore::new(limits.concurrency)),
+			per_key: Arc::default(),
+		}
+	}
+	
+	pub fn limits(&self) -> Limits {
+		self.limits
+	}
+	
+	fn key_semaphore(&self, key: String) -> Arc<Semaphore> {
+		let mut lock = self.per_key.lock().expect("per key semaphores shouldn't be poisoned");
+		//Only referenced by the map means there are no permits or waiting fetches, so it can be made again when needed
+		lock.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+		lock.entry(key)
+			.or_insert_with(|| Arc::new(Semaphore::new(self.limits.per_host)))
+			.clone()
+	}
+	
+	///How many keys currently have their own limit, keys without fetches get dropped
+	pub fn key_count(&self) -> usize {
+		self.per_key.lock().expect("per key semaphores shouldn't be poisoned").len()
+	}
+	
+	///Waits until a fetch with `key` (see [limit_key]) is allowed
+	pub async fn acquire(&self, key: String) -> Permit {
+		//The per key permit comes first so waiting fetches don't take up slots of the total
+		let key = self.key_semaphore(key).acquire_owned().await.expect("semaphores never get closed");
+		let total = self.total.clone().acquire_owned().await.expect("semaphores never get closed");
+		Permit {
+			_key: key,
+			_total: total,
+		}
+	}
+}
+
+impl Default for Limiter {
+	fn default() -> Self {
+		Self::new(Limits::default())
+	}
+}
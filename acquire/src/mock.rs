@@ -1,15 +1,24 @@
+use std::{collections::HashSet, sync::{Arc, Mutex}};
 use sea_orm::*;
 use entities::prelude::*;
 use tokio::sync::broadcast;
 use crate::strategy::{
 	Strategy,
-	EntryInfo
+	EntryInfo,
+	Fetched,
+	Validators,
 };
+use crate::retry::ErrorKind;
+
+const TRANSIENT_ERROR_PREFIX: &str = "Mock transient";
 
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MockStrat {
 	///Private field to prevent this from being created without a function call
 	_unused: (),
+	///Feeds that already failed once with the "flaky" url
+	#[serde(skip)]
+	flaky_failed: Arc<Mutex<HashSet<i32>>>,
 }
 
 #[async_trait::async_trait]
@@ -18,9 +27,16 @@ impl Strategy for MockStrat {
 		"Mock test"
 	}
 	
-	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<String> {
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
 		let mock_fetched = match feed.url.as_str() {
 			"ok" => "Mock ok".to_owned(),
+			"not modified" => {
+				let validators = Validators {
+					etag: Some("\"mock\"".to_owned()),
+					last_modified: None,
+				};
+				return Ok(Fetched::NotModified(validators));
+			},
 			"log ok" => {
 				tracing::info!("Mock fetch log");
 				"Mock logged".to_owned()
@@ -34,7 +50,17 @@ impl Strategy for MockStrat {
 				anyhow::bail!("Mock fetch log error")
 			},
 			"parse error" => "Mock don't parse this".to_owned(),
+			"hang" => std::future::pending().await,
+			"hang parse" => "Mock hang".to_owned(),
 			"fetch error" => anyhow::bail!("Mock fetch error"),
+			"transient error" => anyhow::bail!("{TRANSIENT_ERROR_PREFIX} error"),
+			"flaky" => {
+				let first_attempt = self.flaky_failed.lock().expect("lock poisoned").insert(feed.id);
+				if first_attempt {
+					anyhow::bail!("{TRANSIENT_ERROR_PREFIX} flaky error");
+				}
+				"Mock ok".to_owned()
+			},
 			entries if entries.contains('n') => {
 				let (n, new) = entries.split_once('n').expect("we just checked in the match guard");
 				let n = str::parse::<i32>(n)?;
@@ -52,7 +78,7 @@ impl Strategy for MockStrat {
 			},
 			_ => anyhow::bail!("Unknown url, don't know which mocked behaviour to use"),
 		};
-		Ok(mock_fetched)
+		Ok(mock_fetched.into())
 	}
 	
 	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
@@ -67,6 +93,7 @@ impl Strategy for MockStrat {
 				anyhow::bail!("Mock parse log error");
 			},
 			"parse error" => anyhow::bail!("This mock shouldn't be parsed"),
+			"Mock hang" => std::future::pending().await,
 			range if range.contains('-') => {
 				let (from, to) = range.split_once('-').expect("we just checked in the match guard");
 				let from = str::parse::<i32>(from)?;
@@ -86,6 +113,14 @@ impl Strategy for MockStrat {
 		
 		Ok(entries)
 	}
+	
+	fn classify_error(&self, err: &anyhow::Error) -> ErrorKind {
+		if err.to_string().starts_with(TRANSIENT_ERROR_PREFIX) {
+			ErrorKind::Transient
+		} else {
+			ErrorKind::Permanent
+		}
+	}
 }
 
 
@@ -125,12 +160,21 @@ impl Default for CommandStrat {
 	}
 }
 
+impl Clone for CommandStrat {
+	fn clone(&self) -> Self {
+		Self {
+			send: self.send.clone(),
+			recv: self.recv.resubscribe(),
+		}
+	}
+}
+
 #[async_trait::async_trait]
 impl Strategy for CommandStrat {
 	fn name(&self) -> &'static str {
 		"commandable mock"
 	}
-	async fn fetch(&self, _conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<String> {
+	async fn fetch(&self, _conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
 		let id = feed.id;
 		
 		let mut recv = self.recv.resubscribe();
@@ -141,7 +185,7 @@ impl Strategy for CommandStrat {
 			}
 		}
 		
-		Ok(id.to_string())
+		Ok(id.to_string().into())
 	}
 	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
 		let id = data.parse()?;
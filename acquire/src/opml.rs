@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use entities::prelude::*;
+use sea_orm::*;
+use quick_xml::{
+	Writer,
+	events::{Event, BytesDecl, BytesStart, BytesEnd, BytesText},
+};
+use crate::StrategyList;
+
+/*
+Folders (outlines without a feed url) map to tags, a feed in nested folders gets a tag for each of them.
+Feeds with multiple tags show up in the folder of each tag when exporting, importing merges them again by url.
+
+OPML 2.0: https://opml.org/spec2.opml
+*/
+
+///Not part of OPML, used to keep the strategy when exporting and importing again
+const STRATEGY_ATTRIBUTE: &str = "strategy";
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct Opml {
+	body: OpmlBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct OpmlBody {
+	#[serde(rename = "outline", default)]
+	outlines: Vec<Outline>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct Outline {
+	#[serde(rename = "@text")]
+	text: Option<String>,
+	#[serde(rename = "@title")]
+	title: Option<String>,
+	#[serde(rename = "@xmlUrl")]
+	xml_url: Option<String>,
+	#[serde(rename = "@strategy")]
+	strategy: Option<String>,
+	#[serde(rename = "outline", default)]
+	outlines: Vec<Outline>,
+}
+
+///A feed found in an OPML document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpmlFeed {
+	pub title: String,
+	pub url: String,
+	pub strategy: Option<String>,
+	///The folders it's in
+	pub tags: Vec<String>,
+}
+
+fn collect_feeds(outlines: Vec<Outline>, folders: &mut Vec<String>, feeds: &mut Vec<OpmlFeed>) {
+	for outline in outlines {
+		let title = outline.title.or(outline.text).unwrap_or_default().trim().to_owned();
+		match outline.xml_url {
+			Some(url) => {
+				feeds.push(OpmlFeed {
+					title,
+					url: url.trim().to_owned(),
+					strategy: outline.strategy,
+					tags: folders.clone(),
+				});
+			},
+			None => {
+				//Untitled folders don't make for useful tags
+				let is_tag = !title.is_empty() && !folders.contains(&title);
+				if is_tag {
+					folders.push(title);
+				}
+				collect_feeds(outline.outlines, folders, feeds);
+				if is_tag {
+					folders.pop();
+				}
+			},
+		}
+	}
+}
+
+pub fn parse(data: &str) -> Result<Vec<OpmlFeed>, quick_xml::DeError> {
+	let opml = quick_xml::de::from_str::<Opml>(data)?;
+	let mut feeds = Vec::new();
+	collect_feeds(opml.body.outlines, &mut Vec::new(), &mut feeds);
+	Ok(feeds)
+}
+
+fn is_youtube_page(url: &str) -> bool {
+	let url = if url.contains("://") { url.to_owned() } else { format!("https://{url}") };
+	let Ok(url) = reqwest::Url::parse(&url) else {
+		return false;
+	};
+	let Some(host) = url.host_str() else {
+		return false;
+	};
+	let host = host.to_lowercase();
+	let is_youtube = host == "youtube.com" || host.ends_with(".youtube.com");
+	//YouTube also has actual RSS feeds
+	is_youtube && !url.path().starts_with("/feeds/")
+}
+
+///Which strategy in `strats` to use for a feed at `url`, [None] if none of the ones that could work are available
+pub fn guess_strategy(url: &str, strats: &StrategyList) -> Option<String> {
+	let candidates: &[&str] = if is_youtube_page(url) {
+		&["yt-dlp", "rss"]
+	} else {
+		&["rss"]
+	};
+	candidates.iter()
+		.find_map(|name| strats.get_by_name(name).ok())
+		.map(|strat| strat.name().to_owned())
+}
+
+
+///What happened while importing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+	pub created: usize,
+	///Feeds with the same url that already existed, their tags still get added
+	pub existing: usize,
+	pub tagged: usize,
+	///Urls of feeds for which no strategy could be found
+	pub skipped: Vec<String>,
+}
+
+impl std::fmt::Display for ImportSummary {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "Created {} feeds, {} already existed, added {} tags", self.created, self.existing, self.tagged)?;
+		if !self.skipped.is_empty() {
+			write!(f, ", skipped {} without a strategy: {}", self.skipped.len(), self.skipped.join(", "))?;
+		}
+		Ok(())
+	}
+}
+
+async fn tag_id(conn: &DatabaseTransaction, title: &str, cache: &mut HashMap<String, i32>) -> Result<i32, DbErr> {
+	if let Some(id) = cache.get(title) {
+		return Ok(*id);
+	}
+	let existing = tag::Entity::find()
+		.filter(tag::Column::Title.eq(title))
+		.one(conn).await?;
+	let id = match existing {
+		Some(tag) => tag.id,
+		None => {
+			let mut new = tag::ActiveModel::new();
+			new.title = Set(title.to_owned());
+			new.insert(conn).await?.id
+		},
+	};
+	cache.insert(title.to_owned(), id);
+	Ok(id)
+}
+
+///Creates the feeds and tags that don't exist yet, all at once or not at all
+pub async fn import(conn: &DatabaseConnection, strats: &StrategyList, feeds: Vec<OpmlFeed>) -> Result<ImportSummary, DbErr> {
+	let feeds = feeds.into_iter()
+		.map(|feed| {
+			let exported = feed.strategy.as_deref()
+				.and_then(|name| strats.get_by_name(name).ok())
+				.map(|strat| strat.name().to_owned());
+			let strategy = exported.or_else(|| guess_strategy(&feed.url, strats));
+			(feed, strategy)
+		})
+		.collect::<Vec<_>>();
+	
+	conn.transaction::<_,_,DbErr>(|conn| Box::pin(async move {
+		let mut summary = ImportSummary::default();
+		let mut tag_ids = HashMap::new();
+		
+		for (opml_feed, strategy) in feeds {
+			let existing = feed::Entity::find()
+				.filter(feed::Column::Url.eq(&opml_feed.url))
+				.one(conn).await?;
+			let feed_id = match (existing, strategy) {
+				(Some(feed), _) => {
+					summary.existing += 1;
+					feed.id
+				},
+				(None, None) => {
+					summary.skipped.push(opml_feed.url);
+					continue;
+				},
+				(None, Some(strategy)) => {
+					let mut new = feed::ActiveModel::new();
+					new.name = Set(opml_feed.title);
+					new.url = Set(opml_feed.url);
+					new.strategy = Set(strategy);
+					summary.created += 1;
+					new.insert(conn).await?.id
+				},
+			};
+			
+			for title in &opml_feed.tags {
+				let tag_id = tag_id(conn, title, &mut tag_ids).await?;
+				let already_tagged = feed_tag::Entity::find()
+					.filter(feed_tag::Column::FeedId.eq(feed_id))
+					.filter(feed_tag::Column::TagId.eq(tag_id))
+					.count(conn).await? > 0;
+				if already_tagged {
+					continue;
+				}
+				let mut new = feed_tag::ActiveModel::new();
+				new.feed_id = Set(feed_id);
+				new.tag_id = Set(tag_id);
+				new.insert(conn).await?;
+				summary.tagged += 1;
+			}
+		}
+		
+		Ok(summary)
+	})).await.map_err(|err| match err {
+		TransactionError::Connection(err) => err,
+		TransactionError::Transaction(err) => err,
+	})
+}
+
+
+fn write_feed(writer: &mut Writer<Vec<u8>>, feed: &feed::Model) -> std::io::Result<()> {
+	let outline = BytesStart::new("outline").with_attributes([
+		("type", "rss"),
+		("text", feed.name.as_str()),
+		("title", feed.name.as_str()),
+		("xmlUrl", feed.url.as_str()),
+		(STRATEGY_ATTRIBUTE, feed.strategy.as_str()),
+	]);
+	writer.write_event(Event::Empty(outline))
+}
+
+fn write_document(untagged: &[feed::Model], folders: &[(tag::Model, Vec<feed::Model>)]) -> std::io::Result<String> {
+	let mut writer = Writer::new_with_indent(Vec::new(), b'\t', 1);
+	
+	writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+	writer.write_event(Event::Start(BytesStart::new("opml").with_attributes([("version", "2.0")])))?;
+	
+	writer.write_event(Event::Start(BytesStart::new("head")))?;
+	writer.write_event(Event::Start(BytesStart::new("title")))?;
+	writer.write_event(Event::Text(BytesText::new("Fusta Femas feeds")))?;
+	writer.write_event(Event::End(BytesEnd::new("title")))?;
+	writer.write_event(Event::End(BytesEnd::new("head")))?;
+	
+	writer.write_event(Event::Start(BytesStart::new("body")))?;
+	for (tag, feeds) in folders {
+		let folder = BytesStart::new("outline").with_attributes([
+			("text", tag.title.as_str()),
+			("title", tag.title.as_str()),
+		]);
+		writer.write_event(Event::Start(folder))?;
+		for feed in feeds {
+			write_feed(&mut writer, feed)?;
+		}
+		writer.write_event(Event::End(BytesEnd::new("outline")))?;
+	}
+	for feed in untagged {
+		write_feed(&mut writer, feed)?;
+	}
+	writer.write_event(Event::End(BytesEnd::new("body")))?;
+	
+	writer.write_event(Event::End(BytesEnd::new("opml")))?;
+	
+	Ok(String::from_utf8(writer.into_inner()).expect("quick-xml should write valid utf8"))
+}
+
+///An OPML document with all the feeds, grouped by tag
+pub async fn export(conn: &DatabaseConnection) -> Result<String, DbErr> {
+	let folders = tag::Entity::find()
+		.order_by_asc(tag::Column::Title)
+		.find_with_related(feed::Entity)
+		.all(conn).await?
+		.into_iter()
+		.filter(|(_, feeds)| !feeds.is_empty())
+		.collect::<Vec<_>>();
+	
+	let untagged = feed::Entity::find()
+		.filter(feed::Column::Id.not_in_subquery(
+			sea_query::Query::select()
+				.column(feed_tag::Column::FeedId)
+				.from(feed_tag::Entity)
+				.to_owned()
+		))
+		.order_by_asc(feed::Column::Name)
+		.all(conn).await?;
+	
+	Ok(write_document(&untagged, &folders).expect("writing to a Vec shouldn't fail"))
+}
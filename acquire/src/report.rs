@@ -0,0 +1,180 @@
+use std::{collections::HashMap, time::Duration};
+use serde::{Deserialize, Serialize};
+use entities::prelude::*;
+use sea_orm::{*, DatabaseConnection as Db, sea_query::{Query, Expr, Func}};
+use crate::scheduler::{FAILURES, consecutive_failures};
+
+
+///How bad a feed is doing, worst last
+#[derive(Debug, Clone,Copy, PartialEq,Eq, PartialOrd,Ord, Serialize, Deserialize)]
+pub enum Severity {
+	Healthy,
+	///Stopped producing entries, or slowed down a lot
+	Stale,
+	///Fetching keeps failing
+	Failing,
+}
+
+impl std::fmt::Display for Severity {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let str = match self {
+			Self::Healthy => "✅ Healthy",
+			Self::Stale => "💤 Stale",
+			Self::Failing => "❌ Failing",
+		};
+		write!(f, "{str}")
+	}
+}
+
+///When feeds count as stale or failing
+#[derive(Debug, Clone)]
+pub struct ReportSettings {
+	///Consecutive failed fetches after which a feed counts as failing
+	pub failing_after: u32,
+	///A feed is stale when it didn't produce anything for this many times its average posting interval
+	pub stale_factor: u32,
+	///Feeds are never stale before this, so frequently posting feeds don't get flagged after one quiet day
+	pub min_stale_after: Duration,
+}
+
+impl Default for ReportSettings {
+	fn default() -> Self {
+		Self {
+			failing_after: 3,
+			stale_factor: 3,
+			min_stale_after: Duration::from_secs(14 * 24 * 60 * 60),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct FeedHealth {
+	pub feed_id: i32,
+	pub feed_name: String,
+	///When the last fetch that didn't fail happened
+	pub last_success: Option<time::PrimitiveDateTime>,
+	///Failed fetches since the last one that didn't fail
+	pub consecutive_failures: u32,
+	pub last_produced: Option<time::Date>,
+	///Average time between the produced dates of the entries
+	pub average_interval: Option<Duration>,
+	pub stale: bool,
+	pub severity: Severity,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct EntryStats {
+	feed_id: i32,
+	count: i64,
+	first: Option<time::Date>,
+	last: Option<time::Date>,
+}
+
+fn average_interval(stats: &EntryStats) -> Option<Duration> {
+	let (first, last) = (stats.first?, stats.last?);
+	if stats.count < 2 {
+		return None;
+	}
+	let intervals = u32::try_from(stats.count - 1).unwrap_or(u32::MAX);
+	Some((last - first).unsigned_abs() / intervals)
+}
+
+fn is_stale(settings: &ReportSettings, last_produced: Option<time::Date>, average_interval: Option<Duration>, now: time::PrimitiveDateTime) -> bool {
+	let Some(last_produced) = last_produced else {
+		return true;
+	};
+	let allowed = average_interval
+		.map(|interval| interval.saturating_mul(settings.stale_factor))
+		.unwrap_or_default()
+		.max(settings.min_stale_after);
+	let since = now.date() - last_produced;
+	since.is_positive() && since.unsigned_abs() > allowed
+}
+
+///When the last fetch that didn't fail happened, by feed id
+async fn last_successes(db: &Db) -> Result<HashMap<i32, time::PrimitiveDateTime>, DbErr> {
+	let last_ids = Query::select()
+		.expr(Func::max(Expr::col(fetch::Column::Id)))
+		.from(fetch::Entity)
+		.and_where(fetch::Column::Status.is_not_in(FAILURES))
+		.group_by_col(fetch::Column::FeedId)
+		.to_owned();
+	let successes = fetch::Entity::find()
+		.select_only()
+		.column(fetch::Column::FeedId)
+		.column(fetch::Column::CreatedAt)
+		.filter(fetch::Column::Id.in_subquery(last_ids))
+		.into_tuple::<(i32, time_fields::PrimitiveDateTime)>()
+		.all(db).await?;
+	Ok(successes.into_iter().map(|(feed_id, created_at)| (feed_id, created_at.0)).collect())
+}
+
+///Entry count and produced date range, by feed id
+async fn entry_stats(db: &Db) -> Result<HashMap<i32, EntryStats>, DbErr> {
+	let stats = entry::Entity::find()
+		.select_only()
+		.column(entry::Column::FeedId)
+		.column_as(entry::Column::Id.count(), "count")
+		.column_as(entry::Column::ProducedDate.min(), "first")
+		.column_as(entry::Column::ProducedDate.max(), "last")
+		.group_by(entry::Column::FeedId)
+		.into_model::<EntryStats>()
+		.all(db).await?;
+	Ok(stats.into_iter().map(|stats| (stats.feed_id, stats)).collect())
+}
+
+fn health(settings: &ReportSettings, feed_id: i32, feed_name: String, last_success: Option<time::PrimitiveDateTime>, consecutive_failures: u32, stats: Option<&EntryStats>, now: time::PrimitiveDateTime) -> FeedHealth {
+	let average_interval = stats.and_then(average_interval);
+	let last_produced = stats.and_then(|stats| stats.last);
+	
+	let stale = is_stale(settings, last_produced, average_interval, now);
+	let severity = if consecutive_failures >= settings.failing_after {
+		Severity::Failing
+	} else if stale {
+		Severity::Stale
+	} else {
+		Severity::Healthy
+	};
+	
+	FeedHealth {
+		feed_id,
+		feed_name,
+		last_success,
+		consecutive_failures,
+		last_produced,
+		average_interval,
+		stale,
+		severity,
+	}
+}
+
+///The health of every feed at `now` (UTC), worst first
+pub async fn feed_health(db: &Db, settings: &ReportSettings, now: time::PrimitiveDateTime) -> Result<Vec<FeedHealth>, DbErr> {
+	let feeds = feed::Entity::find()
+		.select_only()
+		.column(feed::Column::Id)
+		.column(feed::Column::Name)
+		.into_tuple::<(i32, String)>()
+		.all(db).await?;
+	let last_successes = last_successes(db).await?;
+	let consecutive_failures = consecutive_failures(db).await?;
+	let entry_stats = entry_stats(db).await?;
+	
+	let mut report = feeds.into_iter().map(|(id, name)| health(
+		settings,
+		id,
+		name,
+		last_successes.get(&id).copied(),
+		consecutive_failures.get(&id).copied().unwrap_or(0),
+		entry_stats.get(&id),
+		now,
+	)).collect::<Vec<_>>();
+	
+	report.sort_by(|a, b| {
+		b.severity.cmp(&a.severity)
+			.then(b.consecutive_failures.cmp(&a.consecutive_failures))
+			//Longest without entries first, None sorts first already
+			.then(a.last_produced.cmp(&b.last_produced))
+	});
+	Ok(report)
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+use reqwest::StatusCode;
+
+///Whether a failed fetch might succeed when trying again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+	///Probably temporary, like a network hiccup or an overloaded server
+	Transient,
+	///Trying again won't help
+	Permanent,
+}
+
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+	status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::REQUEST_TIMEOUT
+}
+
+///The classification [Strategy::classify_error](crate::strategy::Strategy::classify_error) uses by default:
+///connection problems, timeouts and some HTTP statuses are transient, everything else is permanent
+pub fn classify(err: &anyhow::Error) -> ErrorKind {
+	for cause in err.chain() {
+		let Some(err) = cause.downcast_ref::<reqwest::Error>() else {
+			continue;
+		};
+		if let Some(status) = err.status() {
+			if is_transient_status(status) {
+				return ErrorKind::Transient;
+			}
+			return ErrorKind::Permanent;
+		}
+		if err.is_timeout() || err.is_connect() || err.is_request() || err.is_body() {
+			return ErrorKind::Transient;
+		}
+	}
+	ErrorKind::Permanent
+}
+
+///How often and when fetching gets tried again after a transient error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+	///Including the first one, treated as 1 if it's 0
+	pub max_attempts: u32,
+	///How long to wait before the second attempt, doubles for every attempt after that
+	pub backoff: Duration,
+}
+
+impl RetryPolicy {
+	pub fn new(max_attempts: u32, backoff: Duration) -> Self {
+		Self {
+			max_attempts,
+			backoff,
+		}
+	}
+	
+	///Only tries once
+	pub fn never() -> Self {
+		Self::new(1, Duration::ZERO)
+	}
+	
+	///Whether to try again after failed attempt number `attempt` (starting at 1)
+	pub fn should_retry(&self, attempt: u32, kind: ErrorKind) -> bool {
+		kind == ErrorKind::Transient && attempt < self.max_attempts
+	}
+	
+	///How long to wait after failed attempt number `attempt` (starting at 1)
+	pub fn backoff_after(&self, attempt: u32) -> Duration {
+		let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+		self.backoff.saturating_mul(factor)
+	}
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self::never()
+	}
+}
@@ -0,0 +1,250 @@
+use entities::prelude::*;
+use sea_orm::*;
+use quick_xml::events::Event;
+use reqwest::{header::{self, HeaderMap}, StatusCode};
+use super::strategy::*;
+
+/*
+Both formats get handled by the same strategy, since users usually don't know (or care) which one a site serves.
+Which one it is gets decided by the root element.
+
+RSS 2.0: https://www.rssboard.org/rss-specification
+Atom: https://datatracker.ietf.org/doc/html/rfc4287
+*/
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct Rss {
+	channel: RssChannel,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct RssChannel {
+	#[serde(rename = "item", default)]
+	items: Vec<RssItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct RssItem {
+	title: Option<String>,
+	link: Option<String>,
+	guid: Option<RssGuid>,
+	#[serde(rename = "pubDate")]
+	pub_date: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct RssGuid {
+	#[serde(rename = "$text")]
+	value: String,
+}
+
+impl TryFrom<RssItem> for EntryInfo {
+	type Error = anyhow::Error;
+	
+	fn try_from(item: RssItem) -> anyhow::Result<EntryInfo> {
+		let guid = item.guid.map(|guid| guid.value.trim().to_owned());
+		let link = item.link.map(|link| link.trim().to_owned());
+		
+		let Some(id) = guid.clone().or_else(|| link.clone()) else {
+			anyhow::bail!("Item has neither a guid nor a link to identify it by");
+		};
+		let Some(view_url) = link.or(guid) else {
+			anyhow::bail!("Item has neither a link nor a guid to view it at");
+		};
+		let Some(pub_date) = item.pub_date else {
+			anyhow::bail!("Item has no pubDate");
+		};
+		let produced = time::OffsetDateTime::parse(pub_date.trim(), &time::format_description::well_known::Rfc2822)?;
+		
+		Ok(entry_info(id, item.title.unwrap_or_default(), view_url, produced))
+	}
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct AtomFeed {
+	#[serde(rename = "entry", default)]
+	entries: Vec<AtomEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct AtomEntry {
+	id: String,
+	title: Option<AtomText>,
+	#[serde(rename = "link", default)]
+	links: Vec<AtomLink>,
+	updated: Option<String>,
+	published: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct AtomText {
+	#[serde(rename = "$text", default)]
+	value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct AtomLink {
+	#[serde(rename = "@href")]
+	href: String,
+	#[serde(rename = "@rel")]
+	rel: Option<String>,
+}
+
+impl TryFrom<AtomEntry> for EntryInfo {
+	type Error = anyhow::Error;
+	
+	fn try_from(entry: AtomEntry) -> anyhow::Result<EntryInfo> {
+		//A link without rel is an alternate link according to the spec
+		let alternate = entry.links.iter()
+			.find(|link| matches!(link.rel.as_deref(), None | Some("alternate")));
+		let Some(view_url) = alternate.or(entry.links.first()).map(|link| link.href.trim().to_owned()) else {
+			anyhow::bail!("Entry has no link to view it at");
+		};
+		let Some(date) = entry.updated.or(entry.published) else {
+			anyhow::bail!("Entry has neither an updated nor a published date");
+		};
+		let produced = time::OffsetDateTime::parse(date.trim(), &time::format_description::well_known::Rfc3339)?;
+		let title = entry.title.map(|title| title.value).unwrap_or_default();
+		
+		Ok(entry_info(entry.id.trim().to_owned(), title, view_url, produced))
+	}
+}
+
+
+fn entry_info(id: String, title: String, view_url: String, produced: time::OffsetDateTime) -> EntryInfo {
+	let produced = produced.to_offset(time::UtcOffset::UTC);
+	let mut entry = EntryInfo::new(id, title.trim().to_owned(), view_url, produced.date());
+	entry.produced_time(produced.time());
+	entry
+}
+
+///Finds the name of the root element, so we know which format we're dealing with
+fn root_name(data: &str) -> anyhow::Result<String> {
+	let mut reader = quick_xml::Reader::from_str(data);
+	loop {
+		match reader.read_event()? {
+			Event::Start(start) | Event::Empty(start) => {
+				let name = start.local_name();
+				break Ok(String::from_utf8_lossy(name.as_ref()).into_owned());
+			},
+			Event::Eof => anyhow::bail!("Document has no root element"),
+			_ => (),
+		}
+	}
+}
+
+
+fn validators_from_headers(headers: &HeaderMap) -> Validators {
+	let get = |name| {
+		headers.get(name)
+			.and_then(|value| value.to_str().ok())
+			.map(|value| value.to_owned())
+	};
+	Validators {
+		etag: get(header::ETAG),
+		last_modified: get(header::LAST_MODIFIED),
+	}
+}
+
+
+///Fetches an RSS 2.0 or Atom feed over HTTP(S)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RssStrategy {
+	user_agent: String,
+	#[serde(skip)]
+	client: reqwest::Client,
+}
+
+impl Default for RssStrategy {
+	fn default() -> Self {
+		Self {
+			user_agent: "Fusta Femas".into(),
+			client: reqwest::Client::default(),
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl Strategy for RssStrategy {
+	fn name(&self) -> &'static str {
+		"rss"
+	}
+	
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
+		let mut url = feed.url.to_owned();
+		if !url.contains("://") {
+			url = format!("https://{url}");
+			tracing::info!(url, "Expanded url:")
+		}
+		
+		let validators = Validators::last_for_feed(conn, feed).await?;
+		
+		let mut request = self.client.get(&url)
+			.header(header::USER_AGENT, &self.user_agent);
+		if let Some(etag) = &validators.etag {
+			request = request.header(header::IF_NONE_MATCH, etag);
+		}
+		if let Some(last_modified) = &validators.last_modified {
+			request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+		}
+		
+		tracing::info!(?request, "Requesting feed");
+		
+		let response = request.send().await?;
+		
+		tracing::info!(status = %response.status(), headers = ?response.headers(), "Received response");
+		
+		if response.status() == StatusCode::NOT_MODIFIED {
+			//A 304 should repeat the validators, but not every server does
+			let new_validators = validators_from_headers(response.headers()).or(validators);
+			return Ok(Fetched::NotModified(new_validators));
+		}
+		
+		let response = response.error_for_status()?;
+		let new_validators = validators_from_headers(response.headers());
+		
+		Ok(Fetched::Content(response.text().await?, new_validators))
+	}
+	
+	fn describe_options(&self) -> Vec<OptionDesc> {
+		vec![
+			describe_option("user_agent", OptionType::String, "Sent along with the request, for sites that block unknown ones"),
+		]
+	}
+	
+	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
+		let root = root_name(data)?;
+		tracing::info!(root, "Parsing feed");
+		
+		match root.as_str() {
+			"rss" => {
+				let rss = quick_xml::de::from_str::<Rss>(data)?;
+				rss.channel.items.into_iter()
+					.enumerate()
+					//pubDate is optional in RSS, so one item without it shouldn't fail the whole feed
+					.filter(|(index, item)| {
+						if item.pub_date.is_none() {
+							tracing::warn!(index, title = ?item.title, "Skipping item without pubDate");
+						}
+						item.pub_date.is_some()
+					})
+					.map(|(index, item)| {
+						EntryInfo::try_from(item).map_err(|e| e.context(format!("While parsing item {index}")))
+					})
+					.collect()
+			},
+			"feed" => {
+				let atom = quick_xml::de::from_str::<AtomFeed>(data)?;
+				atom.entries.into_iter()
+					.enumerate()
+					.map(|(index, entry)| {
+						EntryInfo::try_from(entry).map_err(|e| e.context(format!("While parsing entry {index}")))
+					})
+					.collect()
+			},
+			other => anyhow::bail!("Unsupported root element \"{other}\", expected \"rss\" or \"feed\""),
+		}
+	}
+}
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, time::Duration};
+use thiserror::Error;
+use tokio::time::MissedTickBehavior;
+use entities::prelude::*;
+use sea_orm::{*, DatabaseConnection as Db, sea_query::{Query, Expr, Func, Alias, SimpleExpr}};
+use crate::{
+	StrategyList,
+	batch_tracker::{BatchTracker, AwaitFetchError},
+};
+
+///Failing feeds wait at most 2^this times their interval
+pub const MAX_BACKOFF_EXPONENT: u32 = 6;
+
+#[derive(Debug, Error)]
+pub enum CycleError {
+	#[error(transparent)]
+	Db(#[from] DbErr),
+	#[error(transparent)]
+	AwaitFetch(#[from] AwaitFetchError),
+}
+
+///Statuses of fetches that went wrong
+pub(crate) const FAILURES: [fetch::Status; 4] = {
+	use fetch::Status::*;
+	[FetchError, ParseError, EntryUpdateError, Timeout]
+};
+
+///How many fetches failed since the last one that didn't, by feed id
+pub(crate) async fn consecutive_failures(db: &Db) -> Result<HashMap<i32, u32>, DbErr> {
+	let success = Alias::new("success");
+	let last_success = Query::select()
+		.expr(Func::max(Expr::col((success.clone(), fetch::Column::Id))))
+		.from_as(fetch::Entity, success.clone())
+		.and_where(Expr::col((success.clone(), fetch::Column::FeedId)).equals((fetch::Entity, fetch::Column::FeedId)))
+		.and_where(Expr::col((success, fetch::Column::Status)).is_not_in(FAILURES.map(|status| status.into_value())))
+		.to_owned();
+	//Everything after the last success failed, feeds without one only have failures
+	let after_last_success = Func::coalesce([
+		SimpleExpr::SubQuery(None, Box::new(last_success.into_sub_query_statement())),
+		Expr::val(0).into(),
+	]);
+	let failures = fetch::Entity::find()
+		.select_only()
+		.column(fetch::Column::FeedId)
+		.column_as(fetch::Column::Id.count(), "count")
+		.filter(Expr::col((fetch::Entity, fetch::Column::Id)).gt(after_last_success))
+		.group_by(fetch::Column::FeedId)
+		.into_tuple::<(i32, i64)>()
+		.all(db).await?;
+	Ok(failures.into_iter().map(|(feed_id, count)| (feed_id, u32::try_from(count).unwrap_or(u32::MAX))).collect())
+}
+
+///How long to wait since the last fetch after `failures` failed ones, or [None] if the feed shouldn't get fetched automatically
+fn wait_time(feed: &feed::Model, default_interval: Duration, failures: u32) -> Option<Duration> {
+	let interval = match feed.refresh_interval {
+		None => default_interval,
+		Some(minutes) if minutes <= 0 => return None,
+		Some(minutes) => Duration::from_secs(minutes as u64 * 60),
+	};
+	
+	let backoff = 2u32.pow(failures.min(MAX_BACKOFF_EXPONENT));
+	
+	Some(interval.saturating_mul(backoff))
+}
+
+///Ids of the feeds that should get fetched at `now` (UTC)
+pub async fn due_feeds(db: &Db, default_interval: Duration, now: time::PrimitiveDateTime) -> Result<Vec<i32>, DbErr> {
+	let feeds = feed::Entity::find().all(db).await?;
+	let last_ids = Query::select()
+		.expr(Func::max(Expr::col(fetch::Column::Id)))
+		.from(fetch::Entity)
+		.group_by_col(fetch::Column::FeedId)
+		.to_owned();
+	let last_fetched = fetch::Entity::find()
+		.select_only()
+		.column(fetch::Column::FeedId)
+		.column(fetch::Column::CreatedAt)
+		.filter(fetch::Column::Id.in_subquery(last_ids))
+		.into_tuple::<(i32, time_fields::PrimitiveDateTime)>()
+		.all(db).await?
+		.into_iter()
+		.collect::<HashMap<_, _>>();
+	let failures = consecutive_failures(db).await?;
+	
+	let mut due = Vec::new();
+	for feed in feeds {
+		let failures = failures.get(&feed.id).copied().unwrap_or(0);
+		let Some(wait) = wait_time(&feed, default_interval, failures) else {
+			continue;
+		};
+		let is_due = match last_fetched.get(&feed.id) {
+			None => true,
+			Some(last) => now - last.0 >= wait,
+		};
+		if is_due {
+			due.push(feed.id);
+		}
+	}
+	
+	Ok(due)
+}
+
+///Periodically fetches all the feeds that are due
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+	///Time between feeds being fetched if they don't specify their own
+	pub default_interval: Duration,
+	///Time between checks for due feeds
+	pub period: Duration,
+}
+
+impl Scheduler {
+	pub fn new(default_interval: Duration, period: Duration) -> Self {
+		Self {
+			default_interval,
+			period,
+		}
+	}
+	
+	///Queues the due feeds in `tracker` and waits for them to finish fetching
+	pub async fn cycle(&self, tracker: &BatchTracker, db: &Db, strats: &StrategyList) -> Result<(), CycleError> {
+		let now = time::OffsetDateTime::now_utc();
+		let now = time::PrimitiveDateTime::new(now.date(), now.time());
+		
+		let feeds = due_feeds(db, self.default_interval, now).await?;
+		if feeds.is_empty() {
+			return Ok(());
+		}
+		
+		tracing::info!(amount = feeds.len(), "Queueing due feeds");
+		let id = tracker.queue_fetches(feeds, db.clone(), strats.clone()).await?;
+		//Waiting makes sure the next cycle doesn't queue feeds that are still being fetched
+		tracker.await_fetch(id).await?;
+		
+		Ok(())
+	}
+	
+	///Runs forever, so should probably get spawned as a separate task. Returns right away if the period is zero.
+	pub async fn run(self, tracker: BatchTracker, db: Db, strats: StrategyList) {
+		if self.period.is_zero() {
+			tracing::warn!("Not running the scheduler, since its period is zero");
+			return;
+		}
+		let mut interval = tokio::time::interval(self.period);
+		interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+		
+		loop {
+			interval.tick().await;
+			if let Err(err) = self.cycle(&tracker, &db, &strats).await {
+				tracing::error!(?err, "Error running scheduled fetches");
+			}
+		}
+	}
+}
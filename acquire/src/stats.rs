@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use entities::prelude::*;
+use sea_orm::{*, DatabaseConnection as Db};
+
+
+///Size of the buckets entries get counted in
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Serialize, Deserialize)]
+pub enum Period {
+	#[default]
+	Day,
+	///Starting on monday
+	Week,
+}
+
+impl Period {
+	///Start of the bucket `date` falls in
+	pub fn bucket_start(self, date: time::Date) -> time::Date {
+		match self {
+			Self::Day => date,
+			Self::Week => date - time::Duration::days(date.weekday().number_days_from_monday().into()),
+		}
+	}
+	
+	fn length(self) -> time::Duration {
+		match self {
+			Self::Day => time::Duration::DAY,
+			Self::Week => time::Duration::WEEK,
+		}
+	}
+}
+
+///What to make separate [Series] for
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Serialize, Deserialize)]
+pub enum Grouping {
+	///A single series with every entry
+	#[default]
+	All,
+	Feed,
+	///Entries count for every tag of their feed
+	Tag,
+}
+
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct Bucket {
+	pub start: time::Date,
+	///Entries with a produced date in this bucket
+	pub produced: u64,
+	///Entries marked viewed in this bucket, duplicates only count once
+	pub consumed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct Series {
+	pub name: String,
+	pub buckets: Vec<Bucket>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct EntryDates {
+	feed_id: i32,
+	produced_date: time::Date,
+	viewed_at: Option<time::PrimitiveDateTime>,
+	canonical_id: Option<String>,
+}
+
+///Names of the groups, and the groups each feed is in
+async fn groups(db: &Db, grouping: Grouping) -> Result<(HashMap<i32, String>, HashMap<i32, Vec<i32>>), DbErr> {
+	match grouping {
+		Grouping::All => Ok((HashMap::from([(0, "All feeds".to_owned())]), HashMap::new())),
+		Grouping::Feed => {
+			let names = feed::Entity::find().all(db).await?
+				.into_iter()
+				.map(|feed| (feed.id, feed.name))
+				.collect();
+			Ok((names, HashMap::new()))
+		},
+		Grouping::Tag => {
+			let names = tag::Entity::find().all(db).await?
+				.into_iter()
+				.map(|tag| (tag.id, tag.title))
+				.collect();
+			let mut feed_groups = HashMap::<i32, Vec<i32>>::new();
+			for feed_tag in feed_tag::Entity::find().all(db).await? {
+				feed_groups.entry(feed_tag.feed_id).or_default().push(feed_tag.tag_id);
+			}
+			Ok((names, feed_groups))
+		},
+	}
+}
+
+///Produced and consumed entries per `period` from `from` up to and including `to`, one [Series] per group with entries, sorted by name
+pub async fn consumption(db: &Db, period: Period, grouping: Grouping, from: time::Date, to: time::Date) -> Result<Vec<Series>, DbErr> {
+	let first = period.bucket_start(from);
+	let starts = std::iter::successors(Some(first), |start| start.checked_add(period.length()))
+		.take_while(|start| *start <= to)
+		.collect::<Vec<_>>();
+	let index = |date: time::Date| -> Option<usize> {
+		if date < from || date > to {
+			return None;
+		}
+		let index = (period.bucket_start(date) - first).whole_days() / period.length().whole_days();
+		usize::try_from(index).ok()
+	};
+	
+	let from_time = time::PrimitiveDateTime::new(from, time::Time::MIDNIGHT);
+	let entries = entry::Entity::find()
+		.select_only()
+		.columns([entry::Column::FeedId, entry::Column::ProducedDate, entry::Column::ViewedAt, entry::Column::CanonicalId])
+		.filter(
+			Condition::any()
+				.add(entry::Column::ProducedDate.between(from, to))
+				.add(entry::Column::ViewedAt.gte(from_time))
+		)
+		//Oldest first, so the view of duplicates counts for the oldest one
+		.order_by_asc(entry::Column::Id)
+		.into_model::<EntryDates>()
+		.all(db).await?;
+	
+	let (names, feed_groups) = groups(db, grouping).await?;
+	let mut counts = HashMap::<i32, Vec<(u64, u64)>>::new();
+	//Marking an entry viewed marks all its duplicates viewed, but that's still a single view
+	let mut consumed_canonical = HashSet::new();
+	for entry in entries {
+		let entry_groups = match grouping {
+			Grouping::All => std::slice::from_ref(&0),
+			Grouping::Feed => std::slice::from_ref(&entry.feed_id),
+			Grouping::Tag => feed_groups.get(&entry.feed_id).map(Vec::as_slice).unwrap_or_default(),
+		};
+		let produced = index(entry.produced_date);
+		let first_view = match entry.canonical_id {
+			Some(canonical_id) => consumed_canonical.insert(canonical_id),
+			None => true,
+		};
+		let consumed = entry.viewed_at
+			.filter(|_viewed_at| first_view)
+			.and_then(|viewed_at| index(viewed_at.date()));
+		
+		for group in entry_groups {
+			let group_counts = counts.entry(*group).or_insert_with(|| vec![(0, 0); starts.len()]);
+			if let Some(produced) = produced {
+				group_counts[produced].0 += 1;
+			}
+			if let Some(consumed) = consumed {
+				group_counts[consumed].1 += 1;
+			}
+		}
+	}
+	if grouping == Grouping::All {
+		counts.entry(0).or_insert_with(|| vec![(0, 0); starts.len()]);
+	}
+	
+	let mut series = counts.into_iter()
+		.map(|(group, group_counts)| Series {
+			name: names.get(&group).cloned().unwrap_or_else(|| group.to_string()),
+			buckets: starts.iter().zip(group_counts)
+				.map(|(start, (produced, consumed))| Bucket {
+					start: *start,
+					produced,
+					consumed,
+				})
+				.collect(),
+		})
+		.collect::<Vec<_>>();
+	series.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(series)
+}
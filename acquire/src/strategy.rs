@@ -1,7 +1,12 @@
-use std::sync::{Mutex, Arc, MutexGuard};
+use std::{sync::{Mutex, Arc, MutexGuard}, time::Duration, future::Future, collections::HashSet, borrow::Cow};
 
 use entities::prelude::*;
 use sea_orm::*;
+use ff_object::describe::Described;
+use crate::{
+	retry::{self, ErrorKind, RetryPolicy},
+	limiter::{Limiter, limit_key},
+};
 use tracing::instrument::WithSubscriber;
 use tracing_subscriber::{
 	fmt,
@@ -10,6 +15,7 @@ use tracing_subscriber::{
 
 pub struct EntryInfo {
 	feed_entry_id: String,
+	canonical_id: Option<String>,
 	title: String,
 	view_url: String,
 	embed_url: Option<String>,
@@ -21,6 +27,7 @@ impl EntryInfo {
 	pub fn new(feed_entry_id: String, title: String, view_url: String, produced_date: time::Date) -> Self {
 		Self {
 			feed_entry_id,
+			canonical_id: None,
 			title,
 			view_url,
 			embed_url: None,
@@ -38,6 +45,100 @@ impl EntryInfo {
 		self.embed_url = Some(embed_url);
 		self
 	}
+	
+	///For when the strategy knows a better way to identify the content than the view url, like a video id
+	pub fn canonical_id(&mut self, canonical_id: String) -> &mut Self {
+		self.canonical_id = Some(canonical_id);
+		self
+	}
+	
+	pub fn get_feed_entry_id(&self) -> &str {
+		&self.feed_entry_id
+	}
+	
+	///The one set with [Self::canonical_id], or else the [canonical_url](crate::canonical::canonical_url) of the view url
+	pub fn get_canonical_id(&self) -> String {
+		match &self.canonical_id {
+			Some(id) => id.clone(),
+			None => crate::canonical::canonical_url(&self.view_url),
+		}
+	}
+	
+	pub fn get_title(&self) -> &str {
+		&self.title
+	}
+	
+	pub fn get_view_url(&self) -> &str {
+		&self.view_url
+	}
+	
+	pub fn get_embed_url(&self) -> Option<&str> {
+		self.embed_url.as_deref()
+	}
+	
+	pub fn get_produced_date(&self) -> time::Date {
+		self.produced_date
+	}
+	
+	pub fn get_produced_time(&self) -> Option<time::Time> {
+		self.produced_time
+	}
+}
+
+
+
+///Headers used to make a conditional request, see <https://developer.mozilla.org/en-US/docs/Web/HTTP/Conditional_requests>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+	pub etag: Option<String>,
+	pub last_modified: Option<String>,
+}
+
+impl Validators {
+	///Returns the validators of the last fetch of `feed` whose content made it into the database
+	pub async fn last_for_feed(conn: &DatabaseConnection, feed: &feed::Model) -> Result<Self, DbErr> {
+		let maybe_fetch = feed.find_related(fetch::Entity)
+			.filter(fetch::Column::Status.is_in([fetch::Status::Success, fetch::Status::NotModified]))
+			.order_by_desc(fetch::Column::Id)
+			.one(conn).await?;
+		
+		let validators = match maybe_fetch {
+			Some(fetch) => Self {
+				etag: fetch.etag,
+				last_modified: fetch.last_modified,
+			},
+			None => Self::default(),
+		};
+		Ok(validators)
+	}
+	
+	///Uses the validators from `other` for the ones missing in `self`
+	pub fn or(self, other: Self) -> Self {
+		Self {
+			etag: self.etag.or(other.etag),
+			last_modified: self.last_modified.or(other.last_modified),
+		}
+	}
+	
+	fn apply(self, fetch: &mut fetch::ActiveModel) {
+		fetch.etag = Set(self.etag);
+		fetch.last_modified = Set(self.last_modified);
+	}
+}
+
+///What [Strategy::fetch] got
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+	///Content to parse, with the validators to use for the next fetch
+	Content(String, Validators),
+	///Nothing changed since the fetch the validators came from
+	NotModified(Validators),
+}
+
+impl From<String> for Fetched {
+	fn from(content: String) -> Self {
+		Self::Content(content, Validators::default())
+	}
 }
 
 
@@ -77,16 +178,179 @@ async fn logged<Output>(task: impl std::future::Future<Output = Output>) -> (Str
 
 
 
+///What kind of value a per-feed option takes, so an editor can be generated for it
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum OptionType {
+	Bool,
+	Integer,
+	String,
+	///One of these names, like a unit enum variant
+	Choice(Vec<String>),
+	///Anything else, edited as JSON
+	Json,
+}
+
+///An option that can be set per feed, named after the configuration field it overrides
+pub type OptionDesc = Described<OptionType>;
+
+pub fn describe_option(name: &str, kind: OptionType, description: &str) -> OptionDesc {
+	Described::custom_new(kind, name.to_owned(), Some(description.to_owned()))
+}
+
+///Per-feed options, as stored in [feed::Model::strategy_options]
+pub type Options = serde_json::Map<String, serde_json::Value>;
+
+pub fn parse_options(text: &str) -> anyhow::Result<Options> {
+	Ok(serde_json::from_str(text)?)
+}
+
+/**
+The default [Strategy::with_options]: serializes the configuration of `strat` to JSON,
+replaces the fields that are in `options` and deserializes that into a copy.
+*/
+pub fn merge_options<S: Strategy + ?Sized>(strat: &S, options: &Options) -> anyhow::Result<Box<dyn Strategy + Send + Sync>> {
+	let described = strat.describe_options();
+	if let Some(unknown) = options.keys().find(|name| !described.iter().any(|desc| &desc.name == *name)) {
+		anyhow::bail!("Strategy \"{}\" has no per-feed option \"{unknown}\"", strat.name());
+	}
+	
+	let mut serialized = Vec::new();
+	let mut serializer = serde_json::Serializer::new(&mut serialized);
+	strat.serialize(&mut <dyn erased_serde::Serializer>::erase(&mut serializer))?;
+	let mut config = serde_json::from_slice::<serde_json::Value>(&serialized)?;
+	
+	let Some(fields) = config.as_object_mut() else {
+		anyhow::bail!("The configuration of strategy \"{}\" has no fields to override", strat.name());
+	};
+	fields.extend(options.clone());
+	
+	let mut merged = strat.box_clone();
+	merged.deserialize_replace(&mut <dyn erased_serde::Deserializer>::erase(config))?;
+	Ok(merged)
+}
+
+pub trait DynStrategyClone {
+	fn box_clone(&self) -> Box<dyn Strategy + Send + Sync>;
+}
+
+impl<T: Clone + Strategy + 'static> DynStrategyClone for T {
+	fn box_clone(&self) -> Box<dyn Strategy + Send + Sync> {
+		Box::new(self.clone())
+	}
+}
+
 ///The type itself should serve as a configuration/settings, which is why it should be serializable
 #[async_trait::async_trait]
-pub trait Strategy: ff_object::traits::DynSer + Send + Sync {
+pub trait Strategy: ff_object::traits::DynSer + DynStrategyClone + Send + Sync {
 	//&self required to be able to call it in a dyn context
-	fn name(&self) -> &'static str;
-	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<String>;
+	fn name(&self) -> &str;
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched>;
 	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>>;
+	///Whether an error returned by [Self::fetch] might go away when trying again
+	fn classify_error(&self, err: &anyhow::Error) -> ErrorKind {
+		retry::classify(err)
+	}
+	///The parts of the configuration that feeds can override, see [Self::with_options]
+	fn describe_options(&self) -> Vec<OptionDesc> {
+		Vec::new()
+	}
+	///A copy with the per-feed `options` applied, by default only the [described ones](Self::describe_options) are allowed
+	fn with_options(&self, options: &Options) -> anyhow::Result<Box<dyn Strategy + Send + Sync>> {
+		merge_options(self, options)
+	}
+}
+
+/**
+Another instance of a strategy type with its own name, and so its own configuration and feeds.
+E.g. a yt-dlp strategy that only gets the last few videos, next to one that gets everything.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Named<S> {
+	name: Cow<'static, str>,
+	strategy: S,
+}
+
+impl<S: Strategy + Clone + 'static> Named<S> {
+	pub fn new(name: impl Into<Cow<'static, str>>, strategy: S) -> Self {
+		Self {
+			name: name.into(),
+			strategy,
+		}
+	}
+	
+	pub fn get_strategy(&self) -> &S {
+		&self.strategy
+	}
+}
+
+//Only the wrapped strategy gets (de)serialized, the name has to survive loading its configuration
+impl<S: Strategy + Clone + 'static> ff_object::traits::DynSer for Named<S> {
+	fn deserialize_replace(&mut self, deserializer: &mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error> {
+		self.strategy.deserialize_replace(deserializer)
+	}
+	fn serialize(&self, serializer: &mut dyn erased_serde::Serializer) -> Result<(), erased_serde::Error> {
+		self.strategy.serialize(serializer)
+	}
+}
+
+#[async_trait::async_trait]
+impl<S: Strategy + Clone + 'static> Strategy for Named<S> {
+	fn name(&self) -> &str {
+		&self.name
+	}
+	
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
+		self.strategy.fetch(conn, feed).await
+	}
+	
+	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
+		self.strategy.parse(data).await
+	}
+	
+	fn classify_error(&self, err: &anyhow::Error) -> ErrorKind {
+		self.strategy.classify_error(err)
+	}
+	
+	fn describe_options(&self) -> Vec<OptionDesc> {
+		self.strategy.describe_options()
+	}
+	//Not delegated to the wrapped strategy, since the copy should keep the name
+}
+
+///How a strategy gets run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunOptions {
+	///How long fetching and parsing can each take
+	pub timeout: Option<Duration>,
+	///Applies to fetching, parsing the same data again won't change anything
+	pub retry: RetryPolicy,
+}
+
+///What a single run of a strategy is part of
+#[derive(Debug, Clone, Default)]
+pub struct RunContext {
+	///The batch the fetch belongs to, set when the fetch first gets saved so it can't end up without one
+	pub batch_id: Option<i32>,
+	///Gets waited for before every fetch attempt, and released while waiting to try again
+	pub limiter: Option<Limiter>,
 }
 
 
+///Awaits `task`, or returns [None] if that takes longer than `timeout`
+async fn with_timeout<Output>(timeout: Option<Duration>, task: impl Future<Output = Output>) -> Option<Output> {
+	match timeout {
+		Some(timeout) => tokio::time::timeout(timeout, task).await.ok(),
+		None => Some(task.await),
+	}
+}
+
+fn timeout_error(action: &str, timeout: Option<Duration>) -> String {
+	let timeout = timeout.expect("can only time out with a timeout");
+	let error = format!("{action} took longer than {timeout:?}");
+	tracing::error!(error);
+	error
+}
+
 fn error_to_string(err: anyhow::Error) -> String {
 	tracing::error!("{err:?}");
 	format!("{err:?}")
@@ -99,10 +363,25 @@ async fn update_entries(conn: &DatabaseConnection, feed: &feed::Model, fetch_id:
 		.all(conn)
 		.await?;
 	
+	//New duplicates of entries that already got viewed shouldn't show up as unviewed
+	let canonical_ids = entries.iter().map(|e| e.get_canonical_id()).collect::<Vec<_>>();
+	let viewed_canonical_ids = entry::Entity::find()
+		.select_only()
+		.column(entry::Column::CanonicalId)
+		.filter(entry::Column::CanonicalId.is_in(canonical_ids))
+		.filter(entry::Column::Viewed.eq(true))
+		.distinct()
+		.into_tuple::<String>()
+		.all(conn)
+		.await?
+		.into_iter()
+		.collect::<HashSet<_>>();
+	
 	let feed_id = feed.id;
 	
 	conn.transaction::<_,_,DbErr>(|conn| Box::pin(async move {
 		for entry in entries {
+			let canonical_id = entry.get_canonical_id();
 			let mut model = 'model: {
 				for old in &existing {
 					if old.feed_entry_id==entry.feed_entry_id {
@@ -110,11 +389,13 @@ async fn update_entries(conn: &DatabaseConnection, feed: &feed::Model, fetch_id:
 					}
 				}
 				let mut new = entry::ActiveModel::new();
+				new.viewed = Set(viewed_canonical_ids.contains(&canonical_id));
 				new.feed_entry_id = Set(entry.feed_entry_id);
 				new.feed_id = Set(feed_id);
 				new
 			};
 			
+			model.canonical_id = Set(Some(canonical_id));
 			model.name = Set(entry.title);
 			model.view_url = Set(entry.view_url);
 			model.embed_url = Set(entry.embed_url);
@@ -136,8 +417,47 @@ async fn update_entries(conn: &DatabaseConnection, feed: &feed::Model, fetch_id:
 	Ok(())
 }
 
+///Calls [Strategy::fetch] until it succeeds or the retry policy says to stop, returns [None] if the last attempt timed out
+async fn fetch_with_retries(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Strategy, options: &RunOptions, limiter: Option<&Limiter>) -> Option<anyhow::Result<Fetched>> {
+	let mut attempt = 1;
+	loop {
+		let permit = match limiter {
+			Some(limiter) => Some(limiter.acquire(limit_key(feed)).await),
+			None => None,
+		};
+		let fetched = with_timeout(options.timeout, strat.fetch(conn, feed)).await;
+		
+		let kind = match &fetched {
+			Some(Ok(_)) => return fetched,
+			//Could be a slow connection, which is just as likely to get better as other network problems
+			None => ErrorKind::Transient,
+			Some(Err(err)) => strat.classify_error(err),
+		};
+		if !options.retry.should_retry(attempt, kind) {
+			return fetched;
+		}
+		
+		let wait = options.retry.backoff_after(attempt);
+		//Logged here so every attempt shows up in the fetch log
+		match fetched {
+			None => {
+				timeout_error("Fetching", options.timeout);
+			},
+			Some(Err(err)) => {
+				tracing::warn!("{err:?}");
+			},
+			Some(Ok(_)) => unreachable!("successful fetches got returned earlier"),
+		}
+		tracing::warn!(attempt, ?wait, "Fetch attempt failed with a transient error, trying again");
+		//Lets other fetches from the same host go while this one waits
+		drop(permit);
+		tokio::time::sleep(wait).await;
+		attempt += 1;
+	}
+}
+
 ///Runs a fetch, but doesn't do the final save, because that still requires the logs from running this function
-async fn do_fetch(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Strategy) -> Result<fetch::ActiveModel, DbErr> {
+async fn do_fetch(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Strategy, options: &RunOptions, context: &RunContext) -> Result<fetch::ActiveModel, DbErr> {
 	use ActiveValue::Set;
 	
 	tracing::info!(strategy = strat.name(), ?feed, "Fetching feed");
@@ -147,31 +467,73 @@ async fn do_fetch(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Str
 	fetch.feed_id = Set(feed.id);
 	fetch.url = Set(feed.url.clone());
 	fetch.strategy = Set(strat.name().to_owned());
+	fetch.batch_id = Set(context.batch_id);
 	
-	let fetched = strat.fetch(conn, feed).await;
+	let with_options;
+	let strat = match feed.strategy_options.as_deref().filter(|text| !text.trim().is_empty()) {
+		None => strat,
+		Some(text) => match parse_options(text).and_then(|feed_options| strat.with_options(&feed_options)) {
+			Ok(configured) => {
+				tracing::info!(options = text, "Using the feed's strategy options");
+				with_options = configured;
+				with_options.as_ref()
+			},
+			Err(err) => {
+				fetch.status = Set(fetch::Status::FetchError);
+				fetch.error = Set(Some(error_to_string(err.context("While applying the feed's strategy options"))));
+				
+				return Ok(fetch);
+			},
+		},
+	};
+	
+	let timeout = options.timeout;
+	let fetched = fetch_with_retries(conn, feed, strat, options, context.limiter.as_ref()).await;
 	
 	let data = match fetched {
-		Err(err) => {
+		None => {
+			fetch.status = Set(fetch::Status::Timeout);
+			fetch.error = Set(Some(timeout_error("Fetching", timeout)));
+			
+			return Ok(fetch);
+		},
+		Some(Err(err)) => {
 			fetch.status = Set(fetch::Status::FetchError);
 			fetch.error = Set(Some(error_to_string(err)));
 			
 			return Ok(fetch);
 		},
-		Ok(data) => data,
+		Some(Ok(Fetched::NotModified(validators))) => {
+			tracing::info!("Not modified since last fetch");
+			fetch.status = Set(fetch::Status::NotModified);
+			validators.apply(&mut fetch);
+			
+			return Ok(fetch);
+		},
+		Some(Ok(Fetched::Content(data, validators))) => {
+			validators.apply(&mut fetch);
+			data
+		},
 	};
 	
 	fetch.content = Set(Some(data.clone()));
 	
-	let parsed = strat.parse(&data).await;
+	let parsed = with_timeout(timeout, strat.parse(&data)).await;
 	
 	let parsed = match parsed {
-		Err(err) => {
+		None => {
+			fetch.status = Set(fetch::Status::Timeout);
+			fetch.error = Set(Some(timeout_error("Parsing", timeout)));
+			
+			return Ok(fetch);
+		},
+		Some(Err(err)) => {
 			fetch.status = Set(fetch::Status::ParseError);
 			fetch.error = Set(Some(error_to_string(err)));
 			
 			return Ok(fetch);
 		},
-		Ok(parsed) => parsed,
+		Some(Ok(parsed)) => parsed,
 	};
 	
 	fetch.status = Set(fetch::Status::EntryUpdateError);
@@ -191,8 +553,9 @@ async fn do_fetch(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Str
 	Ok(fetch)
 }
 
-pub async fn run_strategy(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Strategy) -> Result<fetch::Model, DbErr> {
-	let (log, fetch) = logged(do_fetch(conn, feed, strat)).await;
+///Fetches and parses `feed` with `strat` as part of what's in `context`. The log of the resulting fetch contains every attempt.
+pub async fn run_strategy(conn: &DatabaseConnection, feed: &feed::Model, strat: &dyn Strategy, options: &RunOptions, context: &RunContext) -> Result<fetch::Model, DbErr> {
+	let (log, fetch) = logged(do_fetch(conn, feed, strat, options, context)).await;
 	let mut fetch = fetch?;
 	
 	fetch.log = Set(log);
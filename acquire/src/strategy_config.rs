@@ -0,0 +1,108 @@
+use std::{io::{Error as IoError, Write}, path::PathBuf};
+use ff_object::traits::DynSer;
+use super::strategy::Strategy;
+
+
+const FILE_EXTENSION: &str = "ron";
+
+
+pub fn strategy_serializer<Writer: Write>(writer: Writer) -> ron::Result<ron::Serializer<Writer>> {
+	use ron::*;
+	Serializer::new(
+		writer,
+		Some(ser::PrettyConfig::new()
+			.indentor("\t".into())
+			.struct_names(true)
+		),
+	)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub enum ConfigError {
+	Ron(#[from] ron::Error),
+	RonSpanned(#[from] ron::error::SpannedError),
+	Io(#[from] IoError),
+	Serde(#[from] erased_serde::Error)
+}
+
+///The configuration of `strat` as RON
+pub fn to_ron<S: Strategy + ?Sized>(strat: &S) -> Result<String, ConfigError> {
+	let mut text = Vec::new();
+	let mut serializer = strategy_serializer(&mut text)?;
+	DynSer::serialize(strat, &mut <dyn erased_serde::Serializer>::erase(&mut serializer))?;
+	Ok(String::from_utf8(text).expect("RON should be valid UTF-8"))
+}
+
+///Copy of `strat` with the configuration from the RON `text`, which fails if it doesn't fit the strategy
+pub fn from_ron<S: Strategy + ?Sized>(strat: &S, text: &str) -> Result<Box<dyn Strategy + Send + Sync>, ConfigError> {
+	let mut configured = strat.box_clone();
+	let mut deserializer = ron::Deserializer::from_str(text)?;
+	configured.deserialize_replace(&mut <dyn erased_serde::Deserializer>::erase(&mut deserializer))?;
+	deserializer.end()?;
+	Ok(configured)
+}
+
+///Folder with a RON configuration file for every strategy, named after the strategy
+#[derive(Debug, Clone)]
+pub struct ConfigFolder {
+	path: PathBuf,
+}
+
+impl ConfigFolder {
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self {
+			path: path.into(),
+		}
+	}
+	
+	pub fn file_path(&self, name: &str) -> PathBuf {
+		//Not set_extension, since names of named instances contain dots
+		self.path.join(format!("{name}.{FILE_EXTENSION}"))
+	}
+	
+	///Replaces the configuration of `strat` with its file, returns false if there's no file for it yet
+	pub fn load<S: Strategy + ?Sized>(&self, strat: &mut S) -> Result<bool, ConfigError> {
+		let path = self.file_path(strat.name());
+		if !path.try_exists()? {
+			return Ok(false);
+		}
+		
+		let text = std::fs::read_to_string(path)?;
+		let mut deserializer = ron::Deserializer::from_str(&text)?;
+		strat.deserialize_replace(&mut <dyn erased_serde::Deserializer>::erase(&mut deserializer))?;
+		Ok(true)
+	}
+	
+	///Names of the extra instances of the strategy called `name` that have a file, like `command.podcasts` for `command.podcasts.ron`.
+	///Used to add [Named](crate::strategy::Named) instances without recompiling.
+	pub fn instance_names(&self, name: &str) -> Result<Vec<String>, ConfigError> {
+		let files = match std::fs::read_dir(&self.path) {
+			Ok(files) => files,
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(err) => return Err(err.into()),
+		};
+		let prefix = format!("{name}.");
+		let mut names = Vec::new();
+		for file in files {
+			let path = file?.path();
+			if path.extension().and_then(|extension| extension.to_str()) != Some(FILE_EXTENSION) {
+				continue;
+			}
+			let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+				continue;
+			};
+			if stem.len() > prefix.len() && stem.starts_with(&prefix) {
+				names.push(stem.to_owned());
+			}
+		}
+		names.sort();
+		Ok(names)
+	}
+	
+	///Writes the configuration of `strat` to its file
+	pub fn save<S: Strategy + ?Sized>(&self, strat: &S) -> Result<(), ConfigError> {
+		std::fs::write(self.file_path(strat.name()), to_ron(strat)?)?;
+		Ok(())
+	}
+}
@@ -1,5 +1,5 @@
-use std::sync::Arc;
-use super::strategy::*;
+use std::{sync::{Arc, RwLock}, time::Duration};
+use super::{strategy::*, retry::RetryPolicy};
 use entities::prelude::*;
 use sea_orm::EntityTrait;
 
@@ -19,6 +19,8 @@ pub enum RunIdError {
 	NoSuchFeed(i32),
 	#[error(transparent)]
 	StrategyNotFound(#[from] NotFoundError),
+	#[error("The fetch got cancelled before it finished")]
+	Cancelled,
 }
 
 impl From<RunError> for RunIdError {
@@ -35,9 +37,11 @@ impl From<RunError> for RunIdError {
 pub struct NotFoundError(String);
 
 
+///Clones share their strategies, so replacing one applies everywhere
 #[derive(Default,Clone)]
 pub struct StrategyList {
-	list: Vec<Arc<dyn Strategy + Send + Sync>>
+	list: Arc<RwLock<Vec<Arc<dyn Strategy + Send + Sync>>>>,
+	options: RunOptions,
 }
 
 impl StrategyList {
@@ -45,27 +49,67 @@ impl StrategyList {
 		Self::default()
 	}
 	
-	pub fn add(&mut self, strat: impl Strategy + Send + 'static) {
-		self.list.push(Arc::new(strat));
+	pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+		self.options.timeout = timeout;
+	}
+	
+	pub fn get_timeout(&self) -> Option<Duration> {
+		self.options.timeout
+	}
+	
+	pub fn set_retry_policy(&mut self, retry: RetryPolicy) {
+		self.options.retry = retry;
+	}
+	
+	pub fn get_retry_policy(&self) -> RetryPolicy {
+		self.options.retry
+	}
+	
+	fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<Arc<dyn Strategy + Send + Sync>>> {
+		self.list.read().expect("strategy list lock shouldn't be poisoned")
+	}
+	
+	fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<Arc<dyn Strategy + Send + Sync>>> {
+		self.list.write().expect("strategy list lock shouldn't be poisoned")
+	}
+	
+	pub fn add(&mut self, strat: impl Strategy + 'static) {
+		self.write().push(Arc::new(strat));
 	}
 	pub fn add_from_container(&mut self, strat: impl Into<Arc<dyn Strategy + Send + Sync>>) {
-		self.list.push(strat.into());
+		self.write().push(strat.into());
 	}
 	
-	pub fn get_by_name(&self, name: &str) -> Result<&Arc<dyn Strategy + Send + Sync>, NotFoundError> {
-		self.list.iter()
+	pub fn get_by_name(&self, name: &str) -> Result<Arc<dyn Strategy + Send + Sync>, NotFoundError> {
+		self.read().iter()
 			.find(|s| s.name()==name)
+			.cloned()
 			.ok_or_else(|| NotFoundError(name.to_owned()))
 	}
 	
-	pub fn iter_strats(&self) -> impl Iterator<Item = &(dyn Strategy + Send + Sync)> {
-		self.list.iter().map(|s| s.as_ref())
+	pub fn iter_strats(&self) -> impl Iterator<Item = Arc<dyn Strategy + Send + Sync>> {
+		self.read().clone().into_iter()
+	}
+	
+	///Swaps out the strategy with the same name, fetches that already started keep using the old one
+	pub fn replace(&self, strat: impl Into<Arc<dyn Strategy + Send + Sync>>) -> Result<(), NotFoundError> {
+		let strat = strat.into();
+		let mut list = self.write();
+		let Some(existing) = list.iter_mut().find(|s| s.name()==strat.name()) else {
+			return Err(NotFoundError(strat.name().to_owned()));
+		};
+		*existing = strat;
+		Ok(())
 	}
 	
 	
 	pub async fn run(&self, conn: &sea_orm::DatabaseConnection, feed: feed::Model) -> Result<fetch::Model, RunError> {
+		self.run_with_context(conn, feed, &RunContext::default()).await
+	}
+	
+	pub async fn run_with_context(&self, conn: &sea_orm::DatabaseConnection, feed: feed::Model, context: &RunContext) -> Result<fetch::Model, RunError> {
 		let strat = self.get_by_name(&feed.strategy)?;
-		let fetch = run_strategy(conn, &feed, strat.as_ref()).await?;
+		let fetch = run_strategy(conn, &feed, strat.as_ref(), &self.options, context).await?;
 		Ok(fetch)
 	}
 	
@@ -85,7 +129,10 @@ impl StrategyList {
 
 impl std::fmt::Debug for StrategyList {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let names = self.iter_strats().map(|s| s.name()).collect::<Vec<_>>();
-		f.debug_struct("StrategyList").field("names", &names).finish()
+		let names = self.iter_strats().map(|s| s.name().to_owned()).collect::<Vec<_>>();
+		f.debug_struct("StrategyList")
+			.field("names", &names)
+			.field("options", &self.options)
+			.finish()
 	}
 }
\ No newline at end of file
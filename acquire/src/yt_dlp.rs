@@ -3,6 +3,7 @@ use std::{ffi::OsString, process::Stdio};
 use entities::prelude::*;
 use sea_orm::*;
 use super::strategy::*;
+use crate::retry::{ErrorKind, is_transient_status};
 
 /*
 --dateafter does not appear to work with flat-playlist
@@ -90,6 +91,53 @@ impl From<YtdlpVideoInfo> for EntryInfo {
 	}
 }
 
+///yt-dlp exited without success, `stderr` says why
+#[derive(thiserror::Error, Debug)]
+#[error("Process returned non-successful exit code: {status}")]
+pub struct ExitError {
+	pub status: std::process::ExitStatus,
+	pub stderr: String,
+}
+
+///Parts of yt-dlp errors that mean the network or the site had a problem, lowercase
+const TRANSIENT_MESSAGES: &[&str] = &[
+	"timed out",
+	"temporary failure in name resolution",
+	"name or service not known",
+	"network is unreachable",
+	"connection reset",
+	"connection refused",
+	"connection aborted",
+	"remote end closed connection",
+	"incompleteread",
+	"transporterror",
+];
+
+/**
+Classifies what yt-dlp wrote to stderr by its `ERROR:` lines,
+which are transient for HTTP statuses like 5xx and 429, and for network problems.
+*/
+pub fn classify_stderr(stderr: &str) -> ErrorKind {
+	let transient = stderr.lines()
+		.filter(|line| line.starts_with("ERROR:"))
+		.any(|line| {
+			let http_status = line.split_once("HTTP Error ")
+				.and_then(|(_, rest)| rest.get(..3))
+				.and_then(|code| code.parse::<u16>().ok())
+				.and_then(|code| reqwest::StatusCode::from_u16(code).ok());
+			if let Some(status) = http_status {
+				return is_transient_status(status);
+			}
+			let line = line.to_lowercase();
+			TRANSIENT_MESSAGES.iter().any(|message| line.contains(message))
+		});
+	if transient {
+		ErrorKind::Transient
+	} else {
+		ErrorKind::Permanent
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize,serde::Deserialize)]
 pub enum Limit {
 	AfterDate(time::Date),
@@ -110,10 +158,19 @@ impl Limit {
 	}
 }
 
+const DEFAULT_URL_TEMPLATE: &str = "www.youtube.com/channel/{}/videos";
+
+fn default_url_template() -> String {
+	DEFAULT_URL_TEMPLATE.into()
+}
+
 #[derive(Debug, Clone,PartialEq,Eq, serde::Serialize,serde::Deserialize)]
 pub struct YtDlpStrategy {
 	command: String,
 	backup_limit: Limit,
+	///Used for feed urls without a `/`, with `{}` replaced by the feed url
+	#[serde(default = "default_url_template")]
+	url_template: String,
 }
 
 impl Default for YtDlpStrategy {
@@ -121,6 +178,7 @@ impl Default for YtDlpStrategy {
 		Self {
 			command: "yt-dlp".into(),
 			backup_limit: Limit::Amount(10),
+			url_template: default_url_template(),
 		}
 	}
 }
@@ -131,14 +189,14 @@ impl Strategy for YtDlpStrategy {
 		"yt-dlp"
 	}
 	
-	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<String> {
+	async fn fetch(&self, conn: &DatabaseConnection, feed: &feed::Model) -> anyhow::Result<Fetched> {
 		let maybe_last_entry = feed.find_related(entry::Entity)
 			.order_by_desc(entry::Column::ProducedDate)
 			.one(conn).await?;
 		
 		let mut url = feed.url.to_owned();
 		if !url.contains('/') {
-			url = format!("www.youtube.com/channel/{url}/videos");
+			url = self.url_template.replace("{}", &url);
 			tracing::info!(url, "Expanded url:")
 		}
 		
@@ -164,14 +222,32 @@ impl Strategy for YtDlpStrategy {
 		
 		let out = cmd.output().await?;
 		
+		let stderr = String::from_utf8_lossy(&out.stderr).into_owned();
 		//Formatted like this to get to print out the newlines instead of escaping them
-		tracing::info!("yt-dlp stderr:\n{}", String::from_utf8_lossy(&out.stderr));
+		tracing::info!("yt-dlp stderr:\n{stderr}");
 		
 		if !out.status.success() && !matches!(out.status.code(),Some(101))  {
-			anyhow::bail!("Process returned non-successful exit code: {}",out.status);
+			return Err(ExitError {
+				status: out.status,
+				stderr,
+			}.into());
 		}
 		
-		Ok(String::from_utf8(out.stdout)?)
+		Ok(String::from_utf8(out.stdout)?.into())
+	}
+	
+	fn classify_error(&self, err: &anyhow::Error) -> ErrorKind {
+		match err.downcast_ref::<ExitError>() {
+			Some(exit) => classify_stderr(&exit.stderr),
+			None => crate::retry::classify(err),
+		}
+	}
+	
+	fn describe_options(&self) -> Vec<OptionDesc> {
+		vec![
+			describe_option("backup_limit", OptionType::Json, "What to get when there are no entries yet, e.g. {\"Amount\": 10}"),
+			describe_option("url_template", OptionType::String, "Used for urls without a /, with {} replaced by the url"),
+		]
 	}
 	
 	async fn parse(&self, data: &str) -> anyhow::Result<Vec<EntryInfo>> {
@@ -6,11 +6,13 @@ use acquire::{
 	strategy::Strategy,
 	mock::{MockStrat, FetchCommand}, 
 	RunError,
+	strategy_list::RunIdError,
 	batch::{fetch_batch, BatchStatusUpdate},
-	batch_tracker::{BatchTracker, BroadcastListener}
+	batch_tracker::{BatchTracker, BroadcastListener},
+	limiter::Limiter,
 };
 use entities::prelude::*;
-use sea_orm::{ModelTrait, PaginatorTrait};
+use sea_orm::{EntityTrait, ModelTrait, PaginatorTrait};
 use tokio::sync::broadcast;
 
 const CMD_STRAT: &str = "command strat";
@@ -26,15 +28,15 @@ fn listener() -> (broadcast::Receiver<BatchStatusUpdate>, BroadcastListener) {
 async fn basic() -> Result<(), RunError> {
 	let db = init().await?;
 	let strat = MockStrat::default();
-	let strat_name = strat.name();
+	let strat_name = strat.name().to_owned();
 	let strats = single_strat_list(strat);
 	
-	let feed1 = feed_strat_name("ok", strat_name, &db).await?;
-	let feed2 = feed_strat_name("ok", strat_name, &db).await?;
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("ok", &strat_name, &db).await?;
 	
 	let (recv, listener) = listener();
 	
-	let (batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone());
+	let (batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone(), Limiter::default(), None);
 	future.await;
 	
 	assert_eq!(1, feed1.find_related(fetch::Entity).count(&db).await? );
@@ -48,23 +50,51 @@ async fn basic() -> Result<(), RunError> {
 	Ok(())
 }
 
+///A snapshot has what all the updates said, for listeners that missed some
+#[tokio::test]
+async fn snapshot() -> Result<(), RunError> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("fetch error", &strat_name, &db).await?;
+	
+	let (mut recv, listener) = listener();
+	
+	let (batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone(), Limiter::default(), None);
+	future.await;
+	
+	let mut latest = Vec::new();
+	while let Ok(update) = recv.try_recv() {
+		latest.extend(update.latest);
+	}
+	let snapshot = batch_sync.read().await.snapshot();
+	assert_eq!(latest, snapshot.fetched);
+	assert_eq!(2, snapshot.fetched.len());
+	assert!(snapshot.status.is_done());
+	
+	Ok(())
+}
+
 ///The generated results look good
 #[tokio::test]
 async fn results() -> Result<(), RunError> {
 	let db = init().await?;
 	let strat = MockStrat::default();
-	let strat_name = strat.name();
+	let strat_name = strat.name().to_owned();
 	let strats = single_strat_list(strat);
 	
-	let feed1 = feed_strat_name("ok", strat_name, &db).await?;
-	let feed2 = feed_strat_name("ok", strat_name, &db).await?;
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("ok", &strat_name, &db).await?;
 	
 	let ids = vec![feed1.id, feed2.id];
 	
 	let (recv, listener) = listener();
 	std::mem::drop(recv); // don't care
 	
-	let (batch_sync, future) = fetch_batch(ids.clone(), listener, strats, db.clone());
+	let (batch_sync, future) = fetch_batch(ids.clone(), listener, strats, db.clone(), Limiter::default(), None);
 	future.await;
 	
 	let batch_lock = batch_sync.read().await;
@@ -94,15 +124,15 @@ async fn results() -> Result<(), RunError> {
 async fn broadcast_listener_updates() -> Result<(), anyhow::Error> {
 	let db = init().await?;
 	let strat = MockStrat::default();
-	let strat_name = strat.name();
+	let strat_name = strat.name().to_owned();
 	let strats = single_strat_list(strat);
 	
-	let feed1 = feed_strat_name("ok", strat_name, &db).await?;
-	let feed2 = feed_strat_name("ok", strat_name, &db).await?;
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("ok", &strat_name, &db).await?;
 	
 	let (mut recv, listener) = listener();
 	
-	let (_batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone());
+	let (_batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone(), Limiter::default(), None);
 	
 	future.await;
 	
@@ -119,23 +149,51 @@ async fn broadcast_listener_updates() -> Result<(), anyhow::Error> {
 	Ok(())
 }
 
+///Broadcast updates say which fetch finished
+#[tokio::test]
+async fn broadcast_latest_fetch() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed = feed_strat_name("ok", &strat_name, &db).await?;
+	
+	let (mut recv, listener) = listener();
+	
+	let (_batch_sync, future) = fetch_batch(vec![feed.id], listener, strats, db.clone(), Limiter::default(), None);
+	future.await;
+	
+	let fetch = feed.find_related(fetch::Entity).one(&db).await?.expect("feed should have been fetched");
+	
+	let update = recv.recv().await?;
+	assert!(update.is_done());
+	assert!(!update.cancelled);
+	let latest = update.latest.expect("update should be caused by a fetch");
+	assert_eq!(feed.id, latest.feed_id);
+	assert_eq!(Some(fetch.id), latest.fetch_id);
+	assert_eq!(Some(fetch::Status::Success), latest.status);
+	
+	Ok(())
+}
+
 ///Things keep looking good when using the tracker
 #[tokio::test]
 async fn tracked() -> Result<(), anyhow::Error> {
 	let db = init().await?;
 	let strat = MockStrat::default();
-	let strat_name = strat.name();
+	let strat_name = strat.name().to_owned();
 	let strats = single_strat_list(strat);
 	
-	let feed1 = feed_strat_name("ok", strat_name, &db).await?;
-	let feed2 = feed_strat_name("ok", strat_name, &db).await?;
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("ok", &strat_name, &db).await?;
 	
 	let tracker = BatchTracker::default();
 	
-	let index = tracker.queue_fetches(vec![feed1.id, feed2.id], db.clone(), strats).await;
-	tracker.await_fetch(index).await?;
+	let id = tracker.queue_fetches(vec![feed1.id, feed2.id], db.clone(), strats).await?;
+	tracker.await_fetch(id).await?;
 	
-	let status_sync = tracker.get_status(index).await?;
+	let status_sync = tracker.get_status(id).await?;
 	let status_lock = status_sync.read().await;
 	
 	assert_eq!(2, status_lock.total);
@@ -159,7 +217,7 @@ async fn batch_status() -> Result<(), anyhow::Error> {
 	
 	let (mut recv, listener) = listener();
 	
-	let (batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone());
+	let (batch_sync, future) = fetch_batch(vec![feed1.id, feed2.id], listener, strats, db.clone(), Limiter::default(), None);
 	let join_handle = tokio::spawn(future);
 	
 	cmd.send(FetchCommand::Fetch(feed1.id))?;
@@ -188,4 +246,191 @@ async fn batch_status() -> Result<(), anyhow::Error> {
 	join_handle.await?;
 	
 	Ok(())
-}
\ No newline at end of file
+}
+///Cancelling stops the remaining fetches and marks them as cancelled
+#[tokio::test]
+async fn cancel() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed_ok = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed_hang = feed_strat_name("hang", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::default();
+	let id = tracker.queue_fetches(vec![feed_ok.id, feed_hang.id], db.clone(), strats).await?;
+	let mut recv = tracker.subscribe(id).await?;
+	
+	//Wait for the one that doesn't hang
+	let update = recv.recv().await?;
+	assert_eq!(1, update.done);
+	
+	tracker.cancel(id).await?;
+	
+	let update = recv.recv().await?;
+	assert_eq!(2, update.done);
+	
+	//Shouldn't hang anymore
+	tracker.await_fetch(id).await?;
+	
+	let status_sync = tracker.get_status(id).await?;
+	let status_lock = status_sync.read().await;
+	
+	assert!(status_lock.cancelled);
+	assert!(status_lock.is_done());
+	assert_eq!(1, status_lock.finished.iter().filter(|res| matches!(res, Err(RunIdError::Cancelled))).count());
+	assert_eq!(0, feed_hang.find_related(fetch::Entity).count(&db).await? );
+	
+	Ok(())
+}
+
+///Cancelling a finished batch doesn't change it
+#[tokio::test]
+async fn cancel_finished() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed = feed_strat_name("ok", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::default();
+	let id = tracker.queue_fetches(vec![feed.id], db.clone(), strats).await?;
+	tracker.await_fetch(id).await?;
+	
+	tracker.cancel(id).await?;
+	
+	let status_sync = tracker.get_status(id).await?;
+	let status_lock = status_sync.read().await;
+	
+	assert!(!status_lock.cancelled);
+	assert!(status_lock.finished.iter().all(|res| res.is_ok()));
+	
+	Ok(())
+}
+
+///Timeouts of the strategy list apply to batches
+#[tokio::test]
+async fn timeout() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let mut strats = single_strat_list(strat);
+	strats.set_timeout(Some(std::time::Duration::from_millis(50)));
+	
+	let feed = feed_strat_name("hang", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::default();
+	let id = tracker.queue_fetches(vec![feed.id], db.clone(), strats).await?;
+	tracker.await_fetch(id).await?;
+	
+	let fetch = feed.find_related(fetch::Entity).one(&db).await?.expect("should have timed out");
+	assert_eq!(fetch::Status::Timeout, fetch.status);
+	
+	Ok(())
+}
+
+///Tracked batches get saved in the database along with their fetches
+#[tokio::test]
+async fn persisted() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("fetch error", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::default();
+	let id = tracker.queue_fetches(vec![feed1.id, feed2.id], db.clone(), strats).await?;
+	tracker.await_fetch(id).await?;
+	
+	let batch = batch::Entity::find_by_id(id).one(&db).await?.expect("should have been saved");
+	
+	assert_eq!(batch::Status::Finished, batch.status);
+	assert_eq!(2, batch.total);
+	assert_eq!(2, batch.find_related(fetch::Entity).count(&db).await? );
+	
+	let fetch = feed2.find_related(fetch::Entity).one(&db).await?.expect("just fetched");
+	assert_eq!(Some(id), fetch.batch_id);
+	assert_eq!(fetch::Status::FetchError, fetch.status);
+	
+	Ok(())
+}
+
+///Finished batches get forgotten once the next batch gets queued, since they're in the database
+#[tokio::test]
+async fn pruned() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed = feed_strat_name("ok", &strat_name, &db).await?;
+	let hanging_feed = feed_strat_name("hang", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::default();
+	let finished_id = tracker.queue_fetches(vec![feed.id], db.clone(), strats.clone()).await?;
+	tracker.await_fetch(finished_id).await?;
+	let running_id = tracker.queue_fetches(vec![hanging_feed.id], db.clone(), strats.clone()).await?;
+	let last_id = tracker.queue_fetches(vec![feed.id], db.clone(), strats).await?;
+	
+	assert!(tracker.get_status(finished_id).await.is_err());
+	assert!(tracker.get_status(running_id).await.is_ok());
+	assert!(tracker.get_status(last_id).await.is_ok());
+	
+	tracker.cancel(running_id).await?;
+	
+	Ok(())
+}
+
+///Cancelled batches get saved as such
+#[tokio::test]
+async fn persisted_cancel() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed = feed_strat_name("hang", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::default();
+	let id = tracker.queue_fetches(vec![feed.id], db.clone(), strats).await?;
+	tracker.cancel(id).await?;
+	tracker.await_fetch(id).await?;
+	
+	let batch = batch::Entity::find_by_id(id).one(&db).await?.expect("should have been saved");
+	
+	assert_eq!(batch::Status::Cancelled, batch.status);
+	
+	Ok(())
+}
+
+///Batches that were running when the server stopped get marked as interrupted
+#[tokio::test]
+async fn interrupted() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed = feed_strat_name("hang", &strat_name, &db).await?;
+	
+	//Pretend the server stopped by forgetting about the tracker
+	let tracker = BatchTracker::default();
+	let running_id = tracker.queue_fetches(vec![feed.id], db.clone(), strats.clone()).await?;
+	let feed = feed_strat_name("ok", &strat_name, &db).await?;
+	let finished_id = tracker.queue_fetches(vec![feed.id], db.clone(), strats).await?;
+	tracker.await_fetch(finished_id).await?;
+	
+	assert_eq!(1, BatchTracker::mark_interrupted(&db).await?);
+	
+	let running = batch::Entity::find_by_id(running_id).one(&db).await?.expect("should have been saved");
+	let finished = batch::Entity::find_by_id(finished_id).one(&db).await?.expect("should have been saved");
+	
+	assert_eq!(batch::Status::Interrupted, running.status);
+	assert_eq!(batch::Status::Finished, finished.status);
+	
+	Ok(())
+}
@@ -0,0 +1,119 @@
+mod common;
+use common::{init, feed, run_strategy};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, ModelTrait, QueryFilter, QueryOrder};
+use sea_migration::{Migrator, MigratorTrait};
+use acquire::{
+	canonical::canonical_url,
+	mock::MockStrat,
+};
+use entities::prelude::*;
+
+///Different ways of linking to the same page end up the same
+#[test]
+fn urls() {
+	let canonical = canonical_url("example.com/page?b=2&a=1");
+	assert_eq!(canonical, canonical_url("https://www.example.com/page/?a=1&b=2"));
+	assert_eq!(canonical, canonical_url("http://EXAMPLE.com/page?a=1&utm_source=feed&b=2&fbclid=abc"));
+	
+	assert_ne!(canonical, canonical_url("example.com/other?b=2&a=1"));
+	assert_ne!(canonical, canonical_url("example.com/page?b=3&a=1"));
+}
+
+///Parts that only mean something on some sites don't get thrown away everywhere
+#[test]
+fn site_specific() {
+	assert_ne!(canonical_url("comic.example/#/page/1"), canonical_url("comic.example/#/page/2"));
+	assert_ne!(canonical_url("example.com/page?ref=1"), canonical_url("example.com/page?ref=2"));
+	assert_ne!(canonical_url("example.com/page?si=1"), canonical_url("example.com/page"));
+	
+	assert_eq!(canonical_url("youtube.com/@someone"), canonical_url("https://www.youtube.com/@someone?si=tracking"));
+	assert_eq!(canonical_url("open.spotify.com/episode/abc"), canonical_url("https://open.spotify.com/episode/abc?si=tracking"));
+}
+
+///YouTube videos get recognised no matter how they're linked to
+#[test]
+fn youtube() {
+	assert_eq!("youtube:abc123", canonical_url("https://www.youtube.com/watch?v=abc123"));
+	assert_eq!("youtube:abc123", canonical_url("https://www.youtube.com/watch?v=abc123&list=PL123&index=4"));
+	assert_eq!("youtube:abc123", canonical_url("https://youtu.be/abc123?si=tracking"));
+	assert_eq!("youtube:abc123", canonical_url("youtube.com/shorts/abc123"));
+	assert_eq!("youtube:abc123", canonical_url("www.youtube-nocookie.com/embed/abc123"));
+}
+
+///The same entries in different feeds get the same canonical id
+#[tokio::test]
+async fn duplicate_entries() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed1 = feed("3n3", &strat, &db).await?;
+	let feed2 = feed("3n3", &strat, &db).await?;
+	
+	run_strategy(&db, &feed1, &strat).await?;
+	run_strategy(&db, &feed2, &strat).await?;
+	
+	let entries1 = feed1.find_related(entry::Entity).order_by_asc(entry::Column::Id).all(&db).await?;
+	let entries2 = feed2.find_related(entry::Entity).order_by_asc(entry::Column::Id).all(&db).await?;
+	
+	assert_eq!(3, entries1.len());
+	for (entry1, entry2) in entries1.iter().zip(&entries2) {
+		assert!(entry1.canonical_id.is_some());
+		assert_eq!(entry1.canonical_id, entry2.canonical_id);
+	}
+	
+	Ok(())
+}
+
+///New duplicates of viewed entries are viewed as well
+#[tokio::test]
+async fn viewed_duplicates() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed1 = feed("3n3", &strat, &db).await?;
+	let feed2 = feed("3n3", &strat, &db).await?;
+	
+	run_strategy(&db, &feed1, &strat).await?;
+	let viewed = feed1.find_related(entry::Entity).order_by_asc(entry::Column::Id).one(&db).await?.expect("feed should have entries");
+	let mut viewed = viewed.into_active_model();
+	viewed.viewed = Set(true);
+	let viewed = viewed.update(&db).await?;
+	
+	run_strategy(&db, &feed2, &strat).await?;
+	
+	let duplicates = entry::Entity::find()
+		.filter(entry::Column::CanonicalId.eq(viewed.canonical_id.clone()))
+		.all(&db)
+		.await?;
+	assert_eq!(2, duplicates.len());
+	assert!(duplicates.iter().all(|entry| entry.viewed));
+	
+	let unviewed = feed2.find_related(entry::Entity)
+		.filter(entry::Column::Viewed.eq(false))
+		.all(&db)
+		.await?;
+	assert_eq!(2, unviewed.len());
+	
+	Ok(())
+}
+
+///Entries from before canonical ids existed get one when upgrading
+#[tokio::test]
+async fn migration_backfill() -> Result<(), DbErr> {
+	let db = sea_orm::Database::connect("sqlite::memory:").await?;
+	let before_canonical_ids = Migrator::migrations().iter()
+		.position(|migration| migration.name().ends_with("entry_canonical_id"))
+		.expect("migration should exist");
+	Migrator::up(&db, Some(before_canonical_ids as u32)).await?;
+	
+	db.execute_unprepared("INSERT INTO feed (url, name, strategy) VALUES ('feed', 'feed', 'Mock test')").await?;
+	db.execute_unprepared("
+		INSERT INTO entry (name, view_url, feed_entry_id, feed_id, produced_date)
+		VALUES ('old', 'https://www.example.com/old/?utm_source=feed', 'old', 1, '2000-01-01')
+	").await?;
+	
+	Migrator::up(&db, None).await?;
+	
+	let entry = entry::Entity::find().one(&db).await?.expect("entry should still be there");
+	assert_eq!(Some("example.com/old"), entry.canonical_id.as_deref());
+	
+	Ok(())
+}
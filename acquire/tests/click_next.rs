@@ -0,0 +1,153 @@
+mod common;
+use common::{init, feed, run_strategy, serve_fixtures};
+use sea_orm::{ModelTrait, QueryOrder, DatabaseConnection};
+use acquire::click_next::{ClickNextStrategy, Direction};
+use entities::prelude::*;
+
+fn strat() -> ClickNextStrategy {
+	let mut strat = ClickNextStrategy::default();
+	strat.title("h1.title").image("img#comic");
+	strat
+}
+
+///Urls of the entries of `fetch`, in the order they were crawled
+async fn fetched_urls(fetch: &fetch::Model, db: &DatabaseConnection) -> Result<Vec<String>, sea_orm::DbErr> {
+	let entries = fetch.find_related(entry::Entity)
+		.order_by_asc(entry::Column::Id)
+		.all(db).await?;
+	Ok(entries.into_iter().map(|entry| entry.view_url).collect())
+}
+
+///Walks forward until there's no next link, picking out the title and image
+#[tokio::test]
+async fn forward() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = strat();
+	let feed = feed(format!("{base}/click_next/1.html"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	let expected = (1..=4).map(|page| format!("{base}/click_next/{page}.html")).collect::<Vec<_>>();
+	assert_eq!(expected, fetched_urls(&fetch, &db).await?);
+	
+	let last = feed.find_related(entry::Entity)
+		.order_by_desc(entry::Column::ProducedDate)
+		.order_by_desc(entry::Column::ProducedTime)
+		.one(&db).await?
+		.expect("just fetched");
+	assert_eq!("Page 4", last.name);
+	assert_eq!(Some(format!("{base}/click_next/images/4.png")), last.embed_url);
+	
+	Ok(())
+}
+
+///The page cap gets respected, and the next fetch continues from the last known entry
+#[tokio::test]
+async fn resume() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let mut strat = strat();
+	strat.max_pages(2);
+	let feed = feed(format!("{base}/click_next/1.html"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	assert_eq!(vec![format!("{base}/click_next/1.html"), format!("{base}/click_next/2.html")], fetched_urls(&fetch, &db).await?);
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	assert_eq!(vec![format!("{base}/click_next/3.html"), format!("{base}/click_next/4.html")], fetched_urls(&fetch, &db).await?);
+	
+	//Nothing new since then
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert!(fetched_urls(&fetch, &db).await?.is_empty());
+	
+	Ok(())
+}
+
+///Backfilling follows the previous link instead
+#[tokio::test]
+async fn backward() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let mut strat = strat();
+	strat.direction(Direction::Backward);
+	let feed = feed(format!("{base}/click_next/4.html"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	let expected = (1..=4).rev().map(|page| format!("{base}/click_next/{page}.html")).collect::<Vec<_>>();
+	assert_eq!(expected, fetched_urls(&fetch, &db).await?);
+	
+	//Older pages still get older produced dates
+	let first = feed.find_related(entry::Entity)
+		.order_by_asc(entry::Column::ProducedDate)
+		.order_by_asc(entry::Column::ProducedTime)
+		.one(&db).await?
+		.expect("just fetched");
+	assert_eq!("Page 1", first.name);
+	
+	Ok(())
+}
+
+///Backfilling over several capped fetches keeps older pages before the ones from earlier fetches
+#[tokio::test]
+async fn backward_resume() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let mut strat = strat();
+	strat.direction(Direction::Backward).max_pages(2);
+	let feed = feed(format!("{base}/click_next/4.html"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	assert_eq!(vec![format!("{base}/click_next/4.html"), format!("{base}/click_next/3.html")], fetched_urls(&fetch, &db).await?);
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	assert_eq!(vec![format!("{base}/click_next/2.html"), format!("{base}/click_next/1.html")], fetched_urls(&fetch, &db).await?);
+	
+	let oldest_first = feed.find_related(entry::Entity)
+		.order_by_asc(entry::Column::ProducedDate)
+		.order_by_asc(entry::Column::ProducedTime)
+		.all(&db).await?
+		.into_iter()
+		.map(|entry| entry.name)
+		.collect::<Vec<_>>();
+	assert_eq!(vec!["Page 1", "Page 2", "Page 3", "Page 4"], oldest_first);
+	
+	Ok(())
+}
+
+///A link back to an already visited page stops the crawl
+#[tokio::test]
+async fn link_loop() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = ClickNextStrategy::default();
+	let feed = feed(format!("{base}/click_next/loop.html"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(vec![format!("{base}/click_next/loop.html")], fetched_urls(&fetch, &db).await?);
+	
+	Ok(())
+}
+
+///Selectors that don't parse become fetch errors
+#[tokio::test]
+async fn invalid_selector() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let mut strat = ClickNextStrategy::default();
+	strat.next("a[rel=");
+	let feed = feed(format!("{base}/click_next/1.html"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	assert!(fetch.error.is_some_and(|error| error.contains("Invalid selector")));
+	
+	Ok(())
+}
@@ -0,0 +1,102 @@
+mod common;
+use common::{init, feed, run_strategy};
+use sea_orm::{ModelTrait, QueryOrder};
+use acquire::{strategy::{Strategy, Named}, command::CommandStrategy};
+use entities::prelude::*;
+
+const SCRIPTS_PATH: &str = "tests/fixtures/command";
+
+///Runs one of the fixture scripts with sh, so they don't have to be executable
+fn script_strat(script: &str) -> CommandStrategy {
+	let mut strat = CommandStrategy::default();
+	strat.command("sh").args([format!("{SCRIPTS_PATH}/{script}")]);
+	strat
+}
+
+///Printed lines become entries
+#[tokio::test]
+async fn entries() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = script_strat("entries.sh");
+	let feed = feed("https://example.com", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	let entries = fetch.find_related(entry::Entity)
+		.order_by_asc(entry::Column::ProducedDate)
+		.all(&db).await?;
+	assert_eq!(2, entries.len());
+	
+	let second = &entries[1];
+	assert_eq!("2", second.feed_entry_id);
+	assert_eq!("Second", second.name);
+	assert_eq!("https://example.com/2", second.view_url);
+	assert_eq!(Some("https://example.com/2.png"), second.embed_url.as_deref());
+	assert_eq!(time::macros::date!(2024-01-02), second.produced_date.clone().into());
+	assert_eq!(Some(time::macros::time!(10:30)), second.produced_time.clone().into());
+	
+	Ok(())
+}
+
+///The last known entry gets passed along
+#[tokio::test]
+async fn last_entry() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = script_strat("resume.sh");
+	let feed = feed("https://example.com", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	let first = fetch.find_related(entry::Entity).one(&db).await?.expect("should have fetched one");
+	assert_eq!("1", first.feed_entry_id);
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	let second = fetch.find_related(entry::Entity).one(&db).await?.expect("should have fetched one");
+	assert_eq!("2", second.feed_entry_id);
+	
+	Ok(())
+}
+
+///A non-zero exit code becomes a fetch error, with stderr in the log
+#[tokio::test]
+async fn exit_code() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = script_strat("fail.sh");
+	let feed = feed("https://example.com", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	assert!(fetch.log.contains("Something went wrong"));
+	
+	Ok(())
+}
+
+///Lines that don't have all the fields are parse errors
+#[tokio::test]
+async fn invalid_line() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = script_strat("invalid.sh");
+	let feed = feed("https://example.com", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::ParseError);
+	
+	Ok(())
+}
+
+///Without a configured command, there's nothing to run
+#[tokio::test]
+async fn unconfigured() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = Named::new("Command test", CommandStrategy::default());
+	let feed = feed("https://example.com", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	assert_eq!("Command test", strat.name());
+	
+	Ok(())
+}
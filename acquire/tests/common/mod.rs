@@ -1,11 +1,14 @@
 //Not all tests use all the stuff in here, so they generate false warnings
 #![allow(dead_code)]
 
-use acquire::{strategy::{Strategy, self}, StrategyList, mock::{FetchCommand, CommandStrat, MockStrat}};
+use acquire::{strategy::{Strategy, RunOptions, RunContext, self}, StrategyList, mock::{FetchCommand, CommandStrat, MockStrat}};
 use sea_migration::{MigratorTrait, Migrator};
 use sea_orm::{DatabaseConnection, error::DbErr, Set, ActiveModelTrait, ActiveModelBehavior};
 use entities::{prelude::feed, entities::fetch};
 use tokio::sync::broadcast;
+use axum::{Router, routing::get, extract::Path, http::StatusCode};
+
+const FIXTURES_PATH: &str = "tests/fixtures";
 
 pub async fn db() -> Result<DatabaseConnection, DbErr> {
 	let conn = sea_orm::Database::connect("sqlite::memory:").await?;
@@ -53,7 +56,7 @@ pub async fn feed_strat_name(
 }
 
 pub async fn run_strategy(db: &DatabaseConnection, feed: &feed::Model, strategy: &dyn Strategy) -> Result<fetch::Model, DbErr> {
-	let fetch = strategy::run_strategy(db, feed, strategy).await?;
+	let fetch = strategy::run_strategy(db, feed, strategy, &RunOptions::default(), &RunContext::default()).await?;
 	
 	if let Some(ref error) = fetch.error {
 		//Formatted like this to preserve newlines
@@ -65,7 +68,7 @@ pub async fn run_strategy(db: &DatabaseConnection, feed: &feed::Model, strategy:
 	Ok(fetch)
 }
 
-pub fn single_strat_list(strat: impl Strategy + Send + Sync + 'static) -> StrategyList {
+pub fn single_strat_list(strat: impl Strategy + 'static) -> StrategyList {
 	let mut list = StrategyList::new();
 	list.add(strat);
 	list
@@ -81,4 +84,26 @@ pub fn cmd_strats() -> (broadcast::Sender<FetchCommand>, StrategyList) {
 	list.add(MockStrat::default());
 	
 	(sender, list)
+}
+
+pub fn fixture(path: impl AsRef<std::path::Path>) -> std::io::Result<String> {
+	std::fs::read_to_string(std::path::Path::new(FIXTURES_PATH).join(path))
+}
+
+///Serves `router` on a random local port in a new task, returns the url to reach it at
+pub async fn serve(router: Router) -> std::io::Result<String> {
+	let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+	let address = listener.local_addr()?;
+	tokio::spawn(async move {
+		axum::serve(listener, router).await.expect("test server should keep running");
+	});
+	Ok(format!("http://{address}"))
+}
+
+///Serves the files in the fixtures folder, returns the url to reach them at
+pub async fn serve_fixtures() -> std::io::Result<String> {
+	let router = Router::new().route("/*path", get(|Path(path): Path<String>| async move {
+		fixture(path).map_err(|_| StatusCode::NOT_FOUND)
+	}));
+	serve(router).await
 }
\ No newline at end of file
@@ -0,0 +1,5 @@
+#!/bin/sh
+# Prints two entries, the second one for the feed url it got
+echo '{"id": "1", "title": "First", "view_url": "https://example.com/1", "produced_date": "2024-01-01"}'
+echo
+echo "{\"id\": \"2\", \"title\": \"Second\", \"view_url\": \"$1/2\", \"embed_url\": \"$1/2.png\", \"produced_date\": \"2024-01-02\", \"produced_time\": \"10:30:00\"}"
@@ -0,0 +1,2 @@
+#!/bin/sh
+echo '{"id": "1", "title": "Missing the rest"}'
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Prints the entry after the last known one
+read -r request
+case "$request" in
+	*'"last_entry":null'*)
+		echo '{"id": "1", "title": "First", "view_url": "https://example.com/1", "produced_date": "2024-01-01"}';;
+	*'"id":"1"'*)
+		echo '{"id": "2", "title": "Second", "view_url": "https://example.com/2", "produced_date": "2024-01-02"}';;
+esac
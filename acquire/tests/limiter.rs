@@ -0,0 +1,175 @@
+mod common;
+use common::{init, feed, single_strat_list, feed_strat_name};
+use std::time::Duration;
+use acquire::{
+	strategy::{Strategy, RunOptions, RunContext, run_strategy},
+	retry::RetryPolicy,
+	mock::MockStrat,
+	batch_tracker::BatchTracker,
+	limiter::{Limiter, Limits, limit_key},
+};
+use entities::prelude::*;
+use sea_orm::{ModelTrait, PaginatorTrait};
+
+///Long enough for a permit to have been given out if it was going to be
+const WAIT: Duration = Duration::from_millis(50);
+
+async fn acquires(limiter: &Limiter, key: &str) -> bool {
+	tokio::time::timeout(WAIT, limiter.acquire(key.to_owned())).await.is_ok()
+}
+
+///A simple test that can copy/pasted to be the basis of other tests
+#[tokio::test]
+async fn basic() {
+	let limiter = Limiter::default();
+	
+	let _permit = limiter.acquire("example.com".to_owned()).await;
+}
+
+///The same host doesn't get more than its limit
+#[tokio::test]
+async fn per_host() {
+	let limiter = Limiter::new(Limits {
+		concurrency: 8,
+		per_host: 1,
+	});
+	
+	let permit = limiter.acquire("a".to_owned()).await;
+	
+	assert!(!acquires(&limiter, "a").await);
+	assert!(acquires(&limiter, "b").await);
+	
+	drop(permit);
+	
+	assert!(acquires(&limiter, "a").await);
+}
+
+///Keys without permits or waiting fetches don't stay around
+#[tokio::test]
+async fn idle_keys() {
+	let limiter = Limiter::default();
+	
+	let permit = limiter.acquire("a".to_owned()).await;
+	drop(limiter.acquire("b".to_owned()).await);
+	let _permit_c = limiter.acquire("c".to_owned()).await;
+	assert_eq!(2, limiter.key_count());
+	
+	drop(permit);
+	let _permit_d = limiter.acquire("d".to_owned()).await;
+	assert_eq!(2, limiter.key_count());
+}
+
+///Different hosts still share the total limit
+#[tokio::test]
+async fn total() {
+	let limiter = Limiter::new(Limits {
+		concurrency: 2,
+		per_host: 2,
+	});
+	
+	let _permit_a = limiter.acquire("a".to_owned()).await;
+	let permit_b = limiter.acquire("b".to_owned()).await;
+	
+	assert!(!acquires(&limiter, "c").await);
+	
+	drop(permit_b);
+	
+	assert!(acquires(&limiter, "c").await);
+}
+
+///Limits of 0 would block everything forever
+#[tokio::test]
+async fn zero() {
+	let limiter = Limiter::new(Limits {
+		concurrency: 0,
+		per_host: 0,
+	});
+	
+	assert_eq!(Limits { concurrency: 1, per_host: 1 }, limiter.limits());
+	assert!(acquires(&limiter, "a").await);
+}
+
+///Feeds get limited by host, or by strategy without one
+#[tokio::test]
+async fn keys() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	
+	let video = feed_strat_name("https://www.YouTube.com/@someone/videos", "yt-dlp", &db).await?;
+	let other_video = feed_strat_name("https://www.youtube.com/@someone_else", "yt-dlp", &db).await?;
+	let without_scheme = feed_strat_name("www.youtube.com/@another", "yt-dlp", &db).await?;
+	let video_id = feed_strat_name("dQw4w9WgXcQ", "yt-dlp", &db).await?;
+	let mock = feed_strat_name("ok", "mock", &db).await?;
+	
+	assert_eq!("youtube.com", limit_key(&video));
+	assert_eq!(limit_key(&video), limit_key(&other_video));
+	assert_eq!(limit_key(&video), limit_key(&without_scheme));
+	assert_eq!("strategy yt-dlp", limit_key(&video_id));
+	assert_eq!("strategy mock", limit_key(&mock));
+	
+	Ok(())
+}
+
+///Batches still fetch everything with the lowest limits
+#[tokio::test]
+async fn tracked() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let strat_name = strat.name().to_owned();
+	let strats = single_strat_list(strat);
+	
+	let feed1 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed2 = feed_strat_name("ok", &strat_name, &db).await?;
+	let feed3 = feed_strat_name("ok", &strat_name, &db).await?;
+	
+	let tracker = BatchTracker::new(Limiter::new(Limits {
+		concurrency: 1,
+		per_host: 1,
+	}));
+	
+	let id = tracker.queue_fetches(vec![feed1.id, feed2.id, feed3.id], db.clone(), strats).await?;
+	tracker.await_fetch(id).await?;
+	
+	assert_eq!(1, feed1.find_related(fetch::Entity).count(&db).await? );
+	assert_eq!(1, feed2.find_related(fetch::Entity).count(&db).await? );
+	assert_eq!(1, feed3.find_related(fetch::Entity).count(&db).await? );
+	
+	Ok(())
+}
+
+///Fetches waiting to try again don't keep other fetches of the same host waiting
+#[tokio::test]
+async fn released_during_backoff() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let flaky = feed("flaky", &strat, &db).await?;
+	
+	let limiter = Limiter::new(Limits {
+		concurrency: 8,
+		per_host: 1,
+	});
+	let options = RunOptions {
+		retry: RetryPolicy::new(2, WAIT * 4),
+		..Default::default()
+	};
+	let context = RunContext {
+		limiter: Some(limiter.clone()),
+		..Default::default()
+	};
+	
+	let task = {
+		let db = db.clone();
+		let strat = strat.clone();
+		let flaky = flaky.clone();
+		tokio::spawn(async move {
+			run_strategy(&db, &flaky, &strat, &options, &context).await
+		})
+	};
+	
+	tokio::time::sleep(WAIT).await;
+	assert!(acquires(&limiter, &limit_key(&flaky)).await);
+	
+	let fetch = task.await??;
+	assert_eq!(fetch::Status::Success, fetch.status);
+	
+	Ok(())
+}
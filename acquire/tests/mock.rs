@@ -1,8 +1,10 @@
 mod common;
 use common::{init, feed, run_strategy};
 use sea_orm::{DbErr, ModelTrait, PaginatorTrait, QueryOrder};
+use std::time::Duration;
 use acquire::{
-	strategy::Strategy,
+	strategy::{self, Strategy, RunOptions, RunContext},
+	retry::RetryPolicy,
 	mock::MockStrat
 };
 use entities::prelude::*;
@@ -134,4 +136,129 @@ async fn entries() -> Result<(), DbErr> {
 	assert_eq!(1, entry_14.find_related(fetch::Entity).count(&db).await? );
 	
 	Ok(())
-}
\ No newline at end of file
+}
+///Not modified fetches keep their validators but have no content
+#[tokio::test]
+async fn not_modified() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("not modified", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::NotModified);
+	assert!(fetch.error.is_none());
+	assert!(fetch.content.is_none());
+	assert_eq!(Some("\"mock\""), fetch.etag.as_deref());
+	assert_eq!(0, fetch.find_related(entry::Entity).count(&db).await? );
+	
+	Ok(())
+}
+
+fn timeout_options() -> RunOptions {
+	RunOptions {
+		timeout: Some(Duration::from_millis(50)),
+		..Default::default()
+	}
+}
+
+///Fetches that take too long time out
+#[tokio::test]
+async fn fetch_timeout() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("hang", &strat, &db).await?;
+	
+	let fetch = strategy::run_strategy(&db, &feed, &strat, &timeout_options(), &RunContext::default()).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Timeout);
+	assert!(fetch.error.is_some());
+	assert!(fetch.content.is_none());
+	
+	Ok(())
+}
+
+///Parsing that takes too long times out, but keeps the fetched content
+#[tokio::test]
+async fn parse_timeout() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("hang parse", &strat, &db).await?;
+	
+	let fetch = strategy::run_strategy(&db, &feed, &strat, &timeout_options(), &RunContext::default()).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Timeout);
+	assert!(fetch.error.is_some());
+	assert!(fetch.content.is_some());
+	
+	Ok(())
+}
+
+
+fn retry_options(max_attempts: u32) -> RunOptions {
+	RunOptions {
+		retry: RetryPolicy::new(max_attempts, Duration::from_millis(1)),
+		..Default::default()
+	}
+}
+
+///Transient errors get tried again until the attempts run out, with every attempt in the log
+#[tokio::test]
+async fn retry_transient() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("transient error", &strat, &db).await?;
+	
+	let fetch = strategy::run_strategy(&db, &feed, &strat, &retry_options(3), &RunContext::default()).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	assert!(fetch.error.is_some());
+	assert_eq!(3, fetch.log.matches("Mock transient error").count());
+	
+	Ok(())
+}
+
+///Permanent errors don't get tried again
+#[tokio::test]
+async fn retry_permanent() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("fetch error", &strat, &db).await?;
+	
+	let fetch = strategy::run_strategy(&db, &feed, &strat, &retry_options(3), &RunContext::default()).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	assert_eq!(1, fetch.log.matches("Mock fetch error").count());
+	
+	Ok(())
+}
+
+///A fetch that fails once succeeds on the retry, but the failure stays in the log
+#[tokio::test]
+async fn retry_success() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("flaky", &strat, &db).await?;
+	
+	let fetch = strategy::run_strategy(&db, &feed, &strat, &retry_options(3), &RunContext::default()).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert!(fetch.error.is_none());
+	assert!(fetch.log.contains("Mock transient flaky error"));
+	
+	Ok(())
+}
+
+///Without a retry policy, transient errors also only get tried once
+#[tokio::test]
+async fn no_retry() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("flaky", &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	
+	Ok(())
+}
@@ -0,0 +1,131 @@
+mod common;
+use common::{init, fixture};
+use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder};
+use acquire::{
+	opml,
+	rss::RssStrategy,
+	yt_dlp::YtDlpStrategy,
+	StrategyList,
+};
+use entities::prelude::*;
+
+fn rss_strats() -> StrategyList {
+	let mut list = StrategyList::new();
+	list.add(RssStrategy::default());
+	list
+}
+
+///Nested folders become tags
+#[test]
+fn parse() -> Result<(), anyhow::Error> {
+	let feeds = opml::parse(&fixture("opml/subscriptions.opml")?)?;
+	
+	assert_eq!(4, feeds.len());
+	assert_eq!("Example Blog", feeds[0].title);
+	assert_eq!("https://example.com/feed.xml", feeds[0].url);
+	assert_eq!(vec!["Tech"], feeds[0].tags);
+	assert_eq!(vec!["Tech", "Rust"], feeds[1].tags);
+	assert_eq!(vec!["Videos"], feeds[2].tags);
+	assert_eq!("Untagged & Co", feeds[3].title);
+	assert!(feeds[3].tags.is_empty());
+	
+	Ok(())
+}
+
+///YouTube pages need something else than an RSS feed
+#[test]
+fn guess_strategy() {
+	let rss = rss_strats();
+	let mut both = rss_strats();
+	both.add(YtDlpStrategy::default());
+	
+	assert_eq!(Some("rss"), opml::guess_strategy("https://example.com/feed.xml", &rss).as_deref());
+	assert_eq!(Some("rss"), opml::guess_strategy("https://www.youtube.com/@somechannel", &rss).as_deref());
+	assert_eq!(Some("yt-dlp"), opml::guess_strategy("https://www.youtube.com/@somechannel", &both).as_deref());
+	assert_eq!(Some("rss"), opml::guess_strategy("https://www.youtube.com/feeds/videos.xml?channel_id=abc", &both).as_deref());
+	assert_eq!(None, opml::guess_strategy("https://example.com/feed.xml", &StrategyList::new()));
+}
+
+///Importing creates feeds and tags, and doesn't duplicate them when importing again
+#[tokio::test]
+async fn import() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strats = rss_strats();
+	let feeds = opml::parse(&fixture("opml/subscriptions.opml")?)?;
+	
+	let summary = opml::import(&db, &strats, feeds.clone()).await?;
+	
+	assert_eq!(4, summary.created);
+	assert_eq!(0, summary.existing);
+	assert_eq!(4, summary.tagged);
+	assert_eq!(4, feed::Entity::find().count(&db).await? );
+	assert_eq!(3, tag::Entity::find().count(&db).await? );
+	
+	let rust_blog = feed::Entity::find()
+		.filter(feed::Column::Url.eq("https://blog.rust-lang.org/feed.xml"))
+		.one(&db).await?
+		.expect("feed should have been imported");
+	assert_eq!("rss", rust_blog.strategy);
+	let tags = rust_blog.find_related(tag::Entity)
+		.order_by_asc(tag::Column::Title)
+		.all(&db).await?
+		.into_iter()
+		.map(|tag| tag.title)
+		.collect::<Vec<_>>();
+	assert_eq!(vec!["Rust", "Tech"], tags);
+	
+	let summary = opml::import(&db, &strats, feeds).await?;
+	
+	assert_eq!(0, summary.created);
+	assert_eq!(4, summary.existing);
+	assert_eq!(0, summary.tagged);
+	assert_eq!(4, feed::Entity::find().count(&db).await? );
+	assert_eq!(3, tag::Entity::find().count(&db).await? );
+	assert_eq!(4, feed_tag::Entity::find().count(&db).await? );
+	
+	Ok(())
+}
+
+///Feeds without a usable strategy get skipped
+#[tokio::test]
+async fn import_skipped() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let feeds = opml::parse(&fixture("opml/subscriptions.opml")?)?;
+	
+	let summary = opml::import(&db, &StrategyList::new(), feeds).await?;
+	
+	assert_eq!(0, summary.created);
+	assert_eq!(4, summary.skipped.len());
+	assert_eq!(0, feed::Entity::find().count(&db).await? );
+	
+	Ok(())
+}
+
+///Exporting and importing again results in the same feeds and tags
+#[tokio::test]
+async fn roundtrip() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strats = rss_strats();
+	let feeds = opml::parse(&fixture("opml/subscriptions.opml")?)?;
+	opml::import(&db, &strats, feeds).await?;
+	
+	let exported = opml::export(&db).await?;
+	let mut reparsed = opml::parse(&exported)?;
+	
+	//The Rust Blog is in 2 folders
+	assert_eq!(5, reparsed.len());
+	reparsed.sort_by(|a, b| a.url.cmp(&b.url).then(a.tags.cmp(&b.tags)));
+	assert_eq!("https://blog.rust-lang.org/feed.xml", reparsed[0].url);
+	assert_eq!(vec!["Rust"], reparsed[0].tags);
+	assert_eq!(vec!["Tech"], reparsed[1].tags);
+	assert!(reparsed.iter().all(|feed| feed.strategy.as_deref() == Some("rss")));
+	
+	let other_db = init().await?;
+	let summary = opml::import(&other_db, &strats, reparsed).await?;
+	
+	assert_eq!(4, summary.created);
+	assert_eq!(1, summary.existing);
+	assert_eq!(4, feed_tag::Entity::find().count(&other_db).await? );
+	
+	Ok(())
+}
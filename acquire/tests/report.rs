@@ -0,0 +1,139 @@
+mod common;
+use common::{init, feed, run_strategy};
+use acquire::{
+	mock::MockStrat,
+	report::{feed_health, ReportSettings, Severity},
+};
+use entities::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveModelBehavior, DatabaseConnection, DbErr, Set};
+use time::macros::{date, datetime};
+
+const NOW: time::PrimitiveDateTime = datetime!(2024-06-30 12:00);
+
+async fn entry(feed: &feed::Model, produced: time::Date, db: &DatabaseConnection) -> Result<(), DbErr> {
+	let mut entry = entry::ActiveModel::new();
+	entry.name = Set(format!("Entry {produced}"));
+	entry.view_url = Set("https://example.com".to_owned());
+	entry.viewed = Set(false);
+	entry.feed_entry_id = Set(produced.to_string());
+	entry.feed_id = Set(feed.id);
+	entry.produced_date = Set(produced.into());
+	entry.produced_time = Set(time_fields::OptionTime(None));
+	entry.insert(db).await?;
+	Ok(())
+}
+
+///A feed posting weekly that posted recently is healthy
+#[tokio::test]
+async fn healthy() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	for produced in [date!(2024-06-13), date!(2024-06-20), date!(2024-06-27)] {
+		entry(&feed, produced, &db).await?;
+	}
+	
+	let report = feed_health(&db, &ReportSettings::default(), NOW).await?;
+	
+	assert_eq!(1, report.len());
+	let health = &report[0];
+	assert_eq!(feed.id, health.feed_id);
+	assert_eq!(Some(fetch.created_at.0), health.last_success);
+	assert_eq!(0, health.consecutive_failures);
+	assert_eq!(Some(date!(2024-06-27)), health.last_produced);
+	assert_eq!(Some(time::Duration::weeks(1).unsigned_abs()), health.average_interval);
+	assert!(!health.stale);
+	assert_eq!(Severity::Healthy, health.severity);
+	
+	Ok(())
+}
+
+///Feeds without entries or that stopped posting are stale, failing feeds come first
+#[tokio::test]
+async fn severity() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	
+	let healthy = feed("ok", &strat, &db).await?;
+	entry(&healthy, date!(2024-06-29), &db).await?;
+	
+	let stopped = feed("ok", &strat, &db).await?;
+	for produced in [date!(2024-01-01), date!(2024-01-02), date!(2024-01-03)] {
+		entry(&stopped, produced, &db).await?;
+	}
+	
+	let empty = feed("ok", &strat, &db).await?;
+	
+	let failing = feed("fetch error", &strat, &db).await?;
+	entry(&failing, date!(2024-06-29), &db).await?;
+	for _ in 0..3 {
+		run_strategy(&db, &failing, &strat).await?;
+	}
+	
+	let report = feed_health(&db, &ReportSettings::default(), NOW).await?;
+	
+	let order = report.iter().map(|health| (health.feed_id, health.severity)).collect::<Vec<_>>();
+	assert_eq!(vec![
+		(failing.id, Severity::Failing),
+		//Never produced anything, so sorts before the one that stopped
+		(empty.id, Severity::Stale),
+		(stopped.id, Severity::Stale),
+		(healthy.id, Severity::Healthy),
+	], order);
+	
+	let failing_health = &report[0];
+	assert_eq!(3, failing_health.consecutive_failures);
+	assert_eq!(None, failing_health.last_success);
+	assert!(!failing_health.stale);
+	
+	Ok(())
+}
+
+///Successful fetches reset the consecutive failures
+#[tokio::test]
+async fn failures_since_success() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	entry(&feed, date!(2024-06-29), &db).await?;
+	let success = run_strategy(&db, &feed, &strat).await?;
+	
+	let mut failing = feed::ActiveModel::from(feed.clone());
+	failing.url = Set("fetch error".to_owned());
+	let failing = failing.update(&db).await?;
+	run_strategy(&db, &failing, &strat).await?;
+	
+	let report = feed_health(&db, &ReportSettings::default(), NOW).await?;
+	
+	assert_eq!(1, report[0].consecutive_failures);
+	assert_eq!(Some(success.created_at.0), report[0].last_success);
+	assert_eq!(Severity::Healthy, report[0].severity);
+	
+	Ok(())
+}
+
+///Fetches of other feeds don't count towards a feed's failures
+#[tokio::test]
+async fn failures_per_feed() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let failing = feed("fetch error", &strat, &db).await?;
+	let healthy = feed("ok", &strat, &db).await?;
+	entry(&healthy, date!(2024-06-29), &db).await?;
+	
+	run_strategy(&db, &failing, &strat).await?;
+	let success = run_strategy(&db, &healthy, &strat).await?;
+	run_strategy(&db, &failing, &strat).await?;
+	
+	let report = feed_health(&db, &ReportSettings::default(), NOW).await?;
+	
+	let failing_health = report.iter().find(|health| health.feed_id == failing.id).expect("every feed is in the report");
+	assert_eq!(2, failing_health.consecutive_failures);
+	assert_eq!(None, failing_health.last_success);
+	let healthy_health = report.iter().find(|health| health.feed_id == healthy.id).expect("every feed is in the report");
+	assert_eq!(0, healthy_health.consecutive_failures);
+	assert_eq!(Some(success.created_at.0), healthy_health.last_success);
+	
+	Ok(())
+}
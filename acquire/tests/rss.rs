@@ -0,0 +1,251 @@
+mod common;
+use common::{init, feed, run_strategy, fixture, serve, serve_fixtures};
+use axum::{Router, routing::get, http::{HeaderMap, StatusCode, header}, response::IntoResponse};
+use sea_orm::{ModelTrait, PaginatorTrait, QueryOrder};
+use acquire::{
+	strategy::Strategy,
+	retry::ErrorKind,
+	rss::RssStrategy
+};
+use entities::prelude::*;
+
+///A simple test that can copy/pasted to be the basis of other tests
+#[tokio::test]
+async fn basic() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = RssStrategy::default();
+	let feed = feed(format!("{base}/rss/rss.xml"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	
+	Ok(())
+}
+
+///RSS items get parsed correctly
+#[tokio::test]
+async fn parse_rss() -> Result<(), anyhow::Error> {
+	let strat = RssStrategy::default();
+	let data = fixture("rss/rss.xml")?;
+	
+	let entries = strat.parse(&data).await?;
+	
+	assert_eq!(3, entries.len());
+	
+	let third = &entries[0];
+	assert_eq!("example-post-3", third.get_feed_entry_id());
+	assert_eq!("Third post", third.get_title());
+	assert_eq!("https://blog.example.com/posts/3", third.get_view_url());
+	assert_eq!(time::macros::date!(2024-01-03), third.get_produced_date());
+	assert_eq!(Some(time::macros::time!(10:00)), third.get_produced_time());
+	
+	//Gets converted to UTC
+	let second = &entries[1];
+	assert_eq!("https://blog.example.com/posts/2", second.get_feed_entry_id());
+	assert_eq!(time::macros::date!(2024-01-03), second.get_produced_date());
+	assert_eq!(Some(time::macros::time!(01:30)), second.get_produced_time());
+	
+	//Falls back to the link without a guid
+	let first = &entries[2];
+	assert_eq!("https://blog.example.com/posts/1", first.get_feed_entry_id());
+	assert_eq!(time::macros::date!(2024-01-01), first.get_produced_date());
+	
+	Ok(())
+}
+
+///Atom entries get parsed correctly
+#[tokio::test]
+async fn parse_atom() -> Result<(), anyhow::Error> {
+	let strat = RssStrategy::default();
+	let data = fixture("rss/atom.xml")?;
+	
+	let entries = strat.parse(&data).await?;
+	
+	assert_eq!(2, entries.len());
+	
+	let second = &entries[0];
+	assert_eq!("tag:comic.example.com,2024:page-2", second.get_feed_entry_id());
+	assert_eq!("Page 2: <i>Onwards</i>", second.get_title());
+	assert_eq!("https://comic.example.com/page/2", second.get_view_url());
+	assert_eq!(time::macros::date!(2024-02-10), second.get_produced_date());
+	assert_eq!(Some(time::macros::time!(12:00)), second.get_produced_time());
+	
+	//Falls back to published, gets converted to UTC
+	let first = &entries[1];
+	assert_eq!("https://comic.example.com/page/1", first.get_view_url());
+	assert_eq!(time::macros::date!(2024-02-03), first.get_produced_date());
+	assert_eq!(Some(time::macros::time!(08:30)), first.get_produced_time());
+	
+	Ok(())
+}
+
+///Fetched entries get saved
+#[tokio::test]
+async fn entries() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = RssStrategy::default();
+	let feed = feed(format!("{base}/rss/atom.xml"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(2, fetch.find_related(entry::Entity).count(&db).await? );
+	
+	let latest = feed.find_related(entry::Entity)
+		.order_by_desc(entry::Column::ProducedDate)
+		.one(&db).await?
+		.expect("just fetched");
+	assert_eq!("https://comic.example.com/page/2", latest.view_url);
+	
+	//Fetching again shouldn't duplicate entries
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(2, feed.find_related(entry::Entity).count(&db).await? );
+	
+	Ok(())
+}
+
+///HTTP errors become fetch errors
+#[tokio::test]
+async fn not_found() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = RssStrategy::default();
+	let feed = feed(format!("{base}/rss/does_not_exist.xml"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::FetchError);
+	assert!(fetch.error.is_some());
+	assert!(fetch.log.contains("404"));
+	
+	Ok(())
+}
+
+///Documents that aren't RSS or Atom become parse errors
+#[tokio::test]
+async fn unsupported() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = RssStrategy::default();
+	let feed = feed(format!("{base}/rss/unsupported.xml"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::ParseError);
+	assert!(fetch.content.is_some());
+	
+	Ok(())
+}
+
+///Items without a date get skipped, the rest still become entries
+#[tokio::test]
+async fn missing_date() -> Result<(), anyhow::Error> {
+	let strat = RssStrategy::default();
+	let data = fixture("rss/missing_date.xml")?;
+	
+	let entries = strat.parse(&data).await?;
+	
+	assert_eq!(1, entries.len());
+	assert_eq!("https://undated.example.com/dated", entries[0].get_feed_entry_id());
+	
+	Ok(())
+}
+
+const ETAG: &str = "\"v1\"";
+const LAST_MODIFIED: &str = "Sat, 10 Feb 2024 12:00:00 GMT";
+
+///Serves the atom fixture, but only when the request doesn't already have the current version
+async fn serve_conditional() -> std::io::Result<String> {
+	let router = Router::new()
+		.route("/etag", get(|headers: HeaderMap| async move {
+			if headers.get(header::IF_NONE_MATCH).is_some_and(|etag| etag == ETAG) {
+				return StatusCode::NOT_MODIFIED.into_response();
+			}
+			([(header::ETAG, ETAG)], fixture("rss/atom.xml").expect("fixture should exist")).into_response()
+		}))
+		.route("/last_modified", get(|headers: HeaderMap| async move {
+			if headers.get(header::IF_MODIFIED_SINCE).is_some_and(|date| date == LAST_MODIFIED) {
+				return StatusCode::NOT_MODIFIED.into_response();
+			}
+			([(header::LAST_MODIFIED, LAST_MODIFIED)], fixture("rss/atom.xml").expect("fixture should exist")).into_response()
+		}));
+	serve(router).await
+}
+
+///The ETag gets stored and sent along with the next fetch
+#[tokio::test]
+async fn etag() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_conditional().await?;
+	let strat = RssStrategy::default();
+	let feed = feed(format!("{base}/etag"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(Some(ETAG), fetch.etag.as_deref());
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::NotModified);
+	assert!(fetch.content.is_none());
+	assert_eq!(0, fetch.find_related(entry::Entity).count(&db).await? );
+	//The server didn't repeat it, so it should get carried over
+	assert_eq!(Some(ETAG), fetch.etag.as_deref());
+	
+	//Still has the validators of the previous not modified fetch
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::NotModified);
+	
+	Ok(())
+}
+
+///The Last-Modified date gets stored and sent along with the next fetch
+#[tokio::test]
+async fn last_modified() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_conditional().await?;
+	let strat = RssStrategy::default();
+	let feed = feed(format!("{base}/last_modified"), &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(Some(LAST_MODIFIED), fetch.last_modified.as_deref());
+	assert!(fetch.etag.is_none());
+	
+	let fetch = run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::NotModified);
+	assert_eq!(Some(LAST_MODIFIED), fetch.last_modified.as_deref());
+	
+	Ok(())
+}
+
+
+///Server errors are worth trying again, missing feeds aren't
+#[tokio::test]
+async fn error_classification() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let router = Router::new()
+		.route("/unavailable", get(|| async { StatusCode::SERVICE_UNAVAILABLE }))
+		.route("/missing", get(|| async { StatusCode::NOT_FOUND }));
+	let base = serve(router).await?;
+	let strat = RssStrategy::default();
+	
+	let unavailable = feed(format!("{base}/unavailable"), &strat, &db).await?;
+	let err = strat.fetch(&db, &unavailable).await.expect_err("server should have errored");
+	assert_eq!(ErrorKind::Transient, strat.classify_error(&err));
+	
+	let missing = feed(format!("{base}/missing"), &strat, &db).await?;
+	let err = strat.fetch(&db, &missing).await.expect_err("server should have errored");
+	assert_eq!(ErrorKind::Permanent, strat.classify_error(&err));
+	
+	Ok(())
+}
@@ -0,0 +1,151 @@
+mod common;
+use common::{init, feed, run_strategy, single_strat_list};
+use std::time::Duration;
+use acquire::{
+	mock::MockStrat,
+	batch_tracker::BatchTracker,
+	scheduler::{due_feeds, Scheduler, MAX_BACKOFF_EXPONENT},
+	RunError,
+};
+use entities::prelude::*;
+use sea_orm::{ActiveModelTrait, DbErr, IntoActiveModel, ModelTrait, PaginatorTrait, Set};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+fn in_future(duration: Duration) -> time::PrimitiveDateTime {
+	let then = time::OffsetDateTime::now_utc() + duration;
+	time::PrimitiveDateTime::new(then.date(), then.time())
+}
+
+///A simple test that can copy/pasted to be the basis of other tests
+#[tokio::test]
+async fn basic() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	
+	let due = due_feeds(&db, HOUR, in_future(Duration::ZERO)).await?;
+	
+	assert_eq!(vec![feed.id], due);
+	
+	Ok(())
+}
+
+///Feeds are due again once their interval passed
+#[tokio::test]
+async fn interval() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	
+	run_strategy(&db, &feed, &strat).await?;
+	
+	assert!(due_feeds(&db, HOUR, in_future(Duration::ZERO)).await?.is_empty());
+	assert_eq!(vec![feed.id], due_feeds(&db, HOUR, in_future(HOUR * 2)).await?);
+	
+	Ok(())
+}
+
+///A feeds own interval overrides the default, and disables scheduling when not positive
+#[tokio::test]
+async fn feed_interval() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed_short = feed("ok", &strat, &db).await?;
+	let feed_disabled = feed("ok", &strat, &db).await?;
+	
+	let mut active = feed_short.clone().into_active_model();
+	active.refresh_interval = Set(Some(10));
+	let feed_short = active.update(&db).await?;
+	let mut active = feed_disabled.into_active_model();
+	active.refresh_interval = Set(Some(0));
+	let feed_disabled = active.update(&db).await?;
+	
+	run_strategy(&db, &feed_short, &strat).await?;
+	run_strategy(&db, &feed_disabled, &strat).await?;
+	
+	let due = due_feeds(&db, HOUR, in_future(Duration::from_secs(20 * 60))).await?;
+	
+	assert_eq!(vec![feed_short.id], due);
+	
+	Ok(())
+}
+
+///Failing feeds wait exponentially longer
+#[tokio::test]
+async fn backoff() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("fetch error", &strat, &db).await?;
+	
+	run_strategy(&db, &feed, &strat).await?;
+	run_strategy(&db, &feed, &strat).await?;
+	
+	//2 failures means 4 times the interval
+	assert!(due_feeds(&db, HOUR, in_future(HOUR * 3)).await?.is_empty());
+	assert_eq!(vec![feed.id], due_feeds(&db, HOUR, in_future(HOUR * 5)).await?);
+	
+	//The backoff doesn't grow forever
+	for _ in 0..MAX_BACKOFF_EXPONENT {
+		run_strategy(&db, &feed, &strat).await?;
+	}
+	let max_wait = HOUR * 2u32.pow(MAX_BACKOFF_EXPONENT);
+	assert_eq!(vec![feed.id], due_feeds(&db, HOUR, in_future(max_wait + HOUR)).await?);
+	
+	Ok(())
+}
+
+///A success resets the backoff
+#[tokio::test]
+async fn backoff_reset() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("fetch error", &strat, &db).await?;
+	
+	run_strategy(&db, &feed, &strat).await?;
+	run_strategy(&db, &feed, &strat).await?;
+	
+	let mut active = feed.into_active_model();
+	active.url = Set("ok".into());
+	let feed = active.update(&db).await?;
+	run_strategy(&db, &feed, &strat).await?;
+	
+	assert_eq!(vec![feed.id], due_feeds(&db, HOUR, in_future(HOUR * 2)).await?);
+	
+	Ok(())
+}
+
+///A cycle fetches the due feeds through the tracker
+#[tokio::test]
+async fn cycle() -> Result<(), RunError> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	let strats = single_strat_list(strat);
+	let tracker = BatchTracker::default();
+	let scheduler = Scheduler::new(HOUR, HOUR);
+	
+	scheduler.cycle(&tracker, &db, &strats).await.expect("cycle should succeed");
+	
+	assert_eq!(1, feed.find_related(fetch::Entity).count(&db).await? );
+	
+	//Just fetched, so shouldn't be due again
+	scheduler.cycle(&tracker, &db, &strats).await.expect("cycle should succeed");
+	
+	assert_eq!(1, feed.find_related(fetch::Entity).count(&db).await? );
+	
+	Ok(())
+}
+
+///A zero period doesn't panic, the scheduler just doesn't run
+#[tokio::test]
+async fn zero_period() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strats = single_strat_list(MockStrat::default());
+	let scheduler = Scheduler::new(HOUR, Duration::ZERO);
+	
+	let run = scheduler.run(BatchTracker::default(), db, strats);
+	tokio::time::timeout(Duration::from_secs(5), run).await.expect("run should return right away");
+	
+	Ok(())
+}
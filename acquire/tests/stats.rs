@@ -0,0 +1,132 @@
+mod common;
+use common::{init, feed};
+use acquire::{
+	mock::MockStrat,
+	stats::{consumption, Bucket, Grouping, Period},
+};
+use entities::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveModelBehavior, DatabaseConnection, DbErr, Set};
+use time::macros::{date, datetime};
+
+///An entry that's viewed if `viewed_at` is set
+async fn entry(
+	feed: &feed::Model,
+	produced: time::Date,
+	viewed_at: Option<time::PrimitiveDateTime>,
+	db: &DatabaseConnection
+) -> Result<entry::Model, DbErr> {
+	let mut entry = entry::ActiveModel::new();
+	entry.name = Set(format!("Entry {produced}"));
+	entry.view_url = Set("https://example.com".to_owned());
+	entry.viewed = Set(viewed_at.is_some());
+	entry.viewed_at = Set(time_fields::OptionDateTime(viewed_at));
+	entry.feed_entry_id = Set(produced.to_string());
+	entry.feed_id = Set(feed.id);
+	entry.produced_date = Set(produced.into());
+	entry.produced_time = Set(time_fields::OptionTime(None));
+	
+	entry.insert(db).await
+}
+
+fn counts(buckets: &[Bucket]) -> Vec<(u64, u64)> {
+	buckets.iter().map(|bucket| (bucket.produced, bucket.consumed)).collect()
+}
+
+///Entries count as produced on their produced date, and consumed on the day they got viewed
+#[tokio::test]
+async fn per_day() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	
+	entry(&feed, date!(2024-06-01), Some(datetime!(2024-06-03 10:00)), &db).await?;
+	entry(&feed, date!(2024-06-01), None, &db).await?;
+	entry(&feed, date!(2024-06-02), Some(datetime!(2024-06-03 23:59)), &db).await?;
+	//Produced before the range, but consumed in it
+	entry(&feed, date!(2024-05-01), Some(datetime!(2024-06-02 12:00)), &db).await?;
+	//Outside the range
+	entry(&feed, date!(2024-06-10), None, &db).await?;
+	
+	let series = consumption(&db, Period::Day, Grouping::All, date!(2024-06-01), date!(2024-06-03)).await?;
+	
+	assert_eq!(1, series.len());
+	let starts = series[0].buckets.iter().map(|bucket| bucket.start).collect::<Vec<_>>();
+	assert_eq!(vec![date!(2024-06-01), date!(2024-06-02), date!(2024-06-03)], starts);
+	assert_eq!(vec![(2, 0), (1, 1), (0, 2)], counts(&series[0].buckets));
+	
+	Ok(())
+}
+
+///Viewing an entry marks its duplicates viewed too, but only counts once
+#[tokio::test]
+async fn duplicates() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let feed = feed("ok", &strat, &db).await?;
+	
+	for _ in 0..2 {
+		let mut duplicate = entry::ActiveModel::from(
+			entry(&feed, date!(2024-06-01), Some(datetime!(2024-06-02 10:00)), &db).await?
+		);
+		duplicate.canonical_id = Set(Some("example.com/duplicate".to_owned()));
+		duplicate.update(&db).await?;
+	}
+	
+	let series = consumption(&db, Period::Day, Grouping::All, date!(2024-06-01), date!(2024-06-02)).await?;
+	
+	assert_eq!(vec![(2, 0), (0, 1)], counts(&series[0].buckets));
+	
+	Ok(())
+}
+
+///Weeks start on monday, and there's always a series for all entries
+#[tokio::test]
+async fn per_week() -> Result<(), DbErr> {
+	let db = init().await?;
+	
+	let empty = consumption(&db, Period::Week, Grouping::All, date!(2024-06-05), date!(2024-06-20)).await?;
+	
+	assert_eq!(1, empty.len());
+	let starts = empty[0].buckets.iter().map(|bucket| bucket.start).collect::<Vec<_>>();
+	assert_eq!(vec![date!(2024-06-03), date!(2024-06-10), date!(2024-06-17)], starts);
+	assert_eq!(vec![(0, 0); 3], counts(&empty[0].buckets));
+	
+	Ok(())
+}
+
+///Feeds get their own series, and entries count for every tag of their feed
+#[tokio::test]
+async fn grouped() -> Result<(), DbErr> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	let first = feed("ok", &strat, &db).await?;
+	let second = feed("ok", &strat, &db).await?;
+	let _without_entries = feed("ok", &strat, &db).await?;
+	
+	let mut tag = tag::ActiveModel::new();
+	tag.title = Set("Comics".to_owned());
+	let tag = tag.insert(&db).await?;
+	for feed in [&first, &second] {
+		let mut feed_tag = feed_tag::ActiveModel::new();
+		feed_tag.feed_id = Set(feed.id);
+		feed_tag.tag_id = Set(tag.id);
+		feed_tag.insert(&db).await?;
+	}
+	
+	entry(&first, date!(2024-06-01), None, &db).await?;
+	entry(&second, date!(2024-06-01), Some(datetime!(2024-06-01 12:00)), &db).await?;
+	
+	let per_feed = consumption(&db, Period::Day, Grouping::Feed, date!(2024-06-01), date!(2024-06-01)).await?;
+	let mut names = per_feed.iter().map(|series| series.name.clone()).collect::<Vec<_>>();
+	names.sort();
+	let mut expected = vec![first.name.clone(), second.name.clone()];
+	expected.sort();
+	assert_eq!(expected, names);
+	
+	let per_tag = consumption(&db, Period::Day, Grouping::Tag, date!(2024-06-01), date!(2024-06-01)).await?;
+	assert_eq!(1, per_tag.len());
+	assert_eq!("Comics", per_tag[0].name);
+	assert_eq!(vec![(2, 1)], counts(&per_tag[0].buckets));
+	
+	Ok(())
+}
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+use acquire::{
+	strategy::{DynStrategyClone, Named},
+	strategy_config::{to_ron, from_ron, ConfigFolder},
+	click_next::ClickNextStrategy,
+	mock::MockStrat,
+	StrategyList,
+};
+
+///Empty folder in the temp directory, unique to the test
+fn temp_folder(test: &str) -> std::io::Result<PathBuf> {
+	let path = std::env::temp_dir().join(format!("ff-strategy-config-{}-{test}", std::process::id()));
+	if path.try_exists()? {
+		std::fs::remove_dir_all(&path)?;
+	}
+	std::fs::create_dir_all(&path)?;
+	Ok(path)
+}
+
+///Configurations survive going to RON and back
+#[test]
+fn round_trip() -> Result<(), anyhow::Error> {
+	let mut strat = ClickNextStrategy::default();
+	strat.max_pages(3).title("h1");
+	let text = to_ron(&strat)?;
+	
+	let configured = from_ron(&ClickNextStrategy::default(), &text)?;
+	
+	assert_eq!(text, to_ron(configured.as_ref())?);
+	assert_ne!(text, to_ron(&ClickNextStrategy::default())?);
+	
+	Ok(())
+}
+
+///Text that isn't RON, or doesn't fit the strategy, doesn't get through
+#[test]
+fn invalid() -> Result<(), anyhow::Error> {
+	let strat = ClickNextStrategy::default();
+	let valid = to_ron(&strat)?;
+	assert!(valid.contains("max_pages: 20"));
+	
+	assert!(from_ron(&strat, "not ron").is_err());
+	assert!(from_ron(&strat, &valid.replace("max_pages: 20", "max_pages: \"many\"")).is_err());
+	assert!(from_ron(&strat, &format!("{valid} trailing")).is_err());
+	
+	Ok(())
+}
+
+///Saved configurations get loaded by the strategy with the same name
+#[test]
+fn folder() -> Result<(), anyhow::Error> {
+	let folder = ConfigFolder::new(temp_folder("folder")?);
+	let mut strat = Named::new("Crawler", ClickNextStrategy::default());
+	assert!(!folder.load(&mut strat)?);
+	
+	let mut configured = ClickNextStrategy::default();
+	configured.max_pages(5);
+	folder.save(&Named::new("Crawler", configured.clone()))?;
+	
+	assert!(folder.load(&mut strat)?);
+	assert_eq!(to_ron(&configured)?, to_ron(&strat)?);
+	assert!(folder.file_path("Crawler").try_exists()?);
+	
+	Ok(())
+}
+
+///Replacing a strategy applies to every clone of the list
+#[test]
+fn replace() -> Result<(), anyhow::Error> {
+	let mut strats = StrategyList::new();
+	strats.add(Named::new("Crawler", ClickNextStrategy::default()));
+	let shared = strats.clone();
+	
+	let mut configured = ClickNextStrategy::default();
+	configured.max_pages(5);
+	let text = to_ron(&configured)?;
+	strats.replace(from_ron(strats.get_by_name("Crawler")?.as_ref(), &text)?)?;
+	
+	let replaced = shared.get_by_name("Crawler")?;
+	assert_eq!("Crawler", replaced.name());
+	assert_eq!(text, to_ron(replaced.as_ref())?);
+	assert!(strats.replace(MockStrat::default().box_clone()).is_err());
+	
+	Ok(())
+}
+
+///Files named `<strategy>.<instance>.ron` are extra instances of that strategy, with their own configuration
+#[test]
+fn instance_names() -> Result<(), anyhow::Error> {
+	let path = temp_folder("instance_names")?;
+	let folder = ConfigFolder::new(&path);
+	let mut configured = ClickNextStrategy::default();
+	configured.max_pages(5);
+	std::fs::write(path.join("click-next.slow.ron"), to_ron(&configured)?)?;
+	for file in ["click-next.fast.ron", "click-nextish.other.ron", "click-next.notes.txt", "rss.ron"] {
+		std::fs::write(path.join(file), "")?;
+	}
+	
+	assert_eq!(vec!["click-next.fast", "click-next.slow"], folder.instance_names("click-next")?);
+	assert!(folder.instance_names("rss")?.is_empty());
+	assert!(ConfigFolder::new(path.join("missing")).instance_names("click-next")?.is_empty());
+	
+	let mut strat = Named::new(String::from("click-next.slow"), ClickNextStrategy::default());
+	assert!(folder.load(&mut strat)?);
+	assert_eq!(to_ron(&configured)?, to_ron(&strat)?);
+	assert!(!path.join("click-next.ron").try_exists()?);
+	
+	Ok(())
+}
+
+///Saving an edited instance, like the strategy editor does, doesn't touch the file of the strategy it's an instance of
+#[test]
+fn save_instance() -> Result<(), anyhow::Error> {
+	let path = temp_folder("save_instance")?;
+	let folder = ConfigFolder::new(&path);
+	let mut strats = StrategyList::new();
+	strats.add(ClickNextStrategy::default());
+	strats.add(Named::new(String::from("click-next.slow"), ClickNextStrategy::default()));
+	folder.save(strats.get_by_name("click-next")?.as_ref())?;
+	let base = std::fs::read_to_string(folder.file_path("click-next"))?;
+	
+	let mut configured = ClickNextStrategy::default();
+	configured.max_pages(5);
+	let edited = from_ron(strats.get_by_name("click-next.slow")?.as_ref(), &to_ron(&configured)?)?;
+	folder.save(edited.as_ref())?;
+	
+	assert_eq!(base, std::fs::read_to_string(folder.file_path("click-next"))?);
+	assert_eq!(to_ron(&configured)?, std::fs::read_to_string(path.join("click-next.slow.ron"))?);
+	
+	Ok(())
+}
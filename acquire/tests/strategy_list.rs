@@ -1,8 +1,8 @@
 mod common;
 use common::{init, single_strat_list, feed_strat_name};
 use acquire::{
-	strategy::Strategy,
-	mock::MockStrat, RunError, strategy_list::RunIdError
+	strategy::{Strategy, Named},
+	mock::MockStrat, RunError, strategy_list::RunIdError, StrategyList
 };
 use entities::prelude::*;
 
@@ -11,9 +11,9 @@ use entities::prelude::*;
 async fn basic() -> Result<(), RunError> {
 	let db = init().await?;
 	let strat = MockStrat::default();
-	let strat_name = strat.name();
+	let strat_name = strat.name().to_owned();
 	let strats = single_strat_list(strat);
-	let feed = feed_strat_name("ok", strat_name, &db).await?;
+	let feed = feed_strat_name("ok", &strat_name, &db).await?;
 	
 	let fetch = strats.run(&db, feed).await?;
 	
@@ -27,13 +27,34 @@ async fn basic() -> Result<(), RunError> {
 async fn id() -> Result<(), RunIdError> {
 	let db = init().await?;
 	let strat = MockStrat::default();
-	let strat_name = strat.name();
+	let strat_name = strat.name().to_owned();
 	let strats = single_strat_list(strat);
-	let feed = feed_strat_name("ok", strat_name, &db).await?;
+	let feed = feed_strat_name("ok", &strat_name, &db).await?;
 	
 	let fetch = strats.run_id(feed.id, &db).await?;
 	
 	assert_eq!(fetch.status, fetch::Status::Success);
 	
 	Ok(())
-}
\ No newline at end of file
+}
+
+///Named instances of the same strategy type can be told apart
+#[tokio::test]
+async fn named() -> Result<(), RunError> {
+	let db = init().await?;
+	let mut strats = StrategyList::new();
+	strats.add(MockStrat::default());
+	strats.add(Named::new("Mock first", MockStrat::default()));
+	strats.add(Named::new("Mock second", MockStrat::default()));
+	
+	assert_eq!("Mock second", strats.get_by_name("Mock second")?.name());
+	assert!(strats.get_by_name("Mock third").is_err());
+	
+	let feed = feed_strat_name("ok", "Mock first", &db).await?;
+	let fetch = strats.run(&db, feed).await?;
+	
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!("Mock first", fetch.strategy);
+	
+	Ok(())
+}
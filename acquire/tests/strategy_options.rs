@@ -0,0 +1,78 @@
+mod common;
+use common::{init, feed, run_strategy, serve_fixtures};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, ModelTrait, PaginatorTrait, Set};
+use acquire::{
+	strategy::{Strategy, Named, parse_options},
+	click_next::ClickNextStrategy,
+	mock::MockStrat,
+};
+use entities::prelude::*;
+
+async fn set_options(feed: feed::Model, options: &str, db: &DatabaseConnection) -> Result<feed::Model, DbErr> {
+	let mut feed = feed::ActiveModel::from(feed);
+	feed.strategy_options = Set(Some(options.to_owned()));
+	feed.update(db).await
+}
+
+///Options of a feed override the configuration of the strategy for that feed only
+#[tokio::test]
+async fn override_config() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let base = serve_fixtures().await?;
+	let strat = ClickNextStrategy::default();
+	let url = format!("{base}/click_next/1.html");
+	let limited = feed(&url, &strat, &db).await?;
+	let limited = set_options(limited, r#"{"max_pages": 2, "title": "h1.title"}"#, &db).await?;
+	let unlimited = feed(&url, &strat, &db).await?;
+	
+	let fetch = run_strategy(&db, &limited, &strat).await?;
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(2, fetch.find_related(entry::Entity).count(&db).await?);
+	let entries = limited.find_related(entry::Entity).all(&db).await?;
+	assert!(entries.iter().any(|entry| entry.name == "Page 1"));
+	
+	let fetch = run_strategy(&db, &unlimited, &strat).await?;
+	assert_eq!(fetch.status, fetch::Status::Success);
+	assert_eq!(4, fetch.find_related(entry::Entity).count(&db).await?);
+	let entries = unlimited.find_related(entry::Entity).all(&db).await?;
+	assert!(entries.iter().any(|entry| entry.name == "Example comic - Page 1"));
+	
+	Ok(())
+}
+
+///Options that aren't described, don't deserialize or aren't JSON become fetch errors
+#[tokio::test]
+async fn invalid() -> Result<(), anyhow::Error> {
+	let db = init().await?;
+	let strat = MockStrat::default();
+	
+	for options in [r#"{"unknown": 1}"#, "not json", "[]"] {
+		let feed = feed("ok", &strat, &db).await?;
+		let feed = set_options(feed, options, &db).await?;
+		
+		let fetch = run_strategy(&db, &feed, &strat).await?;
+		
+		assert_eq!(fetch.status, fetch::Status::FetchError, "options: {options}");
+		assert!(fetch.error.is_some_and(|error| error.contains("strategy options")));
+	}
+	
+	let strat = ClickNextStrategy::default();
+	let options = parse_options(r#"{"max_pages": "many"}"#)?;
+	assert!(strat.with_options(&options).is_err());
+	
+	Ok(())
+}
+
+///Named instances keep their name and options
+#[tokio::test]
+async fn named() -> Result<(), anyhow::Error> {
+	let strat = Named::new("Crawler", ClickNextStrategy::default());
+	let options = parse_options(r#"{"max_pages": 2}"#)?;
+	
+	let configured = strat.with_options(&options)?;
+	
+	assert_eq!("Crawler", configured.name());
+	assert_eq!(strat.describe_options(), configured.describe_options());
+	
+	Ok(())
+}
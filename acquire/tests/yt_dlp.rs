@@ -0,0 +1,42 @@
+use acquire::{
+	yt_dlp::classify_stderr,
+	retry::ErrorKind,
+};
+
+///Network problems and overloaded servers are worth trying again
+#[test]
+fn transient() {
+	let errors = [
+		"ERROR: [youtube] abc: Unable to download webpage: HTTP Error 503: Service Unavailable",
+		"ERROR: [youtube] abc: Unable to download API page: HTTP Error 429: Too Many Requests",
+		"ERROR: [youtube:tab] @someone: Unable to download webpage: <urlopen error [Errno -3] Temporary failure in name resolution> (caused by TransportError('<urlopen error [Errno -3] Temporary failure in name resolution>'))",
+		"ERROR: [youtube] abc: Unable to download webpage: The read operation timed out",
+	];
+	for stderr in errors {
+		assert_eq!(ErrorKind::Transient, classify_stderr(stderr), "{stderr}");
+	}
+}
+
+///Errors about the video or channel itself won't go away
+#[test]
+fn permanent() {
+	let errors = [
+		"ERROR: [youtube] abc: Video unavailable",
+		"ERROR: [youtube:tab] @someone: Unable to download webpage: HTTP Error 404: Not Found",
+		"ERROR: 'not a url' is not a valid URL",
+		"",
+	];
+	for stderr in errors {
+		assert_eq!(ErrorKind::Permanent, classify_stderr(stderr), "{stderr}");
+	}
+}
+
+///Only the final errors count, not the warnings about attempts yt-dlp already retried itself
+#[test]
+fn warnings() {
+	let stderr = "\
+WARNING: [youtube] abc: Unable to download webpage: HTTP Error 500: Internal Server Error. Retrying (1/3)...
+ERROR: [youtube] abc: Private video";
+	
+	assert_eq!(ErrorKind::Permanent, classify_stderr(stderr));
+}
@@ -37,7 +37,10 @@ pub fn App() -> impl IntoView {
 						<crate::fetch::batch::Routes />
 						<crate::entry::Routes />
 						<crate::tag::Routes />
+						<crate::saved_query::Routes />
 						<Route path="/strats" view=crate::strategies::Strategies />
+						<Route path="/report" view=crate::report::Report />
+						<Route path="/stats" view=crate::stats::Stats />
 					</Routes>
 				</div>
 			</ErrorBoundary>
@@ -53,7 +56,11 @@ fn Nav() -> impl IntoView {
 			<A href="entry">Entries</A>
 			<A href="feed">Feeds</A>
 			<A href="tag">Tags</A>
+			<A href="saved_query">Saved queries</A>
 			<A href="strats">Strategies</A>
+			<A href="fetch_batch">Batches</A>
+			<A href="report">Report</A>
+			<A href="stats">Stats</A>
 		</nav>
 	}
 }
@@ -3,12 +3,13 @@ use leptos_router::{A, Route, ActionForm};
 use entities::prelude::*;
 use crate::table;
 use crate::fetch::search::FetchOverview;
+use super::search::{self, EntryOverview};
 use crate::utils;
-use ff_object::ref_signal;
+use ff_object::{ref_signal, page::{Page, PageRequest}};
 #[cfg(feature="ssr")]
 use sea_orm::*;
 #[cfg(feature="ssr")]
-use ff_object::View;
+use ff_object::{View, page::fetch_page};
 
 #[component(transparent)]
 pub fn Routes() -> impl IntoView {
@@ -18,6 +19,7 @@ pub fn Routes() -> impl IntoView {
 			<Route path="about" view = About />
 			<Route path="embedded" view = Embed />
 			<Route path="fetches" view = Fetches />
+			<Route path="duplicates" view = Duplicates />
 		</Route>
 	}
 }
@@ -46,6 +48,9 @@ pub fn Sidebar() -> impl IntoView {
 				<li>
 					<A href="fetches">Fetches</A>
 				</li>
+				<li>
+					<A href="duplicates">Duplicates</A>
+				</li>
 			</ul>
 		</nav>
 	}
@@ -63,15 +68,34 @@ pub async fn get_entry(id: i32) -> Result<entry::Model, ServerFnError> {
 		)
 }
 
+///Also marks the duplicates of the entry, and records when it got viewed
 #[server]
 pub async fn mark_viewed(entry: entry::Ref, viewed: bool) -> Result<entry::Model, ServerFnError> {
 	let db = crate::extension!(DatabaseConnection);
 	
+	let viewed_at = if viewed {
+		let now = time::OffsetDateTime::now_utc();
+		Some(time::PrimitiveDateTime::new(now.date(), now.time()))
+	} else {
+		None
+	};
+	let viewed_at = time_fields::OptionDateTime(viewed_at);
+	
 	let mut entry_model = entry::ActiveModel::new();
 	entry_model.viewed = Set(viewed);
+	entry_model.viewed_at = Set(viewed_at.clone());
 	entry_model.id = Unchanged(entry.id());
 	let entry = entry_model.update(&db).await?;
 	
+	if let Some(canonical_id) = &entry.canonical_id {
+		entry::Entity::update_many()
+			.col_expr(entry::Column::Viewed, sea_query::Expr::value(viewed))
+			.col_expr(entry::Column::ViewedAt, sea_query::Expr::value(viewed_at))
+			.filter(entry::Column::CanonicalId.eq(canonical_id))
+			.exec(&db)
+			.await?;
+	}
+	
 	Ok(entry)
 }
 
@@ -175,24 +199,63 @@ pub fn Embed() -> impl IntoView {
 }
 
 #[server]
-pub async fn get_fetches(entry: entry::Ref) -> Result<Vec<FetchOverview>, ServerFnError> {
+pub async fn get_fetches(entry: entry::Ref, page: PageRequest) -> Result<Page<FetchOverview>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	
+	let query = FetchOverview::from_query(entry.find_related());
+	Ok(fetch_page(query, page, &conn).await?)
+}
+
+#[component]
+pub fn Fetches() -> impl IntoView {
+	let entry = crate::model!(entry);
+	let entry_ref = ref_signal(entry);
+	let request = RwSignal::new(PageRequest::default());
+	let fetches = Resource::new(
+		move || (entry_ref.get(), request.get()),
+		|(entry, page)| get_fetches(entry, page)
+	);
+	
+	view! {
+		<utils::ResourceOk resource=fetches let:page fallback=|| ()>
+			<table::ObjectTable items = page.items paging=Some(table::Paging { request, info: page.info })/>
+		</utils::ResourceOk>
+	}.into()
+}
+
+///Other entries with the same canonical id
+#[server]
+pub async fn get_duplicates(entry: entry::Ref) -> Result<Vec<EntryOverview>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
 	
-	let query = entry.find_related();
-	FetchOverview::from_query(query)
+	let maybe_entry = entry::Entity::find_by_id(entry.id())
+		.one(&conn)
+		.await?;
+	let Some(entry) = maybe_entry else {
+		return Err(ServerFnError::ServerError("No such entry".into()));
+	};
+	let Some(canonical_id) = entry.canonical_id else {
+		return Ok(Vec::new());
+	};
+	
+	EntryOverview::query(|query| {
+		query
+			.filter(entry::Column::CanonicalId.eq(canonical_id))
+			.filter(entry::Column::Id.ne(entry.id))
+	})
 		.all(&conn)
 		.await
-		.map_err(|e| e.into())	
+		.map_err(|e| e.into())
 }
 
 #[component]
-pub fn Fetches() -> impl IntoView {
+pub fn Duplicates() -> impl IntoView {
 	let entry = crate::model!(entry);
 	let entry_ref = ref_signal(entry);
 	
 	view! {
-		<utils::AwaitOk future=move || get_fetches(entry_ref.get()) let:fetches>
-			<table::ObjectTable items = fetches />
+		<utils::AwaitOk future=move || get_duplicates(entry_ref.get()) let:entries>
+			<search::Table entries />
 		</utils::AwaitOk>
 	}.into()
-}
\ No newline at end of file
+}
@@ -3,6 +3,7 @@ use leptos_router::{Route, Outlet};
 
 pub mod details;
 pub mod search;
+pub mod player;
 
 // ROUTING
 
@@ -0,0 +1,285 @@
+/*!
+Walks through unviewed entries one by one, which is the main way to consume them.
+*/
+
+use leptos::*;
+use leptos_router::A;
+use serde::{Deserialize, Serialize};
+use entities::prelude::*;
+use super::details::MarkViewed;
+use crate::utils;
+#[cfg(feature="ssr")]
+use sea_orm::*;
+#[cfg(feature="ssr")]
+use ffilter::filter_list::FilterLists;
+
+
+///Where the player gets its entries from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Source {
+	///Entries of feeds with the tag with this id
+	Tag(i32),
+	///Entries matched by the saved query with this id
+	SavedQuery(i32),
+}
+
+///In which order the player plays entries, by produced date
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Order {
+	#[default]
+	OldestFirst,
+	NewestFirst,
+}
+
+impl Order {
+	pub const ALL: [Self; 2] = [Self::OldestFirst, Self::NewestFirst];
+	
+	fn name(&self) -> &'static str {
+		match self {
+			Self::OldestFirst => "oldest first",
+			Self::NewestFirst => "newest first",
+		}
+	}
+}
+
+
+///How many entries the player loads at a time
+const BATCH_SIZE: u64 = 20;
+///How many entries past the current one should be loaded, so the next one can be preloaded
+const LOAD_AHEAD: usize = 2;
+
+///Some of the unviewed entries of a [Source], in play order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerBatch {
+	pub entries: Vec<entry::Model>,
+	///How many entries there are from the first of [Self::entries] on, including the ones not loaded yet
+	pub remaining: u64,
+}
+
+///The entries that come after `entry` when playing in `order`
+#[cfg(feature="ssr")]
+fn played_after(entry: &entry::Model, order: Order) -> Condition {
+	use entry::Column;
+	let id = entry.id;
+	//Entries without a time sort first when ascending and last when descending, like SQLite sorts NULLs
+	let same_date = match (order, entry.produced_time.0) {
+		(Order::OldestFirst, None) => Condition::any()
+			.add(Column::ProducedTime.is_not_null())
+			.add(Column::Id.gt(id)),
+		(Order::OldestFirst, Some(time)) => Condition::any()
+			.add(Column::ProducedTime.gt(time_fields::Time(time)))
+			.add(Condition::all()
+				.add(Column::ProducedTime.eq(time_fields::Time(time)))
+				.add(Column::Id.gt(id))
+			),
+		(Order::NewestFirst, None) => Condition::all()
+			.add(Column::ProducedTime.is_null())
+			.add(Column::Id.lt(id)),
+		(Order::NewestFirst, Some(time)) => Condition::any()
+			.add(Column::ProducedTime.lt(time_fields::Time(time)))
+			.add(Column::ProducedTime.is_null())
+			.add(Condition::all()
+				.add(Column::ProducedTime.eq(time_fields::Time(time)))
+				.add(Column::Id.lt(id))
+			),
+	};
+	let later_date = match order {
+		Order::OldestFirst => Column::ProducedDate.gt(entry.produced_date.clone()),
+		Order::NewestFirst => Column::ProducedDate.lt(entry.produced_date.clone()),
+	};
+	Condition::any()
+		.add(later_date)
+		.add(Condition::all()
+			.add(Column::ProducedDate.eq(entry.produced_date.clone()))
+			.add(same_date)
+		)
+}
+
+/**
+The next unviewed entries of `source` after the entry with id `after`, or the first ones without it.
+Duplicates only show up once.
+
+Continues from an entry instead of using pages, since the entries before it get marked as viewed while playing.
+*/
+#[server]
+pub async fn player_entries(source: Source, order: Order, after: Option<i32>) -> Result<PlayerBatch, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	
+	let query = entry::Entity::find().filter(entry::Column::Viewed.eq(false));
+	let query = match source {
+		Source::Tag(tag_id) => {
+			let feeds = sea_query::Query::select()
+				.column(feed_tag::Column::FeedId)
+				.from(feed_tag::Entity)
+				.and_where(feed_tag::Column::TagId.eq(tag_id))
+				.to_owned();
+			query.filter(entry::Column::FeedId.in_subquery(feeds))
+		},
+		Source::SavedQuery(saved_query_id) => {
+			let filter_lists = crate::extension!(FilterLists);
+			let saved_query = crate::saved_query::details::get_saved_query(saved_query_id).await?;
+			match crate::saved_query::saved_filter(&saved_query, &filter_lists)? {
+				Some(filter) => filter.filter(query),
+				None => query,
+			}
+		},
+	};
+	let query = super::search::collapse_duplicates(query);
+	
+	let query = match after {
+		None => query,
+		Some(after_id) => {
+			let Some(last) = entry::Entity::find_by_id(after_id).one(&conn).await? else {
+				return Err(ServerFnError::ServerError(format!("No entry with id {after_id}")));
+			};
+			query.filter(played_after(&last, order))
+		},
+	};
+	
+	let query = match order {
+		Order::OldestFirst => query
+			.order_by_asc(entry::Column::ProducedDate)
+			.order_by_asc(entry::Column::ProducedTime)
+			.order_by_asc(entry::Column::Id),
+		Order::NewestFirst => query
+			.order_by_desc(entry::Column::ProducedDate)
+			.order_by_desc(entry::Column::ProducedTime)
+			.order_by_desc(entry::Column::Id),
+	};
+	
+	let remaining = query.clone().count(&conn).await?;
+	let entries = query.limit(BATCH_SIZE).all(&conn).await?;
+	
+	Ok(PlayerBatch {
+		entries,
+		remaining,
+	})
+}
+
+
+#[component]
+pub fn Player(source: Source) -> impl IntoView {
+	let order = RwSignal::new(Order::default());
+	let first = Resource::new(move || order.get(), move |order| player_entries(source, order, None));
+	
+	view! {
+		<div class="player grow">
+			<div class="player_controls">
+				<label for="player_order">order</label>
+				<select id="player_order" on:change=move |event| {
+					let name = event_target_value(&event);
+					let chosen = Order::ALL.into_iter().find(|order| order.name() == name);
+					order.set(chosen.unwrap_or_default());
+				}>
+					{
+						Order::ALL.into_iter().map(|option| view! {
+							<option value=option.name() selected=move || order.get() == option>{option.name()}</option>
+						}).collect_view()
+					}
+				</select>
+			</div>
+			<utils::ResourceOk resource=first let:first fallback=|| ()>
+				<Playlist source order=order.get_untracked() first/>
+			</utils::ResourceOk>
+		</div>
+	}
+}
+
+fn embed_link(entry: &entry::Model) -> String {
+	utils::format_link(entry.embed_url.clone().unwrap_or_else(|| entry.view_url.clone()))
+}
+
+/**
+Shows the entry at the current position, and has buttons to move around.
+Next marks the current entry as viewed, skip doesn't.
+The entry after the current one is already embedded but hidden, so it's loaded by the time we get to it.
+Starts with the `first` entries of `source`, and loads more while advancing.
+*/
+#[component]
+pub fn Playlist(source: Source, order: Order, first: PlayerBatch) -> impl IntoView {
+	let total = RwSignal::new(usize::try_from(first.remaining).unwrap_or(usize::MAX));
+	let entries = RwSignal::new(first.entries);
+	let position = RwSignal::new(0_usize);
+	let mark_viewed = create_server_action::<MarkViewed>();
+	let more = create_action(move |after: &i32| player_entries(source, order, Some(*after)));
+	
+	//An effect instead of a memo, since every loaded batch gets added to the ones before it
+	create_effect(move |_| {
+		if let Some(Ok(next)) = more.value().get() {
+			let loaded = entries.with_untracked(Vec::len);
+			let remaining = usize::try_from(next.remaining).unwrap_or(usize::MAX);
+			total.set(loaded.saturating_add(remaining));
+			entries.update(|entries| entries.extend(next.entries));
+		}
+	});
+	
+	let current = move || entries.with(|entries| entries.get(position.get()).cloned());
+	let at_end = move || position.get() >= total.get();
+	
+	let load_more = move || {
+		let (loaded, last) = entries.with_untracked(|entries| (entries.len(), entries.last().map(|entry| entry.id)));
+		let needed = position.get_untracked() + LOAD_AHEAD >= loaded && loaded < total.get_untracked();
+		if !needed || more.pending().get_untracked() {
+			return;
+		}
+		if let Some(last) = last {
+			more.dispatch(last);
+		}
+	};
+	
+	let advance = move |viewed: bool| {
+		if let Some(entry) = current() {
+			if viewed {
+				mark_viewed.dispatch(MarkViewed { entry: entry.id.into(), viewed: true });
+			}
+			position.update(|position| *position += 1);
+			load_more();
+		}
+	};
+	
+	//The current entry and the one to preload
+	let window = move || entries.with(|entries| {
+		let start = position.get().min(entries.len());
+		let end = (start + LOAD_AHEAD).min(entries.len());
+		entries[start..end].to_vec()
+	});
+	let current_id = move || current().map(|entry| entry.id);
+	
+	view! {
+		<div class="player_controls">
+			<button
+				disabled=move || position.get() == 0
+				on:click=move |_event| position.update(|position| *position = position.saturating_sub(1))
+			>
+				previous
+			</button>
+			<span>{move || format!("{} / {}", (position.get() + 1).min(total.get()), total.get())}</span>
+			<button disabled=at_end on:click=move |_event| advance(false)>skip</button>
+			<button disabled=at_end on:click=move |_event| advance(true)>next</button>
+		</div>
+		{move || match current() {
+			Some(entry) => view! {
+				<A href=format!("/entry/{}/about", entry.id)>{entry.name}</A>
+			}.into_view(),
+			None if at_end() => view! {
+				<p>"No more unviewed entries"</p>
+			}.into_view(),
+			None => view! {
+				<p>"loading..."</p>
+			}.into_view(),
+		}}
+		<utils::FormResult action=mark_viewed let:_entry>
+			""
+		</utils::FormResult>
+		<For
+			each=window
+			key=|entry| entry.id
+			let:entry
+		>
+			<iframe
+				class=move || if current_id() == Some(entry.id) {"grow"} else {"preload"}
+				src=embed_link(&entry)
+			/>
+		</For>
+	}
+}
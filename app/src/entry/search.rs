@@ -2,13 +2,18 @@ use leptos::*;
 // use leptos_router::A;
 use entities::prelude::*;
 use leptos_meta::Title;
-use leptos_router::{Route, Outlet, A};
+use leptos_router::{Route, Outlet, A, create_query_signal};
+use ffilter::shared::Target;
+use crate::query::{Query, QueryString, QueryUI};
 use crate::table;
 use crate::utils;
+use ff_object::page::{Page, PageRequest};
 #[cfg(feature="ssr")]
 use sea_orm::*;
 #[cfg(feature="ssr")]
-use ff_object::View;
+use ff_object::{View, page::fetch_page};
+#[cfg(feature="ssr")]
+use ffilter::filter_list::FilterLists;
 
 
 #[derive(
@@ -40,7 +45,7 @@ impl ff_object::View for EntryOverview {
 	fn columns() -> impl Iterator<Item = impl sea_orm::ColumnTrait> {
 		entry::Column::iter().filter(|column| {
 			use entry::Column::*;
-			!matches!(column, ViewUrl | EmbedUrl )
+			!matches!(column, ViewUrl | EmbedUrl | CanonicalId | ViewedAt )
 		})
 	}
 	
@@ -48,6 +53,7 @@ impl ff_object::View for EntryOverview {
 		query
 			.order_by_desc(entry::Column::ProducedDate)
 			.order_by_desc(entry::Column::ProducedTime)
+			.order_by_desc(entry::Column::Id)
 	}
 }
 
@@ -75,6 +81,7 @@ pub fn Routes() -> impl IntoView {
 			<utils::RouteAlias to="all"/>
 			<Route path="all" view=All />
 			<Route path="unviewed" view=Unviewed />
+			<Route path="search" view=Search />
 		</Route>
 	}
 }
@@ -90,6 +97,9 @@ pub fn Sidebar() -> impl IntoView {
 				<li>
 					<A href="unviewed">Unviewed</A>
 				</li>
+				<li>
+					<A href="search">Search</A>
+				</li>
 			</ul>
 		</nav>
 	}
@@ -97,53 +107,127 @@ pub fn Sidebar() -> impl IntoView {
 
 
 
+///Only keeps the oldest entry of entries with the same canonical id that match the query,
+///so an older duplicate that got filtered out doesn't hide the others
+#[cfg(feature="ssr")]
+pub fn collapse_duplicates(query: Select<entry::Entity>) -> Select<entry::Entity> {
+	use sea_query::{Func, Expr};
+	
+	//The same query with the same filters, grouped instead
+	let oldest = query.clone().into_query()
+		.clear_selects()
+		.clear_order_by()
+		.expr(Func::min(Expr::col((entry::Entity, entry::Column::Id))))
+		.and_where(entry::Column::CanonicalId.is_not_null())
+		.group_by_col((entry::Entity, entry::Column::CanonicalId))
+		.to_owned();
+	query.filter(
+		Condition::any()
+			.add(entry::Column::CanonicalId.is_null())
+			.add(entry::Column::Id.in_subquery(oldest))
+	)
+}
+
+///Duplicates only show up once
 #[server]
-pub async fn unviewed() -> Result<Vec<EntryOverview>, ServerFnError> {
+pub async fn unviewed(page: PageRequest) -> Result<Page<EntryOverview>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
-	let entries = EntryOverview::query(|q| {
-		q.filter(entry::Column::Viewed.eq(false))
-	})
-		.all(&conn)
-		.await?;
-	Ok(entries)
+	let query = EntryOverview::query(|q| {
+		collapse_duplicates(q.filter(entry::Column::Viewed.eq(false)))
+	});
+	Ok(fetch_page(query, page, &conn).await?)
 }
 
 #[component]
 pub fn Unviewed() -> impl IntoView {
+	let request = RwSignal::new(PageRequest::default());
+	let entries = Resource::new(move || request.get(), unviewed);
+	
 	view! {
-		<utils::AwaitOk future=unviewed let:entries>
-			<Table entries/>
-		</utils::AwaitOk>
+		<utils::ResourceOk resource=entries let:page fallback=|| ()>
+			<Table entries=page.items paging=Some(table::Paging { request, info: page.info })/>
+		</utils::ResourceOk>
 	}
 }
 
 
 #[server]
-pub async fn all_entries() -> Result<Vec<EntryOverview>, ServerFnError> {
+pub async fn all_entries(page: PageRequest) -> Result<Page<EntryOverview>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
-	let entries = EntryOverview::query(|q| q)
-		.all(&conn)
-		.await?;
-	Ok(entries)
+	let query = EntryOverview::query(|q| q);
+	Ok(fetch_page(query, page, &conn).await?)
 }
 
 #[component]
 pub fn All() -> impl IntoView {
+	let request = RwSignal::new(PageRequest::default());
+	let entries = Resource::new(move || request.get(), all_entries);
+	
+	view! {
+		<utils::ResourceOk resource=entries let:page fallback=|| ()>
+			<Table entries=page.items paging=Some(table::Paging { request, info: page.info })/>
+		</utils::ResourceOk>
+	}
+}
+
+
+#[server]
+// #[server(default)] because it otherwise errors when it only contains a None
+pub async fn search(#[server(default)] search_query: Query, page: PageRequest) -> Result<Page<EntryOverview>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let filter_lists = crate::extension!(FilterLists);
+	
+	let query = search_query.apply(entry::Entity::find(), &filter_lists)?;
+	
+	Ok(fetch_page(EntryOverview::from_query(query), page, &conn).await?)
+}
+
+#[component]
+pub fn Search() -> impl IntoView {
+	let (query_get, query_set) = create_query_signal::<QueryString>("query");
+	let request = RwSignal::new(PageRequest::default());
+	
+	let on_search = move |query: Query| {
+		request.set(PageRequest::default());
+		query_set.set(Some(query.into()));
+	};
+	
+	let initial = query_get.get_untracked().map(Query::from);
+	//Shared between the query and the table headers, so clicking a header sorts without searching again
+	let sort = RwSignal::new(initial.as_ref().map(|query| query.get_sort().to_vec()).unwrap_or_default());
+	
+	let search_results = Resource::new(
+		move || {
+			let mut query = query_get.get().unwrap_or_default().query;
+			query.set_sort(sort.get());
+			(query, request.get())
+		},
+		|(query, page)| search(query, page)
+	);
+	
 	view! {
-		<utils::AwaitOk future=all_entries let:entries>
-			<Table entries/>
-		</utils::AwaitOk>
+		<QueryUI on_search pending=search_results.loading() default=initial target=Target::Entry sort=Some(sort)/>
+		
+		<utils::ResourceOk resource=search_results let:page fallback=|| ()>
+			<Table entries=page.items sort=Some(sort) paging=Some(table::Paging { request, info: page.info })/>
+		</utils::ResourceOk>
 	}
 }
 
 
 #[component]
-pub fn Table(#[prop(into)] entries: MaybeSignal<Vec<EntryOverview>>) -> impl IntoView {
+pub fn Table(
+	#[prop(into)] entries: MaybeSignal<Vec<EntryOverview>>,
+	///See [table::ObjectTable]
+	#[prop(default=None)] sort: Option<RwSignal<Vec<crate::query::SortKey>>>,
+	///See [table::ObjectTable]
+	#[prop(default=None)] paging: Option<table::Paging>,
+) -> impl IntoView {
 	view! {
 		<table::ObjectTable items = entries adds = vec![
 			("view", |entry| view!{
 				<A href=format!("/entry/{}/embedded", entry.id)> view </A>
 			}),
-		]/>
+		] sort paging/>
 	}
 }
\ No newline at end of file
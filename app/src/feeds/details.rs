@@ -3,11 +3,11 @@ use leptos_router::{Route, ActionForm, A};
 use entities::prelude::*;
 use crate::table::*;
 use crate::utils;
-use ff_object::{Object, ref_signal};
+use ff_object::{Object, ref_signal, page::{Page, PageRequest}};
 #[cfg(feature="ssr")]
 use sea_orm::*;
 #[cfg(feature="ssr")]
-use ff_object::View;
+use ff_object::{View, page::fetch_page};
 
 use crate::fetch::search::FetchOverview;
 use crate::entry::search::EntryOverview;
@@ -105,6 +105,41 @@ pub async fn get_feed(id: i32) -> Result<feed::Model, ServerFnError> {
 		)
 }
 
+#[server]
+pub async fn set_refresh_interval(feed: feed::Ref, minutes: String) -> Result<feed::Model, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	
+	let minutes = minutes.trim();
+	let refresh_interval = if minutes.is_empty() {
+		None
+	} else {
+		Some(minutes.parse::<i32>()?)
+	};
+	
+	let mut model = feed::ActiveModel::new();
+	model.id = Set(feed.id());
+	model.refresh_interval = Set(refresh_interval);
+	
+	Ok(model.update(&conn).await?)
+}
+
+#[component]
+pub fn RefreshIntervalForm(#[prop(into)] feed: Signal<feed::Model>) -> impl IntoView {
+	let set_interval = create_server_action::<SetRefreshInterval>();
+	let current = move || feed.get().refresh_interval.map(|minutes| minutes.to_string()).unwrap_or_default();
+	view! {
+		<ActionForm action=set_interval>
+			<input type="hidden" name="feed" value=move || feed.get().id/>
+			<label for="refresh_interval_input"> "Refresh interval (minutes, empty for default, 0 to disable)" </label>
+			<input type="number" name="minutes" id="refresh_interval_input" prop:value=current/>
+			<utils::FormSubmit button="set" action=set_interval/>
+		</ActionForm>
+		<utils::FormResult action=set_interval let:feed>
+			"Set to: " {feed.refresh_interval.map(|minutes| minutes.to_string()).unwrap_or("default".into())}
+		</utils::FormResult>
+	}
+}
+
 #[component]
 pub fn FeedInfo() -> impl IntoView {
 	let feed = crate::model!(feed);
@@ -117,50 +152,58 @@ pub fn FeedInfo() -> impl IntoView {
 		<ObjectFieldValueList<FeedModel> object=feed />
 		<a href=move || feed.get().url target="_blank"> {url} </a>
 		<FetchFeedButton feed=feed_ref />
+		<RefreshIntervalForm feed />
+		<super::strategy_options::StrategyOptionsForm feed />
 	}.into()
 }
 
 
 #[server]
-pub async fn get_fetches(feed: feed::Ref) -> Result<Vec<FetchOverview>, ServerFnError> {
+pub async fn get_fetches(feed: feed::Ref, page: PageRequest) -> Result<Page<FetchOverview>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
-	FetchOverview::query(|q| feed.filter_related(q))
-		.all(&conn)
-		.await
-		.map_err(|e| e.into())
+	let query = FetchOverview::query(|q| feed.filter_related(q));
+	Ok(fetch_page(query, page, &conn).await?)
 }
 
 #[component]
 pub fn Fetches() -> impl IntoView {
 	let feed_ref = ref_signal(crate::model!(feed));
+	let request = RwSignal::new(PageRequest::default());
+	let fetches = Resource::new(
+		move || (feed_ref.get(), request.get()),
+		|(feed, page)| get_fetches(feed, page)
+	);
 	
 	view! {
-		<utils::AwaitOk future=move || get_fetches(feed_ref.get()) let:fetches>
-			<ObjectTable items = fetches />
-		</utils::AwaitOk>
+		<utils::ResourceOk resource=fetches let:page fallback=|| ()>
+			<ObjectTable items = page.items paging=Some(Paging { request, info: page.info })/>
+		</utils::ResourceOk>
 	}.into()
 }
 
 
 #[server]
-pub async fn get_entries(feed: feed::Ref) -> Result<Vec<EntryOverview>, ServerFnError> {
+pub async fn get_entries(feed: feed::Ref, page: PageRequest) -> Result<Page<EntryOverview>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
-	EntryOverview::query( |query|
+	let query = EntryOverview::query( |query|
 		feed.filter_related(query)
-	)
-		.all(&conn)
-		.await
-		.map_err(|e| e.into())
+	);
+	Ok(fetch_page(query, page, &conn).await?)
 }
 
 #[component]
 pub fn Entries() -> impl IntoView {
 	let feed = crate::model!(feed);
+	let request = RwSignal::new(PageRequest::default());
+	let entries = Resource::new(
+		move || (feed.get().get_ref(), request.get()),
+		|(feed, page)| get_entries(feed, page)
+	);
 	
 	view! {
-		<utils::AwaitOk future=move || get_entries(feed.get().get_ref()) let:entries>
-			<crate::entry::search::Table entries />
-		</utils::AwaitOk>
+		<utils::ResourceOk resource=entries let:page fallback=|| ()>
+			<crate::entry::search::Table entries=page.items paging=Some(Paging { request, info: page.info })/>
+		</utils::ResourceOk>
 	}.into()
 }
 
@@ -6,6 +6,8 @@ use crate::utils;
 pub mod new;
 pub mod details;
 pub mod search;
+pub mod opml;
+pub mod strategy_options;
 
 
 #[component(transparent)]
@@ -24,6 +26,7 @@ pub fn FeedRoutes() -> impl IntoView {
 				<Route path="/search2" view=search::Search2 />
 				<Route path="/all" view=search::All />
 				<Route path="/new" view=new::FeedCreator />
+				<Route path="/opml" view=opml::Opml />
 			</Route>
 			<details::Routes />
 		</Route>
@@ -47,6 +50,9 @@ fn Sidebar() -> impl IntoView {
 				<li>
 					<A href="new">New</A>
 				</li>
+				<li>
+					<A href="opml">OPML</A>
+				</li>
 			</ul>
 		</nav>
 	}
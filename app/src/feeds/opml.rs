@@ -0,0 +1,60 @@
+use leptos::*;
+use leptos_router::{ActionForm, create_query_signal};
+use crate::utils;
+
+
+///Returns a summary of what got imported
+#[server]
+pub async fn import_opml(opml: String) -> Result<String, ServerFnError> {
+	let conn = crate::extension!(sea_orm::DatabaseConnection);
+	let strats = crate::extension!(acquire::StrategyList);
+	
+	let feeds = acquire::opml::parse(&opml)?;
+	let summary = acquire::opml::import(&conn, &strats, feeds).await?;
+	
+	Ok(summary.to_string())
+}
+
+#[component]
+pub fn Opml() -> impl IntoView {
+	let import = create_server_action::<ImportOpml>();
+	//Set by the server when redirecting back after an upload
+	let (created, _) = create_query_signal::<usize>("created");
+	let (existing, _) = create_query_signal::<usize>("existing");
+	let (tagged, _) = create_query_signal::<usize>("tagged");
+	let (skipped, _) = create_query_signal::<usize>("skipped");
+	let uploaded = move || {
+		let created = created.get()?;
+		let mut summary = format!(
+			"Created {created} feeds, {} already existed, added {} tags",
+			existing.get().unwrap_or_default(),
+			tagged.get().unwrap_or_default(),
+		);
+		if let Some(skipped @ 1..) = skipped.get() {
+			summary += &format!(", skipped {skipped} without a strategy");
+		}
+		Some(summary)
+	};
+	view! {
+		<h3> "Import" </h3>
+		//A plain form, since it has to upload a file. Gets handled by the server outside of the app.
+		<form method="post" action="/opml/import" enctype="multipart/form-data">
+			<input type="file" name="opml" accept=".opml,.xml,text/x-opml,text/xml" required/>
+			<input type="submit" value="upload"/>
+		</form>
+		{move || uploaded().map(|summary| view! {
+			<p> {summary} </p>
+		})}
+		<ActionForm action=import>
+			<textarea name="opml" rows=10 cols=80 placeholder="or paste OPML here"/>
+			<utils::FormSubmit button="import" action=import/>
+		</ActionForm>
+		<utils::FormResult action=import let:summary>
+			{summary}
+		</utils::FormResult>
+		
+		<h3> "Export" </h3>
+		//Not an <A> since it's a download outside of the app
+		<a href="/opml/export" download rel="external"> "download all feeds as OPML" </a>
+	}
+}
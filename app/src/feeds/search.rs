@@ -4,8 +4,11 @@ use entities::prelude::*;
 use serde::{Serialize, Deserialize};
 use crate::{query::{Query, QueryString}, table::*};
 use crate::utils;
+use ff_object::page::{Page, PageRequest};
 #[cfg(feature="ssr")]
-use ffilter::filter_list::FilterList;
+use ff_object::page::fetch_page;
+#[cfg(feature="ssr")]
+use ffilter::filter_list::FilterLists;
 #[cfg(feature="ssr")]
 use sea_orm::*;
 
@@ -15,7 +18,7 @@ pub struct SearchParameters {
 }
 
 #[server]
-pub async fn search(params: Option<SearchParameters>) -> Result<Vec<feed::Model>, ServerFnError> {
+pub async fn search(params: Option<SearchParameters>, page: PageRequest) -> Result<Page<feed::Model>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
 	let mut query = feed::Entity::find();
 	//Params is an Option because it gets serialised into nothingness when empty
@@ -32,14 +35,15 @@ pub async fn search(params: Option<SearchParameters>) -> Result<Vec<feed::Model>
 			// query = tag_type.filter_query(tag, query);
 		}
 	}
-	let feeds = query.all(&conn).await?;
-	Ok(feeds)
+	let query = query.order_by_asc(feed::Column::Id);
+	Ok(fetch_page(query, page, &conn).await?)
 }
 
 #[component]
 pub fn Search() -> impl IntoView {
 	
 	let params_res_memo = use_query::<SearchParameters>();
+	let request = RwSignal::new(PageRequest::default());
 	
 	let enable_tag = RwSignal::new(false);
 	
@@ -68,10 +72,14 @@ pub fn Search() -> impl IntoView {
 		</form>
 		
 		{ move || {
-			params_res_memo.get().map(|params| view!{
-				<utils::AwaitOk future=move || search(Some(params.clone())) let:feeds>
-					<ObjectTable items = feeds />
-				</utils::AwaitOk>
+			params_res_memo.get().map(|params| {
+				request.set_untracked(PageRequest::default());
+				let feeds = Resource::new(move || request.get(), move |page| search(Some(params.clone()), page));
+				view!{
+					<utils::ResourceOk resource=feeds let:page fallback=|| ()>
+						<ObjectTable items = page.items paging=Some(Paging { request, info: page.info })/>
+					</utils::ResourceOk>
+				}
 			})
 		} }	
 	}
@@ -79,21 +87,14 @@ pub fn Search() -> impl IntoView {
 
 #[server]
 // #[server(default)] because it otherwise errors when it only contains a None
-pub async fn search2(#[server(default)] search_query: Query) -> Result<Vec<feed::Model>, ServerFnError> {
+pub async fn search2(#[server(default)] search_query: Query, page: PageRequest) -> Result<Page<feed::Model>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
-	let filter_list = crate::extension!(FilterList);
-	
-	let maybe_filter = search_query.into_filter();
-	
-	let mut query = feed::Entity::find();
+	let filter_lists = crate::extension!(FilterLists);
 	
-	if let Some(filter) =  maybe_filter {
-		let filter = filter.into_filter(filter_list)?;
-		query = filter.filter(query);
-	}
+	let query = search_query.apply(feed::Entity::find(), &filter_lists)?
+		.order_by_asc(feed::Column::Id);
 	
-	let feeds = query.all(&conn).await?;
-	Ok(feeds)
+	Ok(fetch_page(query, page, &conn).await?)
 }
 
 #[component]
@@ -101,40 +102,59 @@ pub fn Search2() -> impl IntoView {
 	use crate::query::QueryUI;
 	
 	let (query_get, query_set) = create_query_signal::<QueryString>("query");
+	let request = RwSignal::new(PageRequest::default());
 	
 	let on_search = move |query: Query| {
+		request.set(PageRequest::default());
 		query_set.set(Some(query.into()));
 	};
 	
+	let initial = query_get.get_untracked().map(Query::from);
+	//Shared between the query and the table headers, so clicking a header sorts without searching again
+	let sort = RwSignal::new(initial.as_ref().map(|query| query.get_sort().to_vec()).unwrap_or_default());
+	
 	let search_results = Resource::new(
-		move || query_get.get().unwrap_or_default().query,
-		search2
+		move || {
+			let mut query = query_get.get().unwrap_or_default().query;
+			query.set_sort(sort.get());
+			(query, request.get())
+		},
+		|(query, page)| search2(query, page)
 	);
 	
-	let initial = query_get.get_untracked().map(|qs| qs.into());
-	
 	view! {
-		<QueryUI on_search pending=search_results.loading() default=initial/>
+		<QueryUI on_search pending=search_results.loading() default=initial sort=Some(sort)/>
 		
-		<utils::ResourceOk resource=search_results let:feeds fallback=|| ()>
-			<ObjectTable items = feeds />
+		<utils::ResourceOk resource=search_results let:page fallback=|| ()>
+			<ObjectTable items = page.items sort=Some(sort) paging=Some(Paging { request, info: page.info })/>
 		</utils::ResourceOk>
 	}
 }
 
 
+///Every feed at once, for picking one of them
 #[server]
-pub async fn all_feeds() -> Result<Vec<feed::Model>, ServerFnError> {
+pub async fn feed_choices() -> Result<Vec<feed::Model>, ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
-	let feeds = feed::Entity::find().all(&conn).await?;
+	let feeds = feed::Entity::find().order_by_asc(feed::Column::Name).all(&conn).await?;
 	Ok(feeds)
 }
 
+#[server]
+pub async fn all_feeds(page: PageRequest) -> Result<Page<feed::Model>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let query = feed::Entity::find().order_by_asc(feed::Column::Id);
+	Ok(fetch_page(query, page, &conn).await?)
+}
+
 #[component]
 pub fn All() -> impl IntoView {
+	let request = RwSignal::new(PageRequest::default());
+	let feeds = Resource::new(move || request.get(), all_feeds);
+	
 	view! {
-		<utils::AwaitOk future=all_feeds let:feeds>
-			<ObjectTable items = feeds />
-		</utils::AwaitOk>
+		<utils::ResourceOk resource=feeds let:page fallback=|| ()>
+			<ObjectTable items = page.items paging=Some(Paging { request, info: page.info })/>
+		</utils::ResourceOk>
 	}
 }
\ No newline at end of file
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use entities::prelude::*;
+use ff_object::{describe::Described, Object};
+use crate::utils;
+
+
+///What kind of value an option takes, decides how it gets edited
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptionType {
+	Bool,
+	Integer,
+	String,
+	Choice(Vec<String>),
+	Json,
+}
+
+#[cfg(feature="ssr")]
+impl From<acquire::strategy::OptionType> for OptionType {
+	fn from(kind: acquire::strategy::OptionType) -> Self {
+		use acquire::strategy::OptionType as Server;
+		match kind {
+			Server::Bool => Self::Bool,
+			Server::Integer => Self::Integer,
+			Server::String => Self::String,
+			Server::Choice(choices) => Self::Choice(choices),
+			Server::Json => Self::Json,
+		}
+	}
+}
+
+pub type OptionDesc = Described<OptionType>;
+
+///The options the strategy of a feed takes, and the ones the feed sets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedOptions {
+	pub descriptions: Vec<OptionDesc>,
+	///Set options with their value as JSON
+	pub values: BTreeMap<String, String>,
+}
+
+#[cfg(feature="ssr")]
+fn options_error(err: impl std::fmt::Display) -> ServerFnError {
+	ServerFnError::ServerError(format!("Invalid strategy options: {err:#}"))
+}
+
+#[server]
+pub async fn get_strategy_options(feed: feed::Ref) -> Result<FeedOptions, ServerFnError> {
+	use acquire::strategy::parse_options;
+	let conn = crate::extension!(sea_orm::DatabaseConnection);
+	let strats = crate::extension!(acquire::StrategyList);
+	
+	let Some(feed) = feed.find().one(&conn).await? else {
+		return Err(ServerFnError::ServerError(format!("No feed with id {}", feed.id())));
+	};
+	let strat = strats.get_by_name(&feed.strategy)?;
+	
+	let descriptions = strat.describe_options().into_iter()
+		.map(|desc| desc.map(OptionType::from))
+		.collect();
+	let values = match feed.strategy_options.as_deref().filter(|text| !text.trim().is_empty()) {
+		Some(text) => parse_options(text).map_err(options_error)?.into_iter()
+			.map(|(name, value)| (name, value.to_string()))
+			.collect(),
+		None => BTreeMap::new(),
+	};
+	
+	Ok(FeedOptions {
+		descriptions,
+		values,
+	})
+}
+
+///`options` has the values as JSON, they get checked against the strategy before saving
+#[server]
+// #[server(default)] because it otherwise errors when it's empty
+pub async fn set_strategy_options(feed: feed::Ref, #[server(default)] options: BTreeMap<String, String>) -> Result<feed::Model, ServerFnError> {
+	use sea_orm::*;
+	use acquire::strategy::Options;
+	let conn = crate::extension!(DatabaseConnection);
+	let strats = crate::extension!(acquire::StrategyList);
+	
+	let Some(model) = feed.find().one(&conn).await? else {
+		return Err(ServerFnError::ServerError(format!("No feed with id {}", feed.id())));
+	};
+	let strat = strats.get_by_name(&model.strategy)?;
+	
+	let options = options.into_iter()
+		.map(|(name, value)| match serde_json::from_str(&value) {
+			Ok(parsed) => Ok((name, parsed)),
+			Err(err) => Err(options_error(format!("value of \"{name}\" isn't JSON: {err}"))),
+		})
+		.collect::<Result<Options, _>>()?;
+	strat.with_options(&options).map_err(options_error)?;
+	
+	let mut model = model.into_active_model();
+	model.strategy_options = Set(if options.is_empty() {
+		None
+	} else {
+		Some(serde_json::Value::Object(options).to_string())
+	});
+	Ok(model.update(&conn).await?)
+}
+
+
+///What an unset option starts out as when it gets set
+fn default_value(kind: &OptionType) -> String {
+	match kind {
+		OptionType::Bool => "false".into(),
+		OptionType::Integer => "0".into(),
+		OptionType::String => "\"\"".into(),
+		OptionType::Choice(choices) => serde_json::Value::from(choices.first().cloned().unwrap_or_default()).to_string(),
+		OptionType::Json => "null".into(),
+	}
+}
+
+///Input for a single option, `value` is its JSON
+#[component]
+fn OptionInput(kind: OptionType, value: RwSignal<String>, #[prop(into)] id: String, #[prop(into)] disabled: Signal<bool>) -> impl IntoView {
+	let text = move || {
+		let json = value.get();
+		serde_json::from_str::<String>(&json).unwrap_or(json)
+	};
+	let set_text = move |event| value.set(serde_json::Value::from(event_target_value(&event)).to_string());
+	
+	match kind {
+		OptionType::Bool => view! {
+			<input type="checkbox" id=id disabled=disabled prop:checked=move || value.get() == "true" on:change=move |event| {
+				value.set(event_target_checked(&event).to_string());
+			}/>
+		}.into_view(),
+		OptionType::Integer => view! {
+			<input type="number" id=id disabled=disabled prop:value=move || value.get() on:change=move |event| {
+				value.set(event_target_value(&event));
+			}/>
+		}.into_view(),
+		OptionType::String => view! {
+			<input type="text" id=id size=50 disabled=disabled prop:value=text on:change=set_text/>
+		}.into_view(),
+		OptionType::Choice(choices) => view! {
+			<select id=id disabled=disabled on:change=set_text>
+				{
+					choices.into_iter()
+						.map(|choice| {
+							let selected = choice.clone();
+							view! { <option value=choice.clone() selected=move || text() == selected>{choice}</option> }
+						})
+						.collect_view()
+				}
+			</select>
+		}.into_view(),
+		OptionType::Json => view! {
+			<input type="text" id=id size=50 disabled=disabled prop:value=move || value.get() on:change=move |event| {
+				value.set(event_target_value(&event));
+			}/>
+		}.into_view(),
+	}
+}
+
+///Editor for the per-feed options of the feed's strategy, generated from their descriptions
+#[component]
+pub fn StrategyOptionsForm(feed: RwSignal<feed::Model>) -> impl IntoView {
+	let options = Resource::new(
+		move || feed.get(),
+		|feed| get_strategy_options(feed.get_ref())
+	);
+	let set_options = create_server_action::<SetStrategyOptions>();
+	
+	view! {
+		<h2>"Strategy options"</h2>
+		<utils::ResourceOk resource=options let:options fallback=|| ()>
+			<OptionsEditor feed options set_options/>
+		</utils::ResourceOk>
+		<utils::FormResult action=set_options let:updated>
+			{
+				feed.set(updated);
+				"Saved"
+			}
+		</utils::FormResult>
+	}
+}
+
+#[component]
+fn OptionsEditor(
+	feed: RwSignal<feed::Model>,
+	options: FeedOptions,
+	set_options: Action<SetStrategyOptions, Result<feed::Model, ServerFnError>>,
+) -> impl IntoView {
+	if options.descriptions.is_empty() {
+		return view! { <p>"This strategy has no per-feed options"</p> }.into_view();
+	}
+	
+	//Whether it's set, and its value as JSON
+	let rows = options.descriptions.into_iter()
+		.map(|desc| {
+			let current = options.values.get(&desc.name).cloned();
+			let set = RwSignal::new(current.is_some());
+			let value = RwSignal::new(current.unwrap_or_else(|| default_value(&desc.data)));
+			(desc, set, value)
+		})
+		.collect::<Vec<_>>();
+	let rows = store_value(rows);
+	
+	let on_submit = move |event: ev::SubmitEvent| {
+		event.prevent_default();
+		let options = rows.with_value(|rows| {
+			rows.iter()
+				.filter(|(_, set, _)| set.get_untracked())
+				.map(|(desc, _, value)| (desc.name.clone(), value.get_untracked()))
+				.collect()
+		});
+		set_options.dispatch(SetStrategyOptions { feed: feed.get_untracked().get_ref(), options });
+	};
+	
+	view! {
+		<form on:submit=on_submit>
+			<ul class="object_fieldvalue_list">
+				{
+					rows.get_value().into_iter()
+						.map(|(desc, set, value)| {
+							let id = format!("strategy_option_{}", desc.name);
+							view! {
+								<li class="object_fieldvalue">
+									<label class="object_field" for=id.clone()>{desc.name.clone()}</label>
+									<input type="checkbox" title="override" prop:checked=move || set.get() on:change=move |event| {
+										set.set(event_target_checked(&event));
+									}/>
+									<OptionInput kind=desc.data.clone() value id disabled=Signal::derive(move || !set.get())/>
+									<span>{desc.description.clone()}</span>
+								</li>
+							}
+						})
+						.collect_view()
+				}
+			</ul>
+			<utils::FormSubmit button="save" action=set_options/>
+		</form>
+	}.into_view()
+}
@@ -1,20 +1,25 @@
-use std::time::Duration;
-
 use leptos::*;
 use leptos_router::{Route, ActionForm, Outlet, Redirect, A};
-#[cfg(feature="ssr")]
+use leptos_meta::Title;
 use entities::prelude::*;
 use serde::{Serialize, Deserialize};
-use crate::utils;
+use crate::{utils, table::{ObjectTable, Paging}, fetch::search::FetchOverview};
+use ff_object::page::{Page, PageRequest};
 #[cfg(feature="ssr")]
 use sea_orm::*;
 #[cfg(feature="ssr")]
+use ff_object::{View, page::fetch_page};
+#[cfg(feature="ssr")]
 use acquire::batch::Batch;
 
 #[component(transparent)]
 pub fn Routes() -> impl IntoView {
 	view! {
-		<Route path="fetch_batch" view=Outlet>
+		<Route path="fetch_batch" view=|| view! {
+			<Title text="Batches" />
+			<Outlet/>
+		}>
+			<Route path="" view=History />
 			<Route path="/:id" view=UpdatingBatchPage />
 		</Route>
 	}
@@ -25,19 +30,31 @@ pub fn Routes() -> impl IntoView {
 pub struct BatchStatus {
 	total: usize,
 	done: usize,
-	id: usize,
+	cancelled: bool,
+	id: i32,
 }
 
 impl BatchStatus {
 	#[cfg(feature="ssr")]
-	pub fn from_id_batch(id: usize, batch: &Batch) -> Self {
+	pub fn from_id_batch(id: i32, batch: &Batch) -> Self {
 		Self {
 			total: batch.total,
 			done: batch.finished.len(),
+			cancelled: batch.cancelled,
 			id,
 		}
 	}
 	
+	///For batches from before the server started, which are always finished one way or another
+	pub fn from_model(batch: &batch::Model) -> Self {
+		Self {
+			total: batch.total as usize,
+			done: batch.total as usize,
+			cancelled: batch.status == batch::Status::Cancelled,
+			id: batch.id,
+		}
+	}
+	
 	pub fn is_finished(&self) -> bool {
 		self.total == self.done
 	}
@@ -45,54 +62,150 @@ impl BatchStatus {
 
 
 #[server]
-pub async fn get_batch_status(batch_ref: usize) -> Result<BatchStatus, ServerFnError> {
+pub async fn get_batch_status(batch_ref: i32) -> Result<BatchStatus, ServerFnError> {
 	let tracker = crate::extension!(acquire::batch_tracker::BatchTracker);
 	
-	let batch_sync = tracker.get_status(batch_ref).await?;
-	let status = { // Scope to reduce lock time
-		let batch_lock = batch_sync.read().await;
-		BatchStatus::from_id_batch(batch_ref, &batch_lock)
+	if let Ok(batch_sync) = tracker.get_status(batch_ref).await {
+		let status = { // Scope to reduce lock time
+			let batch_lock = batch_sync.read().await;
+			BatchStatus::from_id_batch(batch_ref, &batch_lock)
+		};
+		return Ok(status);
+	}
+	
+	let conn = crate::extension!(DatabaseConnection);
+	let maybe_batch = batch::Entity::find_by_id(batch_ref)
+		.one(&conn)
+		.await?;
+	let Some(batch) = maybe_batch else {
+		return Err(ServerFnError::ServerError("No such batch".into()));
 	};
 	
-	Ok(status)
+	Ok(BatchStatus::from_model(&batch))
+}
+
+#[server]
+pub async fn cancel_batch(batch_ref: i32) -> Result<(), ServerFnError> {
+	let tracker = crate::extension!(acquire::batch_tracker::BatchTracker);
+	
+	tracker.cancel(batch_ref).await?;
+	
+	Ok(())
 }
 
 
-const REFRESH_INTERVAL: Duration = Duration::from_millis(1000);
+///A [BatchStatusUpdate](acquire::batch::BatchStatusUpdate) as streamed by the server
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchUpdate {
+	pub total: usize,
+	pub done: usize,
+	pub cancelled: bool,
+	pub latest: Option<FetchSummary>,
+}
+
+///What happened with a single feed in a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct FetchSummary {
+	pub feed_id: i32,
+	pub fetch_id: Option<i32>,
+	pub status: Option<fetch::Status>,
+}
+
+///A [BatchSnapshot](acquire::batch::BatchSnapshot) as streamed by the server, replaces the updates before it
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchSnapshot {
+	#[serde(flatten)]
+	pub status: BatchUpdate,
+	pub fetched: Vec<FetchSummary>,
+}
+
+///Have to match the event names used by the server
+const BATCH_STATUS_EVENT: &str = "status";
+const BATCH_SNAPSHOT_EVENT: &str = "snapshot";
+
+///Keeps `status` and `fetched` up to date by listening to the Server-Sent Events of batch `id`
+#[cfg(feature="hydrate")]
+fn listen_to_batch(id: i32, status: RwSignal<Option<BatchStatus>>, fetched: RwSignal<Vec<FetchSummary>>) {
+	use wasm_bindgen::JsCast;
+	
+	let source = match web_sys::EventSource::new(&format!("/events/batch/{id}")) {
+		Ok(source) => source,
+		Err(err) => {
+			tracing::error!(?err, "Error connecting to batch events");
+			return;
+		}
+	};
+	
+	let set_status = move |update: &BatchUpdate| status.set(Some(BatchStatus {
+		total: update.total,
+		done: update.done,
+		cancelled: update.cancelled,
+		id,
+	}));
+	
+	let status_source = source.clone();
+	let status_handler = event_handler(move |update: BatchUpdate| {
+		if update.total == update.done {
+			//The browser would otherwise reconnect once the server closes the stream
+			status_source.close();
+		}
+		if let Some(latest) = update.latest {
+			fetched.update(|fetched| fetched.push(latest));
+		}
+		set_status(&update);
+	});
+	let snapshot_source = source.clone();
+	let snapshot_handler = event_handler(move |snapshot: BatchSnapshot| {
+		if snapshot.status.total == snapshot.status.done {
+			snapshot_source.close();
+		}
+		fetched.set(snapshot.fetched);
+		set_status(&snapshot.status);
+	});
+	for (event, handler) in [(BATCH_STATUS_EVENT, &status_handler), (BATCH_SNAPSHOT_EVENT, &snapshot_handler)] {
+		if let Err(err) = source.add_event_listener_with_callback(event, handler.as_ref().unchecked_ref()) {
+			tracing::error!(?err, event, "Error listening to batch events");
+		}
+	}
+	
+	on_cleanup(move || {
+		source.close();
+		drop(status_handler);
+		drop(snapshot_handler);
+	});
+}
+
+///Calls `handle` with the JSON data of the events it gets
+#[cfg(feature="hydrate")]
+fn event_handler<Data: serde::de::DeserializeOwned>(handle: impl Fn(Data) + 'static) -> wasm_bindgen::closure::Closure<dyn Fn(web_sys::MessageEvent)> {
+	wasm_bindgen::closure::Closure::new(move |event: web_sys::MessageEvent| {
+		let Some(data) = event.data().as_string() else {
+			return;
+		};
+		match serde_json::from_str::<Data>(&data) {
+			Ok(parsed) => handle(parsed),
+			Err(err) => tracing::error!(?err, data, "Error parsing batch event"),
+		}
+	})
+}
 
 #[component(transparent)]
 pub fn UpdatingBatchPage() -> impl IntoView {
 	utils::react_id(move |id| {
+		let cancel = create_server_action::<CancelBatch>();
 		let resource = create_resource(
-			|| (),
-			move |_| get_batch_status(id as usize)
+			move || cancel.version().get(),
+			move |_| get_batch_status(id)
 		);
-		let handle_store = RwSignal::<Option<leptos_dom::helpers::TimeoutHandle>>::new(None);
-		//Repeatedly refetch status
+		//Updates pushed by the server, which are newer than the resource
+		let live_status = RwSignal::<Option<BatchStatus>>::new(None);
+		let fetched = RwSignal::<Vec<FetchSummary>>::new(Vec::new());
+		//Effects only run in the browser
 		create_effect(move |_| {
-			if let Some(handle) = handle_store.get() {
-				handle.clear();
-			}
-			if let Some(Ok(batch_status)) = resource.get() {
-				if !batch_status.is_finished() {
-					//Use timeout instead of interval to wait until it has updated and not overfetch the server
-					let handle_res = set_timeout_with_handle(move || resource.refetch(), REFRESH_INTERVAL);
-					match handle_res {
-						Ok(handle) => handle_store.set(Some(handle)),
-						Err(err) => {
-							tracing::error!(?err, "Error setting timeout");
-						}
-					}
-				}
-			}
-		});
-		on_cleanup(move || {
-			if let Some(handle) = handle_store.get() {
-				handle.clear();
-			}
+			#[cfg(feature="hydrate")]
+			listen_to_batch(id, live_status, fetched);
 		});
 		
-		
 		view! {
 			<leptos_meta::Title text=format!("batch {}", id) />
 			<main>
@@ -101,28 +214,207 @@ pub fn UpdatingBatchPage() -> impl IntoView {
 					resource
 					let:batch
 				>
-					<BatchInfo batch/>
+					{
+						let batch = Signal::derive(move || live_status.get().unwrap_or_else(|| batch.clone()));
+						view! {
+							<BatchInfo batch/>
+							<Show
+								when=move || !batch.get().is_finished()
+								fallback=move || view! { <BatchSummary id /> }
+							>
+								<ActionForm action=cancel>
+									<input type="hidden" name="batch_ref" value=id/>
+									<utils::FormSubmit button="cancel" action=cancel/>
+								</ActionForm>
+								<FetchedList fetched />
+							</Show>
+						}
+					}
 				</utils::ResourceOk>
 			</main>
 		}
 	})
 }
 
+///The fetches that finished while watching a batch
 #[component]
-pub fn BatchInfo(#[prop(into)] batch: MaybeSignal<BatchStatus>) -> impl IntoView {
+fn FetchedList(fetched: RwSignal<Vec<FetchSummary>>) -> impl IntoView {
 	view! {
-		<div> {format!("Finished: {} / {}",
+		<ul>
+			<For
+				each=move || fetched.get()
+				key=|summary| summary.feed_id
+				let:summary
+			>
+				<li>
+					<A href=format!("/feed/{}", summary.feed_id)>"feed " {summary.feed_id}</A>
+					": "
+					{
+						match (summary.fetch_id, summary.status) {
+							(Some(fetch_id), Some(status)) => view! {
+								<A href=format!("/fetch/{fetch_id}")>{status.to_string()}</A>
+							}.into_view(),
+							_ => "Error saving fetch".into_view(),
+						}
+					}
+				</li>
+			</For>
+		</ul>
+	}
+}
+
+#[component]
+pub fn BatchInfo(#[prop(into)] batch: Signal<BatchStatus>) -> impl IntoView {
+	view! {
+		<div> {move || format!("Finished: {} / {}",
 			batch.get().done,
 			batch.get().total,
 		)} </div>
-		<progress max=batch.get().total value=batch.get().done/>
+		<progress max=move || batch.get().total value=move || batch.get().done/>
+		<Show when=move || batch.get().cancelled>
+			<div> "Cancelled" </div>
+		</Show>
+	}
+}
+
+
+
+///How many fetches of a batch ended up with each status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCounts {
+	pub fetches: Vec<(fetch::Status, i64)>,
+	///Feeds that never got a fetch, because the batch got cancelled or interrupted first
+	pub unfetched: i64,
+	///The status of the batch, which is why feeds are unfetched
+	pub status: batch::Status,
+}
+
+#[server]
+pub async fn get_batch_status_counts(batch_ref: i32) -> Result<BatchCounts, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	
+	let Some(batch) = batch::Entity::find_by_id(batch_ref).one(&conn).await? else {
+		return Err(ServerFnError::ServerError("No such batch".into()));
+	};
+	
+	let fetches = fetch::Entity::find()
+		.select_only()
+		.column(fetch::Column::Status)
+		.column_as(fetch::Column::Id.count(), "count")
+		.filter(fetch::Column::BatchId.eq(batch_ref))
+		.group_by(fetch::Column::Status)
+		.into_tuple::<(fetch::Status, i64)>()
+		.all(&conn)
+		.await?;
+	let fetched: i64 = fetches.iter().map(|(_status, count)| count).sum();
+	
+	Ok(BatchCounts {
+		fetches,
+		unfetched: i64::from(batch.total) - fetched,
+		status: batch.status,
+	})
+}
+
+#[server]
+pub async fn get_batch_fetches(batch_ref: i32, page: PageRequest) -> Result<Page<FetchOverview>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let query = FetchOverview::query(|query| query
+		.filter(fetch::Column::BatchId.eq(batch_ref))
+		.order_by_asc(fetch::Column::Id)
+	);
+	Ok(fetch_page(query, page, &conn).await?)
+}
+
+///What happened to the fetches of a finished batch
+#[component]
+pub fn BatchSummary(id: i32) -> impl IntoView {
+	let request = RwSignal::new(PageRequest::default());
+	let fetches = Resource::new(move || request.get(), move |page| get_batch_fetches(id, page));
+	
+	view! {
+		<utils::AwaitOk future=move || get_batch_status_counts(id) let:counts>
+			<ul>
+				{
+					counts.fetches.into_iter().map(|(status, count)| view! {
+						<li> {status.to_string()} ": " {count} </li>
+					}).collect_view()
+				}
+				{
+					(counts.unfetched > 0).then(|| view! {
+						<li> {counts.status.to_string()} ": " {counts.unfetched} </li>
+					})
+				}
+			</ul>
+		</utils::AwaitOk>
+		<utils::ResourceOk resource=fetches let:page fallback=|| ()>
+			<ObjectTable items=page.items paging=Some(Paging { request, info: page.info })/>
+		</utils::ResourceOk>
+	}
+}
+
+
+///A batch as displayed in a list
+#[derive(
+	Clone, Debug, PartialEq, Eq,
+	serde::Serialize, serde::Deserialize,
+	ff_macros::FieldList,
+	bevy_reflect::Reflect
+)]
+#[cfg_attr(feature="ssr", derive(FromQueryResult))]
+#[reflect(from_reflect = false)]
+pub struct BatchOverview {
+	pub id: i32,
+	pub status: batch::Status,
+	pub total: i32,
+	pub created_at: time_fields::PrimitiveDateTime,
+	pub updated_at: time_fields::PrimitiveDateTime,
+}
+
+#[cfg(feature="ssr")]
+impl View for BatchOverview {
+	type Entity = batch::Entity;
+	
+	fn columns() -> impl Iterator<Item = impl sea_orm::ColumnTrait> {
+		batch::Column::iter()
+	}
+}
+
+impl ff_object::Object for BatchOverview {
+	fn get_id(&self) -> i32 {
+		self.id
+	}
+	
+	///Matches the route instead of the entity
+	fn get_object_name() -> &'static str {
+		"fetch_batch"
+	}
+}
+
+#[server]
+pub async fn get_batches(page: PageRequest) -> Result<Page<BatchOverview>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let query = BatchOverview::query(|query| query.order_by_desc(batch::Column::Id));
+	Ok(fetch_page(query, page, &conn).await?)
+}
+
+#[component]
+pub fn History() -> impl IntoView {
+	let request = RwSignal::new(PageRequest::default());
+	let batches = Resource::new(move || request.get(), get_batches);
+	
+	view! {
+		<main>
+			<utils::ResourceOk resource=batches let:page fallback=|| ()>
+				<ObjectTable items=page.items paging=Some(Paging { request, info: page.info })/>
+			</utils::ResourceOk>
+		</main>
 	}
 }
 
 
 
 #[server]
-pub async fn fetch_all() -> Result<usize, ServerFnError> {
+pub async fn fetch_all() -> Result<i32, ServerFnError> {
 	let db = crate::extension!(DatabaseConnection);
 	let strats = crate::extension!(acquire::StrategyList);
 	let tracker = crate::extension!(acquire::batch_tracker::BatchTracker);
@@ -131,7 +423,7 @@ pub async fn fetch_all() -> Result<usize, ServerFnError> {
 	
 	let feeds = feeds.into_iter().map(|feed| feed.id).collect();
 	
-	let batch_id = tracker.queue_fetches(feeds, db, strats).await;
+	let batch_id = tracker.queue_fetches(feeds, db, strats).await?;
 	
 	Ok(batch_id)
 }
@@ -5,8 +5,9 @@ use crate::{table, entry::search::EntryOverview};
 use crate::utils;
 #[cfg(feature="ssr")]
 use sea_orm::*;
+use ff_object::page::{Page, PageRequest};
 #[cfg(feature="ssr")]
-use ff_object::View;
+use ff_object::{View, page::fetch_page};
 
 
 #[component(transparent)]
@@ -129,24 +130,26 @@ pub fn FetchLog() -> impl IntoView {
 }
 
 #[server]
-async fn get_entries(fetch_id: i32) -> Result<Vec<EntryOverview>,ServerFnError> {
+async fn get_entries(fetch_id: i32, page: PageRequest) -> Result<Page<EntryOverview>,ServerFnError> {
 	let conn = crate::extension!(DatabaseConnection);
 	let query = <fetch::Entity as Related<entry::Entity>>::find_related()
 		.filter(fetch::Column::Id.eq(fetch_id));
-	EntryOverview::from_query(query)
-		.all(&conn)
-		.await
-		.map_err(|e| e.into())	
+	Ok(fetch_page(EntryOverview::from_query(query), page, &conn).await?)
 }
 
 #[component]
 pub fn Entries() -> impl IntoView {
 	let fetch = crate::model!(fetch);
+	let request = RwSignal::new(PageRequest::default());
+	let entries = Resource::new(
+		move || (fetch.get().id, request.get()),
+		|(id, page)| get_entries(id, page)
+	);
 	
 	view! {
-		<utils::AwaitOk future=move || get_entries(fetch.get().id) let:entries>
-			<crate::entry::search::Table entries />
-		</utils::AwaitOk>
+		<utils::ResourceOk resource=entries let:page fallback=|| ()>
+			<crate::entry::search::Table entries=page.items paging=Some(table::Paging { request, info: page.info })/>
+		</utils::ResourceOk>
 	}.into()
 }
 
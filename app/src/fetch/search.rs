@@ -26,6 +26,7 @@ pub struct FetchOverview {
 	// pub log: String,
 	pub strategy: String,
 	pub feed_id: i32,
+	pub batch_id: Option<i32>,
 	pub created_at: time_fields::PrimitiveDateTime,
 	pub updated_at: time_fields::PrimitiveDateTime,
 }
@@ -38,7 +39,7 @@ impl View for FetchOverview {
 	fn columns() -> impl Iterator<Item = impl sea_orm::ColumnTrait> {
 		fetch::Column::iter().filter(|column| {
 			use fetch::Column::*;
-			!matches!(column, Content | Error | Log )
+			!matches!(column, Content | Error | Log | Etag | LastModified )
 		})
 	}
 }
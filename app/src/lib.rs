@@ -4,7 +4,10 @@ pub mod feeds;
 pub mod fetch;
 pub mod entry;
 pub mod tag;
+pub mod saved_query;
 pub mod strategies;
+pub mod report;
+pub mod stats;
 
 pub mod table;
 pub mod utils;
@@ -1,19 +1,50 @@
+use std::time::Duration;
 use leptos::*;
 use crate::utils;
 use serde::{Deserialize, Serialize};
 use ff_object::describe::Described;
-use entities::prelude::tag;
-use ffilter::shared::{ArgumentData, ArgumentType};
+use entities::prelude::{feed, tag, time_fields};
+use ffilter::shared::{ArgumentData, ArgumentType, NestedFilter, Target};
 #[cfg(feature="ssr")]
 use ffilter::{
-	filter_list::FilterList,
-	filter::Filter as ServerFilter,
+	filter_list::{FilterList, FilterLists},
+	filter::{Filter as ServerFilter, FilterTarget},
 };
 
 
 pub type ArgumentDesc = Described<ArgumentType>;
 pub type FilterDesc = Described<Vec<ArgumentDesc>>;
 
+///The descriptions of the filters for each [Target]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterDescs {
+	pub feed: Vec<FilterDesc>,
+	pub entry: Vec<FilterDesc>,
+}
+
+impl FilterDescs {
+	pub fn get(&self, target: Target) -> &[FilterDesc] {
+		match target {
+			Target::Feed => &self.feed,
+			Target::Entry => &self.entry,
+		}
+	}
+}
+
+const DATE_FORMAT: &[time::format_description::FormatItem<'_>] = time::macros::format_description!("[year]-[month]-[day]");
+
+const MINUTE: Duration = Duration::from_secs(60);
+const HOUR: Duration = Duration::from_secs(60*60);
+const DAY: Duration = Duration::from_secs(24*60*60);
+const WEEK: Duration = Duration::from_secs(7*24*60*60);
+///The units a [Duration] can be edited in, from big to small
+const DURATION_UNITS: [(&str, Duration); 4] = [
+	("weeks", WEEK),
+	("days", DAY),
+	("hours", HOUR),
+	("minutes", MINUTE),
+];
+
 
 impl From<ClientArgument> for ArgumentData {
 	fn from(ca: ClientArgument) -> Self {
@@ -22,6 +53,43 @@ impl From<ClientArgument> for ArgumentData {
 		match ca {
 			CA::Bool(sig) => A::Bool(sig.get()),
 			CA::Tag(sig) => A::Tag(sig.get()),
+			CA::String(sig) => A::String(sig.get()),
+			CA::Integer(sig) => A::Integer(sig.get()),
+			CA::Date(sig) => A::Date(sig.get()),
+			CA::Duration(sig) => A::Duration(sig.get()),
+			CA::Strategy(sig) => A::Strategy(sig.get()),
+			CA::Feed(sig) => A::Feed(sig.get()),
+			CA::Filter(sig) => A::Filter(Filter::from(sig.get()).into()),
+			CA::Filters(sig) => A::Filters(
+				sig.get().into_iter()
+					.map(|filter_sig| Filter::from(filter_sig.get()).into())
+					.collect()
+			),
+			CA::FeedFilter(sig) => A::FeedFilter(Filter::from(sig.get()).into()),
+		}
+	}
+}
+
+impl From<ArgumentData> for ClientArgument {
+	fn from(data: ArgumentData) -> Self {
+		use ClientArgument as CA;
+		use ArgumentData as A;
+		match data {
+			A::Bool(bool) => CA::Bool(RwSignal::new(bool)),
+			A::Tag(tag) => CA::Tag(RwSignal::new(tag)),
+			A::String(string) => CA::String(RwSignal::new(string)),
+			A::Integer(integer) => CA::Integer(RwSignal::new(integer)),
+			A::Date(date) => CA::Date(RwSignal::new(date)),
+			A::Duration(duration) => CA::Duration(RwSignal::new(duration)),
+			A::Strategy(strategy) => CA::Strategy(RwSignal::new(strategy)),
+			A::Feed(feed) => CA::Feed(RwSignal::new(feed)),
+			A::Filter(nested) => CA::Filter(RwSignal::new(Filter::from(nested).into())),
+			A::Filters(nested) => CA::Filters(RwSignal::new(
+				nested.into_iter()
+					.map(|nested| RwSignal::new(Filter::from(nested).into()))
+					.collect()
+			)),
+			A::FeedFilter(nested) => CA::FeedFilter(RwSignal::new(Filter::from(nested).into())),
 		}
 	}
 }
@@ -30,16 +98,62 @@ impl From<ClientArgument> for ArgumentData {
 pub enum ClientArgument {
 	Bool(RwSignal<bool>),
 	Tag(RwSignal<tag::Ref>),
+	String(RwSignal<String>),
+	Integer(RwSignal<i64>),
+	Date(RwSignal<time_fields::Date>),
+	Duration(RwSignal<Duration>),
+	///The name of a strategy, empty when none is selected yet
+	Strategy(RwSignal<String>),
+	Feed(RwSignal<feed::Ref>),
+	Filter(RwSignal<ClientFilter>),
+	Filters(RwSignal<Vec<RwSignal<ClientFilter>>>),
+	FeedFilter(RwSignal<ClientFilter>),
+}
+
+fn is_nesting(arg_desc: &ArgumentDesc) -> bool {
+	matches!(arg_desc.data, ArgumentType::Filter | ArgumentType::Filters | ArgumentType::FeedFilter)
 }
 
-fn client_arg_default(kind: ArgumentType, default_tag: Option<tag::Ref>) -> ClientArgument {
+///The description of the filter to start with when adding a nested filter, one without nested filters itself to avoid infinite nesting.
+///None if the server has no such filter
+fn default_nested_description(filters: &FilterDescs, target: Target) -> Option<&FilterDesc> {
+	filters.get(target).iter()
+		.find(|filter| !filter.data.iter().any(is_nesting))
+}
+
+fn default_nested_filter(filters: &FilterDescs, target: Target) -> Option<ClientFilter> {
+	default_nested_description(filters, target)
+		.and_then(|description| ClientFilter::from_description(description, filters, target))
+}
+
+///If a filter with this description can be created, which isn't the case when it needs a nested filter there is no default for
+fn has_default(description: &FilterDesc, filters: &FilterDescs, target: Target) -> bool {
+	description.data.iter().all(|arg_desc| match arg_desc.data {
+		ArgumentType::Filter => default_nested_description(filters, target).is_some(),
+		ArgumentType::FeedFilter => default_nested_description(filters, Target::Feed).is_some(),
+		_ => true,
+	})
+}
+
+fn client_arg_default(kind: ArgumentType, default_tag: Option<tag::Ref>, filters: &FilterDescs, target: Target) -> Option<ClientArgument> {
 	use ClientArgument as CA;
 	use ArgumentType as AT;
 	
-	match kind {
+	let argument = match kind {
 		AT::Bool => CA::Bool(RwSignal::new(false)),
 		AT::Tag => CA::Tag(RwSignal::new(default_tag.expect("there should exist a tag to select"))),
-	}
+		AT::String => CA::String(RwSignal::new(String::new())),
+		AT::Integer => CA::Integer(RwSignal::new(0)),
+		AT::Date => CA::Date(RwSignal::new(time_fields::Date(time::OffsetDateTime::now_utc().date()))),
+		AT::Duration => CA::Duration(RwSignal::new(DAY)),
+		AT::Strategy => CA::Strategy(RwSignal::new(String::new())),
+		//No feed has this id, so nothing gets selected
+		AT::Feed => CA::Feed(RwSignal::new(feed::Ref::new(-1))),
+		AT::Filter => CA::Filter(RwSignal::new(default_nested_filter(filters, target)?)),
+		AT::Filters => CA::Filters(RwSignal::new(Vec::new())),
+		AT::FeedFilter => CA::FeedFilter(RwSignal::new(default_nested_filter(filters, Target::Feed)?)),
+	};
+	Some(argument)
 }
 
 #[component]
@@ -80,20 +194,213 @@ fn TagEditor(value: RwSignal<tag::Ref>, #[prop(default=None)] id: Option<String>
 }
 
 #[component]
-fn ArgumentUI(argument: ClientArgument, #[prop(optional, default=None)] id: Option<String>) -> impl IntoView {
+fn StringEditor(value: RwSignal<String>, #[prop(default=None)] id: Option<String>) -> impl IntoView {
+	view! {
+		<input type="text" id=id prop:value=value on:input=move |event| {
+			value.set(event_target_value(&event));
+		}/>
+	}
+}
+
+#[component]
+fn IntegerEditor(value: RwSignal<i64>, #[prop(default=None)] id: Option<String>) -> impl IntoView {
+	view! {
+		<input type="number" step="1" id=id prop:value=value on:input=move |event| {
+			//Keep the old value while the input is invalid, e.g. only a minus sign
+			if let Ok(integer) = event_target_value(&event).parse::<i64>() {
+				value.set(integer);
+			}
+		}/>
+	}
+}
+
+///Edits the amount of the biggest unit that fits the duration exactly
+#[component]
+fn DurationEditor(value: RwSignal<Duration>, #[prop(default=None)] id: Option<String>) -> impl IntoView {
+	let initial = value.get_untracked();
+	let (_name, initial_unit) = DURATION_UNITS.into_iter()
+		.find(|(_name, unit)| initial.as_secs().is_multiple_of(unit.as_secs()))
+		.unwrap_or(DURATION_UNITS[DURATION_UNITS.len()-1]);
+	let unit = RwSignal::new(initial_unit);
+	let amount = RwSignal::new(initial.as_secs() / initial_unit.as_secs());
+	
+	let update = move || value.set(Duration::from_secs(unit.get_untracked().as_secs().saturating_mul(amount.get_untracked())));
+	
+	view! {
+		<input type="number" min="0" step="1" id=id prop:value=amount on:input=move |event| {
+			if let Ok(new) = event_target_value(&event).parse::<u64>() {
+				amount.set(new);
+				update();
+			}
+		}/>
+		<select on:change=move |event| {
+			let name = event_target_value(&event);
+			let (_name, new) = DURATION_UNITS.into_iter()
+				.find(|(unit_name, _unit)| *unit_name == name)
+				.expect("option value should have been set to a unit name");
+			unit.set(new);
+			update();
+		}>
+			{
+				DURATION_UNITS.into_iter()
+					.map(|(name, option_unit)| view! {
+						<option value=name selected=move || unit.get()==option_unit> {name} </option>
+					})
+					.collect_view()
+			}
+		</select>
+	}
+}
+
+#[component]
+fn StrategyEditor(value: RwSignal<String>, #[prop(default=None)] id: Option<String>) -> impl IntoView {
+	view! {
+		<select id=id on:change=move |event| {
+			value.set(event_target_value(&event));
+		}>
+			<option value="" disabled=true selected=move || value.get().is_empty()> "select a strategy" </option>
+			<utils::AwaitOk future=crate::strategies::get_strategies let:strategies>
+				<For
+					each=move || strategies.clone()
+					key=|strategy| strategy.clone()
+					let:strategy
+				>
+					{
+						let name_clone = strategy.clone();
+						view! {
+							<option
+								value=strategy.clone()
+								selected=move || value.get()==name_clone
+							>
+								{strategy}
+							</option>
+						}
+					}
+				</For>
+			</utils::AwaitOk>
+		</select>
+	}
+}
+
+#[component]
+fn FeedEditor(value: RwSignal<feed::Ref>, #[prop(default=None)] id: Option<String>) -> impl IntoView {
+	view! {
+		<select id=id on:change=move |event| {
+			let id_str = event_target_value(&event);
+			let id = id_str.parse::<i32>().expect("option value should have been set to a valid i32");
+			value.set(feed::Ref::new(id));
+		}>
+			<option value="" disabled=true selected=move || value.get().id() < 0> "select a feed" </option>
+			<utils::AwaitOk future=crate::feeds::search::feed_choices let:feeds>
+				<For
+					each=move || feeds.clone()
+					key=|feed| feed.id
+					let:feed
+				>
+					<option
+						value=feed.id
+						selected=move || value.get().id()==feed.id
+					>
+						{feed.name}
+					</option>
+				</For>
+			</utils::AwaitOk>
+		</select>
+	}
+}
+
+#[component]
+fn DateEditor(value: RwSignal<time_fields::Date>, #[prop(default=None)] id: Option<String>) -> impl IntoView {
+	let formatted = move || value.get().0.format(DATE_FORMAT).expect("format should be fine");
+	view! {
+		<input type="date" id=id prop:value=formatted on:change=move |event| {
+			//Browsers leave the value empty when the date is incomplete
+			if let Ok(date) = time::Date::parse(&event_target_value(&event), DATE_FORMAT) {
+				value.set(time_fields::Date(date));
+			}
+		}/>
+	}
+}
+
+///Edits a list of filters, which can be added and removed
+#[component]
+fn FiltersEditor(value: RwSignal<Vec<RwSignal<ClientFilter>>>, filters: FilterDescs, target: Target, sub_id: String) -> impl IntoView {
+	let add_filters = filters.clone();
+	let can_add = default_nested_description(&filters, target).is_some();
+	//Ids of removed filters aren't reused, so the ids of the other filters don't change when one gets removed
+	let next_id = StoredValue::new(0_usize);
+	view! {
+		<ul class="nested_filters">
+			<For
+				each=move || value.get()
+				key=|filter_sig| *filter_sig
+				let:filter_sig
+			>
+				{
+					let item_id = next_id.get_value();
+					next_id.set_value(item_id+1);
+					let (get, set) = filter_sig.split();
+					view! {
+						<li>
+							<FilterUI get=get.into() set=set.into() filters=filters.clone() target sub_id=format!("{sub_id}_{item_id}") />
+							<button on:click=move |_event| {
+								value.update(|list| list.retain(|other| *other != filter_sig));
+							}>
+								"remove"
+							</button>
+						</li>
+					}
+				}
+			</For>
+			<li>
+				<button disabled=!can_add on:click=move |_event| {
+					if let Some(new) = default_nested_filter(&add_filters, target) {
+						value.update(|list| list.push(RwSignal::new(new)));
+					}
+				}>
+					"add filter"
+				</button>
+			</li>
+		</ul>
+	}
+}
+
+///Always a [View], since it can contain [FilterUI]s, which would otherwise make for an infinitely recursive type
+#[component]
+fn ArgumentUI(
+	argument: ClientArgument,
+	filters: FilterDescs,
+	///The target of the filter this is an argument of
+	target: Target,
+	#[prop(into)] sub_id: String,
+	#[prop(optional, default=None)] id: Option<String>,
+) -> impl IntoView {
 	use ClientArgument::*;
 	match argument {
-		Bool(value) => view!{ <BoolEditor value id/> },
-		Tag(value) => view!{ <TagEditor value id/> },
+		Bool(value) => view!{ <BoolEditor value id/> }.into_view(),
+		Tag(value) => view!{ <TagEditor value id/> }.into_view(),
+		String(value) => view!{ <StringEditor value id/> }.into_view(),
+		Integer(value) => view!{ <IntegerEditor value id/> }.into_view(),
+		Date(value) => view!{ <DateEditor value id/> }.into_view(),
+		Duration(value) => view!{ <DurationEditor value id/> }.into_view(),
+		Strategy(value) => view!{ <StrategyEditor value id/> }.into_view(),
+		Feed(value) => view!{ <FeedEditor value id/> }.into_view(),
+		Filter(value) => {
+			let (get, set) = value.split();
+			view!{ <FilterUI get=get.into() set=set.into() filters target sub_id /> }.into_view()
+		},
+		Filters(value) => view!{ <FiltersEditor value filters target sub_id /> }.into_view(),
+		FeedFilter(value) => {
+			let (get, set) = value.split();
+			view!{ <FilterUI get=get.into() set=set.into() filters target=Target::Feed sub_id /> }.into_view()
+		},
 	}
 }
 
 
-#[server]
-pub async fn get_filters() -> Result<Vec<FilterDesc>, ServerFnError> {
-	let filters = crate::extension!(FilterList);
-	
-	let filter_descriptions = filters.iter_filters()
+#[cfg(feature="ssr")]
+fn describe_filters<Target: FilterTarget>(filters: &FilterList<Target>) -> Vec<FilterDesc> {
+	filters.iter_filters()
 		.map(|filter| {
 			let args = filter.box_clone().into_arguments().into_iter()
 				.map(|arg_desc| arg_desc.map(ArgumentType::from))
@@ -106,9 +413,17 @@ pub async fn get_filters() -> Result<Vec<FilterDesc>, ServerFnError> {
 				filter.get_description().map(|d| d.to_owned()),
 			)
 		})
-		.collect();
+		.collect()
+}
+
+#[server]
+pub async fn get_filters() -> Result<FilterDescs, ServerFnError> {
+	let filters = crate::extension!(FilterLists);
 	
-	Ok(filter_descriptions)
+	Ok(FilterDescs {
+		feed: describe_filters(&filters.feed),
+		entry: describe_filters(&filters.entry),
+	})
 }
 
 #[cfg(feature="ssr")]
@@ -136,21 +451,35 @@ impl Filter {
 		}
 	}
 	
+	///`Target` is the kind of rows to filter, e.g. [entities::prelude::feed::Entity]
 	#[cfg(feature="ssr")]
-	pub fn into_filter(self, list: FilterList) -> Result<Box<dyn ServerFilter>, FromFilterError> {
-		let builder = list.get_builder_by_name(&self.name)?;
-		
-		let arguments = self.arguments.into_iter()
-			.map(ArgumentData::from)
-			.collect();
+	pub fn into_filter<Target: FilterTarget>(self, lists: &FilterLists) -> Result<Box<dyn ServerFilter<Target> + Send + Sync>, FromFilterError> {
+		let builder = lists.get::<Target>().get_builder_by_name(&self.name)?;
 		
-		
-		let filter = builder.build(arguments)?;
+		let filter = builder.build(self.arguments, lists)?;
 		
 		Ok(filter)
 	}
 }
 
+impl From<Filter> for NestedFilter {
+	fn from(filter: Filter) -> Self {
+		Self {
+			name: filter.name,
+			arguments: filter.arguments,
+		}
+	}
+}
+
+impl From<NestedFilter> for Filter {
+	fn from(nested: NestedFilter) -> Self {
+		Self {
+			name: nested.name,
+			arguments: nested.arguments,
+		}
+	}
+}
+
 
 #[derive(Debug,Clone, PartialEq, Eq)]
 pub struct ClientFilter {
@@ -167,16 +496,18 @@ impl ClientFilter {
 		}
 	}
 	
-	pub fn from_description(description: &FilterDesc) -> Self {
+	///`filters` is used for the defaults of nested filters, `target` is the target of the described filter.
+	///None if the filter needs a nested filter, but there is no filter to start it with
+	pub fn from_description(description: &FilterDesc, filters: &FilterDescs, target: Target) -> Option<Self> {
 		let default_tag = Some(tag::Ref::new(1));
 		let arguments = description.data.iter()
-			.map(|arg_desc| client_arg_default(arg_desc.data, default_tag.clone()))
-			.collect();
+			.map(|arg_desc| client_arg_default(arg_desc.data, default_tag.clone(), filters, target))
+			.collect::<Option<_>>()?;
 		
-		Self {
+		Some(Self {
 			name: description.name.clone(),
 			arguments,
-		}
+		})
 	}
 }
 
@@ -194,7 +525,7 @@ impl From<Filter> for ClientFilter {
 	fn from(filter: Filter) -> Self {
 		Self {
 			name: filter.name,
-			arguments: Vec::new(),
+			arguments: filter.arguments.into_iter().map(|arg| arg.into()).collect(),
 		}
 	}
 }
@@ -204,32 +535,42 @@ impl From<Filter> for ClientFilter {
 pub fn FilterUI(
 	set: SignalSetter<ClientFilter>,
 	get: Signal<ClientFilter>,
-	filters: Vec<FilterDesc>,
+	filters: FilterDescs,
+	///What kind of rows the filter filters, which decides which filters can be selected
+	#[prop(optional)] target: Target,
 	#[prop(into)] sub_id: String
 ) -> impl IntoView {
 	let id = format!("filter_{sub_id}");
+	let sub_id = sub_id.clone();
 	
 	let current = get.get();
-	let desc = filters.iter().find(|filter| filter.name==current.name)
+	let desc = filters.get(target).iter().find(|filter| filter.name==current.name)
 		.expect("provided ClientFilter should be from the filters list");
 	let description = RwSignal::new(desc.clone());
 	
+	let options = filters.get(target).iter()
+		.filter(|filter| has_default(filter, &filters, target))
+		.cloned()
+		.collect::<Vec<_>>();
 	let filters2 = filters.clone();
 	
 	view! {
 		<span>
 			<select name=id.clone() id=id on:change=move |event| {
 				let selected_name = event_target_value(&event);
-				let filter_desc = filters.iter().find(|filter| filter.name==selected_name)
+				let filter_desc = filters.get(target).iter().find(|filter| filter.name==selected_name)
 					.expect("the name can only be selected from values from this list, so it should be in this list");
+				let Some(new) = ClientFilter::from_description(filter_desc, &filters, target) else {
+					return;
+				};
 				//Batch to avoid mismatch between argument data and descriptions
 				batch(|| {
-					set.set(ClientFilter::from_description(filter_desc));
+					set.set(new);
 					description.set(filter_desc.clone());
 				})
 			}>
 				<For
-					each=move || filters2.clone()
+					each=move || options.clone()
 					key=|filter| filter.name.clone()
 					let:filter_data
 				>
@@ -252,10 +593,16 @@ pub fn FilterUI(
 				key=|(_value, desc)| desc.name.clone()
 				let:arg
 			>
-				<span>
-					<label for=format!("arg_{}",arg.1.name)> {arg.1.name.clone()} ":" </label>
-					<ArgumentUI argument=arg.0 id=format!("arg_{}",arg.1.name)/>
-				</span>
+				{
+					let arg_id = format!("arg_{sub_id}_{}", arg.1.name);
+					let arg_sub_id = format!("{sub_id}_{}", arg.1.name);
+					view! {
+						<span>
+							<label for=arg_id.clone()> {arg.1.name.clone()} ":" </label>
+							<ArgumentUI argument=arg.0 filters=filters2.clone() target sub_id=arg_sub_id id=arg_id/>
+						</span>
+					}
+				}
 			</For>
 		</span>
 	}
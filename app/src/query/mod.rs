@@ -1,6 +1,7 @@
 /*!
 
-[ffilter::filter::Filter]s live on the server in a [ffilter::filter_list::FilterList].
+[ffilter::filter::Filter]s live on the server in a [ffilter::filter_list::FilterList],
+one for each [ffilter::filter::FilterTarget] (the kind of rows they filter), together in [ffilter::filter_list::FilterLists].
 
 When we want them on the client, we turn them into a [filter::FilterDesc],
 using [ffilter::filter::DynReprArgument] (which is a dyn wrapper around [ffilter::filter::ReprArgument])
@@ -16,7 +17,8 @@ which then can get edited using a [FilterUI].
 In order to get the server to do things again, the [ClientFilter] gets turned into a [Filter], which is designed for transport.
 On the server, this one can be converted into a [`Box`]`< dyn `[`ffilter::filter::Filter`]`>` using [Filter::into_filter],
 which can then filter a query with [ffilter::filter::Filter::filter].
-All this currently/at the time of this writing has to be done by the end user, e.g. [crate::feeds::search::search2].
+[Query::apply] does this, and also sorts the query using its [sort::SortKey]s,
+e.g. in [crate::feeds::search::search2] and [crate::entry::search::search].
 
 
 */
@@ -29,6 +31,10 @@ pub use filter::{
 	Filter,
 };
 
+pub mod sort;
+pub use sort::SortKey;
+
+#[allow(clippy::module_inception)]
 pub mod query;
 pub use query::{
 	QueryUI,
@@ -1,166 +1,248 @@
-use leptos::*;
-use serde::{Deserialize, Serialize};
-use crate::utils;
-
-use super::{ClientFilter, Filter, FilterUI, filter::get_filters};
-
-
-///Condensed query type for transport between server and client
-#[derive(Debug, Default, Clone, PartialEq,Eq, Serialize, Deserialize)]
-pub struct Query {
-	filter: Option<Filter>,
-}
-
-impl Query {
-	pub fn from_filter_name(name: impl Into<String>) -> Self {
-		Self {
-			filter: Some(Filter::from_name(name.into())),
-		}
-	}
-	
-	pub fn into_filter(self) -> Option<Filter> {
-		self.filter
-	}
-}
-
-
-///Query type with signals in its innards so it can easily be hooked up into editor UI
-#[derive(Debug, Default, Clone, PartialEq,Eq)]
-pub struct ClientQuery {
-	filter: RwSignal<Option<RwSignal<ClientFilter>>>,
-}
-
-impl ClientQuery {
-	pub fn get_filter_signal(&self) -> RwSignal<Option<RwSignal<ClientFilter>>> {
-		self.filter
-	}
-	
-	
-	pub fn into_query(&self) -> Query {
-		let filter = self.filter.get().map(|filter_sig| filter_sig.get().into());
-		Query {
-			filter,
-		}
-	}
-}
-
-impl From<ClientQuery> for Query {
-	fn from(client_query: ClientQuery) -> Self {
-		client_query.into_query()
-	}
-}
-
-impl From<Query> for ClientQuery {
-	fn from(query: Query) -> Self {
-		let filter = RwSignal::new(query.filter.map(|filter| RwSignal::new(filter.into())));
-		Self {
-			filter,
-		}
-	}
-}
-
-
-/**
-Wrapper around [Query] with [std::fmt::Display]/[ToString] and [std::str::FromStr] impl based on serde_json.
-Used to put a (search) query in a browser query.
-*/
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-pub struct QueryString {
-	pub query: Query,
-}
-
-impl std::fmt::Display for QueryString {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let maybe_serialized = serde_json::to_string(&self.query);
-		let serialized = maybe_serialized.map_err(|err| {
-			tracing::error!(?err, "Error serializing query");
-			std::fmt::Error
-		})?;
-		write!(f, "{serialized}")
-	}
-}
-
-impl std::str::FromStr for QueryString {
-	type Err = serde_json::Error;
-	
-	fn from_str(source: &str) -> Result<Self, Self::Err> {
-		let query = serde_json::from_str::<Query>(source)?;
-		Ok(Self { query })
-	}
-}
-
-impl From<Query> for QueryString {
-	fn from(query: Query) -> Self {
-		Self { query }
-	}
-}
-
-impl From<QueryString> for Query {
-	fn from(query_string: QueryString) -> Self {
-		query_string.query
-	}
-}
-
-
-#[component]
-pub fn QueryUI(#[prop(into)] on_search: Callback<Query>, pending: Signal<bool>, #[prop(default=None)] default: Option<Query>) -> impl IntoView {
-	let again = RwSignal::new(false);
-	
-	let button_name = move || {
-		if pending.get() {
-			"searching...".to_owned()
-		} else {
-			format!("search{}", if again.get() {" again"} else {""} )
-		}
-	};
-	
-	let client_query = default.map(ClientQuery::from).unwrap_or_default();
-	let filter = client_query.get_filter_signal();
-	
-	let filter_ui = move |filters| {
-		match filter.get() {
-			Some(filter_sig) => {
-				let (get, set) = filter_sig.split();
-				view! {
-					<FilterUI get=get.into() set=set.into() filters sub_id="" />
-				}.into_view()
-			},
-			None => ().into_view(),
-		}
-	};
-	
-	view! {
-		<div class="search">
-			<div class="search_parameters">
-				<div class="search_parameter">
-					<utils::AwaitOk future=get_filters let:filters>
-						<utils::CloneSignal base=filters let:filters_signal>
-							<label for="filter_enable">filter</label>
-							<input type="checkbox" id="filter_enable" prop:checked=filter.get().is_some() on:input=move |event| {
-								if event_target_checked(&event) {
-									let filters = filters_signal.get();
-									let default = filters.first().expect("the server should have at least 1 filter");
-									filter.set(Some(RwSignal::new(ClientFilter::from_description(default))));
-								} else {
-									filter.set(None);
-								}
-							}/>
-							{ move || filter_ui(filters_signal.get()) }
-						</utils::CloneSignal>
-					</utils::AwaitOk>
-				</div>
-			</div>
-			
-			<button
-				disabled=pending
-				on:click = move |_event| {
-					again.set(true);
-					on_search.call(client_query.clone().into());
-				}
-			>
-				{button_name}
-			</button>
-		</div>
-	}
-}
-
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use ffilter::shared::Target;
+use crate::utils;
+#[cfg(feature="ssr")]
+use ffilter::{filter::FilterTarget, filter_list::FilterLists};
+#[cfg(feature="ssr")]
+use sea_orm::Select;
+
+use super::{ClientFilter, Filter, FilterUI, filter::get_filters, sort::SortKey};
+#[cfg(feature="ssr")]
+use super::{filter::FromFilterError, sort::{apply_sort, UnknownFieldError}};
+
+
+///Condensed query type for transport between server and client
+#[derive(Debug, Default, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct Query {
+	filter: Option<Filter>,
+	#[serde(default)] //Default transport format errors on empty Vec
+	sort: Vec<SortKey>,
+}
+
+#[cfg(feature="ssr")]
+#[derive(Debug, thiserror::Error)]
+pub enum ApplyQueryError {
+	#[error(transparent)]
+	Filter(#[from] FromFilterError),
+	#[error(transparent)]
+	Sort(#[from] UnknownFieldError),
+}
+
+impl Query {
+	pub fn from_filter_name(name: impl Into<String>) -> Self {
+		Self {
+			filter: Some(Filter::from_name(name.into())),
+			sort: Vec::new(),
+		}
+	}
+	
+	pub fn get_sort(&self) -> &[SortKey] {
+		&self.sort
+	}
+	
+	pub fn set_sort(&mut self, sort: Vec<SortKey>) {
+		self.sort = sort;
+	}
+	
+	pub fn into_filter(self) -> Option<Filter> {
+		self.filter
+	}
+	
+	///Names of the filters in this query that aren't in `lists` (anymore)
+	#[cfg(feature="ssr")]
+	pub fn missing_filters<Target: FilterTarget>(&self, lists: &FilterLists) -> Vec<String> {
+		match &self.filter {
+			Some(filter) => lists.missing_filters::<Target>(&filter.clone().into()),
+			None => Vec::new(),
+		}
+	}
+	
+	///Filters and sorts `query`
+	#[cfg(feature="ssr")]
+	pub fn apply<Target: FilterTarget>(self, query: Select<Target>, lists: &FilterLists) -> Result<Select<Target>, ApplyQueryError> {
+		let query = match self.filter {
+			Some(filter) => filter.into_filter::<Target>(lists)?.filter(query),
+			None => query,
+		};
+		Ok(apply_sort(&self.sort, query)?)
+	}
+}
+
+
+///Query type with signals in its innards so it can easily be hooked up into editor UI
+#[derive(Debug, Default, Clone, PartialEq,Eq)]
+pub struct ClientQuery {
+	filter: RwSignal<Option<RwSignal<ClientFilter>>>,
+	sort: RwSignal<Vec<SortKey>>,
+}
+
+impl ClientQuery {
+	pub fn get_filter_signal(&self) -> RwSignal<Option<RwSignal<ClientFilter>>> {
+		self.filter
+	}
+	
+	pub fn get_sort_signal(&self) -> RwSignal<Vec<SortKey>> {
+		self.sort
+	}
+	
+	
+	pub fn into_query(&self) -> Query {
+		let filter = self.filter.get().map(|filter_sig| filter_sig.get().into());
+		Query {
+			filter,
+			sort: self.sort.get(),
+		}
+	}
+}
+
+impl From<ClientQuery> for Query {
+	fn from(client_query: ClientQuery) -> Self {
+		client_query.into_query()
+	}
+}
+
+impl From<Query> for ClientQuery {
+	fn from(query: Query) -> Self {
+		let filter = RwSignal::new(query.filter.map(|filter| RwSignal::new(filter.into())));
+		let sort = RwSignal::new(query.sort);
+		Self {
+			filter,
+			sort,
+		}
+	}
+}
+
+
+/**
+Wrapper around [Query] with [std::fmt::Display]/[ToString] and [std::str::FromStr] impl based on serde_json.
+Used to put a (search) query in a browser query.
+*/
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct QueryString {
+	pub query: Query,
+}
+
+impl std::fmt::Display for QueryString {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let maybe_serialized = serde_json::to_string(&self.query);
+		let serialized = maybe_serialized.map_err(|err| {
+			tracing::error!(?err, "Error serializing query");
+			std::fmt::Error
+		})?;
+		write!(f, "{serialized}")
+	}
+}
+
+impl std::str::FromStr for QueryString {
+	type Err = serde_json::Error;
+	
+	fn from_str(source: &str) -> Result<Self, Self::Err> {
+		let query = serde_json::from_str::<Query>(source)?;
+		Ok(Self { query })
+	}
+}
+
+impl From<Query> for QueryString {
+	fn from(query: Query) -> Self {
+		Self { query }
+	}
+}
+
+impl From<QueryString> for Query {
+	fn from(query_string: QueryString) -> Self {
+		query_string.query
+	}
+}
+
+
+#[component]
+pub fn QueryUI(
+	#[prop(into)] on_search: Callback<Query>,
+	pending: Signal<bool>,
+	#[prop(default=None)] default: Option<Query>,
+	///What kind of rows get searched
+	#[prop(optional)] target: Target,
+	///Replaces the sort of `default`, so it can be shared with e.g. an [ObjectTable](crate::table::ObjectTable)
+	#[prop(default=None)] sort: Option<RwSignal<Vec<SortKey>>>,
+) -> impl IntoView {
+	let again = RwSignal::new(false);
+	
+	let button_name = move || {
+		if pending.get() {
+			"searching...".to_owned()
+		} else {
+			format!("search{}", if again.get() {" again"} else {""} )
+		}
+	};
+	
+	let mut client_query = default.map(ClientQuery::from).unwrap_or_default();
+	if let Some(sort) = sort {
+		client_query.sort = sort;
+	}
+	let filter = client_query.get_filter_signal();
+	let sort = client_query.get_sort_signal();
+	
+	let sort_description = move || sort.with(|keys| {
+		keys.iter()
+			.map(|key| format!("{} {}", key.field, key.direction.arrow()))
+			.collect::<Vec<_>>()
+			.join(", ")
+	});
+	
+	let filter_ui = move |filters| {
+		match filter.get() {
+			Some(filter_sig) => {
+				let (get, set) = filter_sig.split();
+				view! {
+					<FilterUI get=get.into() set=set.into() filters target sub_id="" />
+				}.into_view()
+			},
+			None => ().into_view(),
+		}
+	};
+	
+	view! {
+		<div class="search">
+			<div class="search_parameters">
+				<div class="search_parameter">
+					<utils::AwaitOk future=get_filters let:filters>
+						<utils::CloneSignal base=filters let:filters_signal>
+							<label for="filter_enable">filter</label>
+							<input type="checkbox" id="filter_enable" prop:checked=filter.get().is_some() on:input=move |event| {
+								if event_target_checked(&event) {
+									let filters = filters_signal.get();
+									let default = filters.get(target).iter()
+										.find_map(|description| ClientFilter::from_description(description, &filters, target))
+										.expect("the server should have at least 1 filter that can be created");
+									filter.set(Some(RwSignal::new(default)));
+								} else {
+									filter.set(None);
+								}
+							}/>
+							{ move || filter_ui(filters_signal.get()) }
+						</utils::CloneSignal>
+					</utils::AwaitOk>
+				</div>
+				{move || (!sort.with(Vec::is_empty)).then(|| view! {
+					<div class="search_parameter">
+						<span>sort</span>
+						<span>{sort_description}</span>
+						<button on:click=move |_event| sort.set(Vec::new())>clear</button>
+					</div>
+				})}
+			</div>
+			
+			<button
+				disabled=pending
+				on:click = move |_event| {
+					again.set(true);
+					on_search.call(client_query.clone().into());
+				}
+			>
+				{button_name}
+			</button>
+		</div>
+	}
+}
+
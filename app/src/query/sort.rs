@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature="ssr")]
+use sea_orm::{EntityTrait, Order, QueryOrder, Select};
+
+
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Serialize, Deserialize)]
+pub enum Direction {
+	#[default]
+	Ascending,
+	Descending,
+}
+
+impl Direction {
+	pub fn flip(self) -> Self {
+		match self {
+			Self::Ascending => Self::Descending,
+			Self::Descending => Self::Ascending,
+		}
+	}
+	
+	pub fn arrow(self) -> &'static str {
+		match self {
+			Self::Ascending => "▲",
+			Self::Descending => "▼",
+		}
+	}
+}
+
+#[cfg(feature="ssr")]
+impl From<Direction> for Order {
+	fn from(direction: Direction) -> Self {
+		match direction {
+			Direction::Ascending => Order::Asc,
+			Direction::Descending => Order::Desc,
+		}
+	}
+}
+
+
+///Sort on `field`, which is the name of a column
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct SortKey {
+	pub field: String,
+	#[serde(default)]
+	pub direction: Direction,
+}
+
+/**
+Makes `field` the first key of `keys`, the others come after it in the same order.
+If `field` already was the first key, its direction gets flipped instead.
+*/
+pub fn sort_by(keys: &[SortKey], field: &str) -> Vec<SortKey> {
+	let direction = match keys.first() {
+		Some(first) if first.field == field => first.direction.flip(),
+		_ => Direction::default(),
+	};
+	
+	let first = SortKey {
+		field: field.to_owned(),
+		direction,
+	};
+	let others = keys.iter()
+		.filter(|key| key.field != field)
+		.cloned();
+	std::iter::once(first).chain(others).collect()
+}
+
+
+#[derive(thiserror::Error, Debug)]
+#[error("Can not sort on unknown field \"{0}\"")]
+pub struct UnknownFieldError(String);
+
+///Orders `query` by `keys`, the first key being the most significant
+#[cfg(feature="ssr")]
+pub fn apply_sort<Entity: EntityTrait>(keys: &[SortKey], query: Select<Entity>) -> Result<Select<Entity>, UnknownFieldError> {
+	keys.iter().try_fold(query, |query, key| {
+		let column = key.field.parse::<Entity::Column>()
+			.map_err(|_| UnknownFieldError(key.field.clone()))?;
+		Ok(query.order_by(column, key.direction.into()))
+	})
+}
@@ -0,0 +1,80 @@
+use leptos::*;
+use leptos_meta::Title;
+use crate::table::ObjectTable;
+use crate::utils;
+
+
+///A row of the report, with the values already formatted for display
+#[derive(
+	Clone, Debug, PartialEq, Eq,
+	serde::Serialize, serde::Deserialize,
+	ff_macros::FieldList,
+	bevy_reflect::Reflect
+)]
+#[reflect(from_reflect = false)]
+pub struct FeedHealth {
+	pub name: String,
+	pub severity: String,
+	pub stale: bool,
+	pub consecutive_failures: i32,
+	pub last_success: Option<String>,
+	pub last_produced: Option<String>,
+	pub average_interval: Option<String>,
+	pub id: i32,
+}
+
+impl ff_object::Object for FeedHealth {
+	fn get_id(&self) -> i32 {
+		self.id
+	}
+	
+	//So rows link to the feed
+	fn get_object_name() -> &'static str where Self: Sized {
+		"feed"
+	}
+}
+
+#[cfg(feature="ssr")]
+impl From<acquire::report::FeedHealth> for FeedHealth {
+	fn from(health: acquire::report::FeedHealth) -> Self {
+		use entities::prelude::time_fields;
+		const DAY: f64 = 24.0 * 60.0 * 60.0;
+		Self {
+			name: health.feed_name,
+			severity: health.severity.to_string(),
+			stale: health.stale,
+			consecutive_failures: health.consecutive_failures.try_into().unwrap_or(i32::MAX),
+			last_success: health.last_success.map(|date_time| time_fields::PrimitiveDateTime(date_time).to_string()),
+			last_produced: health.last_produced.map(|date| time_fields::Date(date).to_string()),
+			average_interval: health.average_interval.map(|interval| format!("{:.1} days", interval.as_secs_f64() / DAY)),
+			id: health.feed_id,
+		}
+	}
+}
+
+
+///Every feed, worst first
+#[server]
+pub async fn health_report() -> Result<Vec<FeedHealth>, ServerFnError> {
+	use acquire::report::{self, ReportSettings};
+	
+	let conn = crate::extension!(sea_orm::DatabaseConnection);
+	let now = time::OffsetDateTime::now_utc();
+	let now = time::PrimitiveDateTime::new(now.date(), now.time());
+	
+	let report = report::feed_health(&conn, &ReportSettings::default(), now).await?;
+	Ok(report.into_iter().map(FeedHealth::from).collect())
+}
+
+#[component]
+pub fn Report() -> impl IntoView {
+	view! {
+		<Title text="Report" />
+		<main>
+			<h1>"Feed health"</h1>
+			<utils::AwaitOk future=health_report let:feeds>
+				<ObjectTable items = feeds />
+			</utils::AwaitOk>
+		</main>
+	}
+}
@@ -0,0 +1,203 @@
+use leptos::*;
+use leptos_router::{Route, ActionForm, Redirect, A};
+use entities::prelude::*;
+use crate::table::*;
+use crate::query::{Query, QueryString};
+use crate::utils;
+#[cfg(feature="ssr")]
+use sea_orm::*;
+use ff_object::page::{Page, PageRequest};
+#[cfg(feature="ssr")]
+use ff_object::{View, page::fetch_page};
+#[cfg(feature="ssr")]
+use ffilter::filter_list::FilterLists;
+
+use crate::entry::search::EntryOverview;
+use crate::entry::player::Source;
+
+
+#[component(transparent)]
+pub fn Routes() -> impl IntoView {
+	view! {
+		<Route path="/:id" view=SavedQueryContext>
+			<utils::RouteAlias to="entries" />
+			<Route path="about" view=SavedQueryInfo/>
+			<Route path="entries" view=Entries/>
+			<Route path="player" view=Player/>
+			<Route path="edit" view=Edit/>
+		</Route>
+	}
+}
+
+#[component]
+pub fn SavedQueryContext() -> impl IntoView {
+	view! {
+		<utils::ObjectContext getter=get_saved_query>
+			<Sidebar />
+		</utils::ObjectContext>
+	}
+}
+
+#[component]
+pub fn Sidebar() -> impl IntoView {
+	view! {
+		<nav class="sidebar">
+			<ul>
+				<li>
+					<A href="about">About</A>
+				</li>
+				<li>
+					<A href="entries">Entries</A>
+				</li>
+				<li>
+					<A href="player">Player</A>
+				</li>
+				<li>
+					<A href="edit">Edit</A>
+				</li>
+			</ul>
+		</nav>
+	}
+}
+
+
+#[server]
+pub async fn get_saved_query(id: i32) -> Result<saved_query::Model, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	saved_query::Entity::find_by_id(id)
+		.one(&conn)
+		.await?
+		.ok_or(
+			ServerFnError::ServerError("No such saved query".into())
+		)
+}
+
+///Names of the filters the saved query uses that the server doesn't know (anymore)
+#[server]
+pub async fn missing_filters(id: i32) -> Result<Vec<String>, ServerFnError> {
+	let filter_lists = crate::extension!(FilterLists);
+	let saved_query = get_saved_query(id).await?;
+	
+	let query = saved_query.query.parse::<QueryString>()?.query;
+	Ok(query.missing_filters::<entry::Entity>(&filter_lists))
+}
+
+#[server]
+pub async fn delete_saved_query(id: i32) -> Result<(), ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	saved_query::Entity::delete_by_id(id).exec(&conn).await?;
+	Ok(())
+}
+
+#[component]
+pub fn SavedQueryInfo() -> impl IntoView {
+	let saved_query = crate::model!(saved_query);
+	let delete = create_server_action::<DeleteSavedQuery>();
+	
+	use saved_query::Model as SavedQueryModel;
+	
+	view! {
+		<ObjectFieldValueList<SavedQueryModel> object=saved_query />
+		
+		<utils::AwaitOk future=move || missing_filters(saved_query.get().id) let:missing>
+			{
+				if missing.is_empty() {
+					().into_view()
+				} else {
+					view! {
+						<p>"Filters that no longer exist: " {missing.join(", ")}</p>
+					}.into_view()
+				}
+			}
+		</utils::AwaitOk>
+		
+		<ActionForm action=delete>
+			<input type="hidden" name="id" value=move || saved_query.get().id/>
+			<utils::FormSubmit button="delete" action=delete/>
+		</ActionForm>
+		<utils::FormResult action=delete let:_deleted>
+			<Redirect path="/saved_query" />
+		</utils::FormResult>
+	}.into()
+}
+
+
+#[server]
+pub async fn saved_query_entries(id: i32, page: PageRequest) -> Result<Page<EntryOverview>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let filter_lists = crate::extension!(FilterLists);
+	let saved_query = get_saved_query(id).await?;
+	
+	let query = super::load_query(&saved_query.query, &filter_lists)?
+		.apply(entry::Entity::find(), &filter_lists)?;
+	
+	Ok(fetch_page(EntryOverview::from_query(query), page, &conn).await?)
+}
+
+#[component]
+pub fn Entries() -> impl IntoView {
+	let saved_query = crate::model!(saved_query);
+	let request = RwSignal::new(PageRequest::default());
+	let entries = Resource::new(
+		move || (saved_query.get().id, request.get()),
+		|(id, page)| saved_query_entries(id, page)
+	);
+	
+	view! {
+		<utils::ResourceOk resource=entries let:page fallback=|| ()>
+			<crate::entry::search::Table entries=page.items paging=Some(Paging { request, info: page.info })/>
+		</utils::ResourceOk>
+	}.into()
+}
+
+#[component]
+pub fn Player() -> impl IntoView {
+	let saved_query = crate::model!(saved_query);
+	let source = Source::SavedQuery(saved_query.get_untracked().id);
+	
+	view! {
+		<crate::entry::player::Player source />
+	}.into()
+}
+
+
+#[server]
+// #[server(default)] because it otherwise errors when it only contains a None
+pub async fn update_saved_query(id: i32, name: String, #[server(default)] query: Query) -> Result<saved_query::Model, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let filter_lists = crate::extension!(FilterLists);
+	
+	super::check_query(&query, &filter_lists)?;
+	
+	let mut model = saved_query::ActiveModel::new();
+	model.id = Set(id);
+	model.name = Set(name);
+	model.query = Set(QueryString::from(query).to_string());
+	let updated = model.update(&conn).await?;
+	Ok(updated)
+}
+
+#[component]
+pub fn Edit() -> impl IntoView {
+	let saved_query = crate::model!(saved_query);
+	let update = create_server_action::<UpdateSavedQuery>();
+	
+	let current = saved_query.get_untracked();
+	//A query that doesn't parse anymore can still be replaced
+	let default = current.query.parse::<QueryString>().ok().map(Query::from);
+	
+	let on_save = move |(name, query)| {
+		update.dispatch(UpdateSavedQuery { id: current.id, name, query });
+	};
+	
+	view! {
+		<super::Editor on_save pending=update.pending().into() name=current.name.clone() default />
+		
+		<utils::FormResult action=update let:updated>
+			{
+				saved_query.set(updated);
+				"Saved"
+			}
+		</utils::FormResult>
+	}.into()
+}
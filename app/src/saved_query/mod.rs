@@ -0,0 +1,133 @@
+/*!
+Named [Query]s for entries, a.k.a. output feeds.
+
+They are stored serialized, so the filters they use can disappear from the server.
+That gets checked whenever they are saved or used.
+*/
+
+use leptos::*;
+use leptos_router::{Route, Outlet};
+use leptos_meta::Title;
+use ffilter::shared::Target;
+use crate::query::{Query, QueryUI};
+use crate::utils;
+use crate::table::Paging;
+use ff_object::page::PageRequest;
+#[cfg(feature="ssr")]
+use entities::prelude::*;
+#[cfg(feature="ssr")]
+use ffilter::{filter::Filter as ServerFilter, filter_list::FilterLists};
+#[cfg(feature="ssr")]
+use crate::query::QueryString;
+
+
+pub mod new;
+pub mod details;
+pub mod search;
+
+
+#[component(transparent)]
+pub fn Routes() -> impl IntoView {
+	view! {
+		<Route path="/saved_query" view=Outlet>
+			<Route path="" view= || view! {
+				<Title text="Saved queries" />
+				<main>
+					<Outlet/>
+				</main>
+			}>
+				<Route path="" view=search::Search />
+				<Route path="/new" view=new::SavedQueryCreator />
+			</Route>
+			<details::Routes />
+		</Route>
+	}
+}
+
+
+///Errors if `query` uses filters the server doesn't know (anymore)
+#[cfg(feature="ssr")]
+pub fn check_query(query: &Query, lists: &FilterLists) -> Result<(), ServerFnError> {
+	let missing = query.missing_filters::<entry::Entity>(lists);
+	if missing.is_empty() {
+		Ok(())
+	} else {
+		Err(ServerFnError::ServerError(format!("Query uses filters that no longer exist: {}", missing.join(", "))))
+	}
+}
+
+///Parses a query serialized by [QueryString], and checks it with [check_query]
+#[cfg(feature="ssr")]
+pub fn load_query(serialized: &str, lists: &FilterLists) -> Result<Query, ServerFnError> {
+	let query = serialized.parse::<QueryString>()?.query;
+	check_query(&query, lists)?;
+	Ok(query)
+}
+
+///The filter of the query of `saved_query`, if it has one
+#[cfg(feature="ssr")]
+pub fn saved_filter(saved_query: &saved_query::Model, lists: &FilterLists) -> Result<Option<Box<dyn ServerFilter<entry::Entity> + Send + Sync>>, ServerFnError> {
+	let query = load_query(&saved_query.query, lists)?;
+	let filter = query.into_filter()
+		.map(|filter| filter.into_filter::<entry::Entity>(lists))
+		.transpose()?;
+	Ok(filter)
+}
+
+
+/**
+Inputs for the name and query of a saved query.
+Searching shows a preview of the matching entries, saving uses the last searched query.
+*/
+#[component]
+pub fn Editor(
+	#[prop(into)] on_save: Callback<(String, Query)>,
+	pending: Signal<bool>,
+	#[prop(default=String::new())] name: String,
+	#[prop(default=None)] default: Option<Query>,
+) -> impl IntoView {
+	let name = RwSignal::new(name);
+	let query = RwSignal::new(default.clone().unwrap_or_default());
+	let sort = RwSignal::new(query.with_untracked(|query| query.get_sort().to_vec()));
+	//Clicking a table header should sort the preview, and get saved
+	let sorted_query = move || {
+		let mut query = query.get();
+		query.set_sort(sort.get());
+		query
+	};
+	
+	let request = RwSignal::new(PageRequest::default());
+	
+	let preview = Resource::new(
+		move || (sorted_query(), request.get()),
+		|(query, page)| crate::entry::search::search(query, page)
+	);
+	
+	view! {
+		<ul class="object_fieldvalue_list">
+			<li class="object_fieldvalue">
+				<label class="object_field" for="name_input"> name </label>
+				<input class="object_value" type="text" id="name_input" size=50
+					prop:value=name
+					on:input=move |event| name.set(event_target_value(&event))
+				/>
+			</li>
+		</ul>
+		
+		<QueryUI on_search=move |searched| {
+			request.set(PageRequest::default());
+			query.set(searched);
+		} pending=preview.loading() default target=Target::Entry sort=Some(sort)/>
+		
+		<button
+			disabled=pending
+			on:click=move |_event| on_save.call((name.get(), sorted_query()))
+		>
+			save searched query
+		</button>
+		
+		<utils::ResourceOk resource=preview let:page fallback=|| ()>
+			<crate::entry::search::Table entries=page.items sort=Some(sort) paging=Some(Paging { request, info: page.info })/>
+		</utils::ResourceOk>
+	}
+}
@@ -0,0 +1,44 @@
+use leptos::*;
+use leptos_router::A;
+use entities::prelude::*;
+use crate::query::Query;
+use crate::utils;
+#[cfg(feature="ssr")]
+use sea_orm::*;
+#[cfg(feature="ssr")]
+use ffilter::filter_list::FilterLists;
+#[cfg(feature="ssr")]
+use crate::query::QueryString;
+
+
+#[server]
+// #[server(default)] because it otherwise errors when it only contains a None
+pub async fn new_saved_query(name: String, #[server(default)] query: Query) -> Result<saved_query::Ref, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let filter_lists = crate::extension!(FilterLists);
+	
+	super::check_query(&query, &filter_lists)?;
+	
+	let mut new = saved_query::ActiveModel::new();
+	new.name = Set(name);
+	new.query = Set(QueryString::from(query).to_string());
+	let inserted = new.insert(&conn).await?;
+	Ok(inserted.id.into())
+}
+
+#[component]
+pub fn SavedQueryCreator() -> impl IntoView {
+	let new_saved_query = create_server_action::<NewSavedQuery>();
+	
+	let on_save = move |(name, query)| {
+		new_saved_query.dispatch(NewSavedQuery { name, query });
+	};
+	
+	view! {
+		<super::Editor on_save pending=new_saved_query.pending().into() />
+		
+		<utils::FormResult action=new_saved_query let:saved_query_ref>
+			<A href=format!("/saved_query/{}", saved_query_ref.id())>"Created: " {saved_query_ref.to_string()}</A>
+		</utils::FormResult>
+	}
+}
@@ -0,0 +1,32 @@
+use leptos::*;
+use leptos_router::A;
+use entities::prelude::*;
+use crate::table::*;
+use crate::utils;
+#[cfg(feature="ssr")]
+use sea_orm::*;
+
+
+#[server]
+pub async fn all_saved_queries() -> Result<Vec<saved_query::Model>, ServerFnError> {
+	let conn = crate::extension!(DatabaseConnection);
+	let saved_queries = saved_query::Entity::find().all(&conn).await?;
+	Ok(saved_queries)
+}
+
+#[component]
+pub fn Search() -> impl IntoView {
+	view! {
+		<utils::AwaitOk future=all_saved_queries let:saved_queries>
+			<Table saved_queries />
+		</utils::AwaitOk>
+		<A href="new">Create new saved query</A>
+	}
+}
+
+#[component]
+pub fn Table(#[prop(into)] saved_queries: MaybeSignal<Vec<saved_query::Model>>) -> impl IntoView {
+	view! {
+		<ObjectTable items = saved_queries />
+	}
+}
@@ -0,0 +1,197 @@
+use leptos::*;
+use leptos_meta::Title;
+use serde::{Deserialize, Serialize};
+use crate::utils;
+
+
+///Size of the buckets entries get counted in
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Serialize, Deserialize)]
+pub enum Period {
+	#[default]
+	Day,
+	Week,
+}
+
+///What to make separate charts for
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Serialize, Deserialize)]
+pub enum Grouping {
+	#[default]
+	All,
+	Feed,
+	Tag,
+}
+
+#[cfg(feature="ssr")]
+impl From<Period> for acquire::stats::Period {
+	fn from(period: Period) -> Self {
+		match period {
+			Period::Day => Self::Day,
+			Period::Week => Self::Week,
+		}
+	}
+}
+
+#[cfg(feature="ssr")]
+impl From<Grouping> for acquire::stats::Grouping {
+	fn from(grouping: Grouping) -> Self {
+		match grouping {
+			Grouping::All => Self::All,
+			Grouping::Feed => Self::Feed,
+			Grouping::Tag => Self::Tag,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct Bucket {
+	pub label: String,
+	pub produced: u64,
+	pub consumed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct Series {
+	pub name: String,
+	pub buckets: Vec<Bucket>,
+}
+
+#[cfg(feature="ssr")]
+impl From<acquire::stats::Series> for Series {
+	fn from(series: acquire::stats::Series) -> Self {
+		use entities::prelude::time_fields;
+		Self {
+			name: series.name,
+			buckets: series.buckets.into_iter()
+				.map(|bucket| Bucket {
+					label: time_fields::Date(bucket.start).to_string(),
+					produced: bucket.produced,
+					consumed: bucket.consumed,
+				})
+				.collect(),
+		}
+	}
+}
+
+const MAX_DAYS: u32 = 10 * 366;
+
+///Produced and consumed entries of the last `days` days, up to and including today (UTC)
+#[server]
+pub async fn consumption_stats(period: Period, grouping: Grouping, days: u32) -> Result<Vec<Series>, ServerFnError> {
+	let conn = crate::extension!(sea_orm::DatabaseConnection);
+	
+	let to = time::OffsetDateTime::now_utc().date();
+	let from = to - time::Duration::days(days.clamp(1, MAX_DAYS).into()) + time::Duration::DAY;
+	
+	let series = acquire::stats::consumption(&conn, period.into(), grouping.into(), from, to).await?;
+	Ok(series.into_iter().map(Series::from).collect())
+}
+
+
+#[component]
+pub fn Stats() -> impl IntoView {
+	let period = RwSignal::new(Period::default());
+	let grouping = RwSignal::new(Grouping::default());
+	let days = RwSignal::new(30_u32);
+	
+	let stats = Resource::new(
+		move || (period.get(), grouping.get(), days.get()),
+		|(period, grouping, days)| consumption_stats(period, grouping, days)
+	);
+	
+	view! {
+		<Title text="Stats" />
+		<main>
+			<div class="search_parameters">
+				<div class="search_parameter">
+					<label for="stats_period">per</label>
+					<select id="stats_period" on:change=move |event| {
+						period.set(match event_target_value(&event).as_str() {
+							"week" => Period::Week,
+							_ => Period::Day,
+						});
+					}>
+						<option value="day" selected=move || period.get() == Period::Day>day</option>
+						<option value="week" selected=move || period.get() == Period::Week>week</option>
+					</select>
+				</div>
+				<div class="search_parameter">
+					<label for="stats_grouping">for</label>
+					<select id="stats_grouping" on:change=move |event| {
+						grouping.set(match event_target_value(&event).as_str() {
+							"feed" => Grouping::Feed,
+							"tag" => Grouping::Tag,
+							_ => Grouping::All,
+						});
+					}>
+						<option value="all" selected=move || grouping.get() == Grouping::All>all feeds</option>
+						<option value="feed" selected=move || grouping.get() == Grouping::Feed>each feed</option>
+						<option value="tag" selected=move || grouping.get() == Grouping::Tag>each tag</option>
+					</select>
+				</div>
+				<div class="search_parameter">
+					<label for="stats_days">days</label>
+					<input type="number" id="stats_days" min=1 max=MAX_DAYS prop:value=move || days.get() on:change=move |event| {
+						if let Ok(amount) = event_target_value(&event).parse() {
+							days.set(amount);
+						}
+					}/>
+				</div>
+			</div>
+			
+			<p class="chart_legend">
+				<span class="produced">produced</span>
+				" / "
+				<span class="consumed">consumed</span>
+			</p>
+			
+			<utils::ResourceOk resource=stats let:all_series fallback=|| ()>
+				{
+					all_series.into_iter()
+						.map(|series| view! { <Chart series/> })
+						.collect_view()
+				}
+			</utils::ResourceOk>
+		</main>
+	}
+}
+
+const BAR_WIDTH: u64 = 10;
+const BAR_GAP: u64 = 4;
+const CHART_HEIGHT: u64 = 100;
+
+///Bar chart with a produced and a consumed bar for each bucket
+#[component]
+pub fn Chart(series: Series) -> impl IntoView {
+	let max = series.buckets.iter()
+		.map(|bucket| bucket.produced.max(bucket.consumed))
+		.max()
+		.unwrap_or_default()
+		.max(1);
+	let height = move |amount: u64| amount * CHART_HEIGHT / max;
+	let width = (series.buckets.len() as u64 * (2 * BAR_WIDTH + BAR_GAP)).max(1);
+	
+	let produced = series.buckets.iter().map(|bucket| bucket.produced).sum::<u64>();
+	let consumed = series.buckets.iter().map(|bucket| bucket.consumed).sum::<u64>();
+	
+	let bars = series.buckets.into_iter().enumerate().map(|(index, bucket)| {
+		let x = index as u64 * (2 * BAR_WIDTH + BAR_GAP);
+		view! {
+			<rect class="produced" x=x y=CHART_HEIGHT - height(bucket.produced) width=BAR_WIDTH height=height(bucket.produced)>
+				<title>{format!("{}: {} produced", bucket.label, bucket.produced)}</title>
+			</rect>
+			<rect class="consumed" x=x + BAR_WIDTH y=CHART_HEIGHT - height(bucket.consumed) width=BAR_WIDTH height=height(bucket.consumed)>
+				<title>{format!("{}: {} consumed", bucket.label, bucket.consumed)}</title>
+			</rect>
+		}
+	}).collect_view();
+	
+	view! {
+		<div class="chart">
+			<h2>{series.name}</h2>
+			<p>{format!("{produced} produced, {consumed} consumed")}</p>
+			<svg viewBox=format!("0 0 {width} {CHART_HEIGHT}") preserveAspectRatio="none">
+				{bars}
+			</svg>
+		</div>
+	}
+}
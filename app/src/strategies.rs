@@ -1,7 +1,15 @@
 use leptos::*;
 use leptos_meta::Title;
+use serde::{Deserialize, Serialize};
 use crate::utils;
 
+///A strategy with its configuration as RON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrategyConfig {
+	pub name: String,
+	pub config: String,
+}
+
 #[server]
 pub async fn get_strategies() -> Result<Vec<String>, ServerFnError> {	
 	let strats = crate::extension!(acquire::strategy_list::StrategyList);
@@ -9,20 +17,98 @@ pub async fn get_strategies() -> Result<Vec<String>, ServerFnError> {
 	Ok(list)
 }
 
+#[server]
+pub async fn get_strategy_configs() -> Result<Vec<StrategyConfig>, ServerFnError> {
+	let strats = crate::extension!(acquire::strategy_list::StrategyList);
+	let list = strats.iter_strats()
+		.map(|strat| Ok(StrategyConfig {
+			name: strat.name().to_owned(),
+			config: acquire::strategy_config::to_ron(strat.as_ref())?,
+		}))
+		.collect::<Result<Vec<_>, acquire::strategy_config::ConfigError>>()?;
+	Ok(list)
+}
+
+///Returns the configuration the way it would get saved
+#[server]
+pub async fn check_strategy_config(name: String, config: String) -> Result<String, ServerFnError> {
+	use acquire::strategy_config::*;
+	let strats = crate::extension!(acquire::StrategyList);
+	
+	let strat = strats.get_by_name(&name)?;
+	let configured = from_ron(strat.as_ref(), &config)?;
+	Ok(to_ron(configured.as_ref())?)
+}
+
+///Writes the configuration file and swaps out the running strategy, returns the saved configuration
+#[server]
+pub async fn save_strategy_config(name: String, config: String) -> Result<String, ServerFnError> {
+	use acquire::strategy_config::*;
+	let strats = crate::extension!(acquire::StrategyList);
+	let folder = crate::extension!(ConfigFolder);
+	
+	let strat = strats.get_by_name(&name)?;
+	let configured = from_ron(strat.as_ref(), &config)?;
+	folder.save(configured.as_ref())?;
+	let saved = to_ron(configured.as_ref())?;
+	strats.replace(configured)?;
+	
+	tracing::info!(name, "Applied new strategy configuration");
+	Ok(saved)
+}
+
 #[component]
 pub fn Strategies() -> impl IntoView {
 	view! {
 		<Title text="Strategies" />
 		<main>
-			<utils::AwaitOk future=get_strategies let:strats>
-				<ul>
-					{
-						strats.into_iter()
-							.map(|e| view! {<li>{e}</li>})
-							.collect::<Vec<_>>()
-					}
-				</ul>
+			<utils::AwaitOk future=get_strategy_configs let:strats>
+				{
+					strats.into_iter()
+						.map(|strat| view! { <ConfigEditor strat/> })
+						.collect_view()
+				}
 			</utils::AwaitOk>
 		</main>
 	}
-}
\ No newline at end of file
+}
+
+#[component]
+fn ConfigEditor(strat: StrategyConfig) -> impl IntoView {
+	let name = store_value(strat.name.clone());
+	let config = RwSignal::new(strat.config);
+	let check = create_server_action::<CheckStrategyConfig>();
+	let save = create_server_action::<SaveStrategyConfig>();
+	
+	let on_submit = move |event: ev::SubmitEvent| {
+		event.prevent_default();
+		save.dispatch(SaveStrategyConfig { name: name.get_value(), config: config.get_untracked() });
+	};
+	let on_check = move |_| {
+		check.dispatch(CheckStrategyConfig { name: name.get_value(), config: config.get_untracked() });
+	};
+	
+	view! {
+		<section class="strategy_config">
+			<h3>{strat.name}</h3>
+			<form on:submit=on_submit>
+				<textarea rows=10 cols=80 spellcheck="false" prop:value=move || config.get() on:input=move |event| {
+					config.set(event_target_value(&event));
+				}/>
+				<div>
+					<button type="button" on:click=on_check disabled=move || check.pending().get()>"check"</button>
+					<utils::FormSubmit button="save" action=save/>
+				</div>
+			</form>
+			<utils::FormResult action=check let:_checked>
+				"Valid"
+			</utils::FormResult>
+			<utils::FormResult action=save let:saved>
+				{
+					config.set(saved);
+					"Saved and applied"
+				}
+			</utils::FormResult>
+		</section>
+	}
+}
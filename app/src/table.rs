@@ -18,6 +18,8 @@ use bevy_reflect::prelude::*;
 
 use ff_object::fields::{FieldListable, Field};
 use entities::prelude as entities;
+use ff_object::page::{PageInfo, PageRequest};
+use crate::query::sort::{SortKey, sort_by};
 
 /**
 Turns a reflected value into a string to display
@@ -30,6 +32,7 @@ Turns a reflected value into a string to display
 - [`Option`]`<`[`i32`]`>`
 - [`bool`]
 - [`fetch::Status`](entities::fetch::Status)
+- [`batch::Status`](entities::batch::Status)
 
 */
 pub fn reflect_to_string(value: &dyn Reflect) -> String {
@@ -51,6 +54,8 @@ pub fn reflect_to_string(value: &dyn Reflect) -> String {
 		(if *boolean {"yes"} else {"no"}).to_owned()
 	} else if let Some(status) = value.downcast_ref::<entities::fetch::Status>() {
 		status.to_string()
+	} else if let Some(status) = value.downcast_ref::<entities::batch::Status>() {
+		status.to_string()
 	} else if let Some(date) = value.downcast_ref::<entities::time_fields::Date>() {
 		date.to_string()
 	} else if let Some(time) = value.downcast_ref::<entities::time_fields::Time>() {
@@ -59,6 +64,8 @@ pub fn reflect_to_string(value: &dyn Reflect) -> String {
 		date_time.to_string()
 	} else if let Some(option_time) = value.downcast_ref::<entities::time_fields::OptionTime>() {
 		option_time.to_string()
+	} else if let Some(option_date_time) = value.downcast_ref::<entities::time_fields::OptionDateTime>() {
+		option_date_time.to_string()
 	} else {
 		tracing::error!("Don't know how to display a {}", value.reflect_type_path());
 		"🤷".to_owned()
@@ -74,6 +81,8 @@ Displays a reflected value.
 See [`reflect_to_string()`](reflect_to_string) for supported values
 
 */
+//Lifetime is needed for the generated props struct
+#[allow(clippy::needless_lifetimes)]
 #[component]
 pub fn Reflected<'a>(value: &'a dyn Reflect, #[prop(default = false)] short: bool) -> impl IntoView {
 	let reflected = reflect_to_string(value);
@@ -115,12 +124,22 @@ pub fn Reflected<'a>(value: &'a dyn Reflect, #[prop(default = false)] short: boo
 pub fn ObjectFieldList<Object: FieldListable<dyn Reflect>>(
 	#[prop(optional)] _ignore: PhantomData<Object>,
 	#[prop(optional)] adds: ObjectValueAdds<Object>,
+	///Makes the fields clickable to sort on them, see [sort_by]
+	#[prop(default=None)] sort: Option<RwSignal<Vec<SortKey>>>,
 ) -> impl IntoView {
 	view! {
 		<li class="object_field_list">
 			{
 				Object::iter_fields().map(|field| {
-					view! {<span class="object_field">{field.name()}</span>}
+					let name = field.name().to_owned();
+					match sort {
+						Some(sort) => view! {
+							<SortableField name sort/>
+						}.into_view(),
+						None => view! {
+							<span class="object_field">{name}</span>
+						}.into_view(),
+					}
 				}).collect::<Vec<_>>()
 			}
 			{
@@ -132,6 +151,36 @@ pub fn ObjectFieldList<Object: FieldListable<dyn Reflect>>(
 	}
 }
 
+///Field name that sorts on the field when clicked, with an arrow (and its rank if there are multiple keys) if it's sorted on
+#[component]
+fn SortableField(name: String, sort: RwSignal<Vec<SortKey>>) -> impl IntoView {
+	let indicator = {
+		let name = name.clone();
+		move || sort.with(|keys| {
+			let position = keys.iter().position(|key| key.field == name)?;
+			let arrow = keys[position].direction.arrow();
+			Some(if keys.len() > 1 {
+				format!(" {arrow}{}", position + 1)
+			} else {
+				format!(" {arrow}")
+			})
+		})
+	};
+	let on_click = {
+		let name = name.clone();
+		move |_event| sort.update(|keys| *keys = sort_by(keys, &name))
+	};
+	
+	view! {
+		<span class="object_field sortable" on:click=on_click>
+			{name}
+			{indicator}
+		</span>
+	}
+}
+
+//Lifetime is needed for the generated props struct
+#[allow(clippy::needless_lifetimes)]
 #[component]
 pub fn ObjectValues<Object: FieldListable<dyn Reflect>, 'object>(object: &'object Object) -> impl IntoView {
 	object.iter_values().map(|field| {
@@ -244,11 +293,53 @@ pub fn ObjectLinkValues<Object: FieldListable<dyn Reflect> + Clone + ff_object::
 pub fn ObjectTable<Object: FieldListable<dyn Reflect> + Clone + ff_object::Object + 'static>(
 	#[prop(into)] items: MaybeSignal<Vec<Object>>,
 	#[prop(optional)] adds: ObjectValueAdds<Object>,
+	///Makes the column headers sort on their field when clicked, sorting happens elsewhere
+	#[prop(default=None)] sort: Option<RwSignal<Vec<SortKey>>>,
+	///Adds a [Pager] under the table
+	#[prop(default=None)] paging: Option<Paging>,
 ) -> impl IntoView {
 	view! {
 		<ul class="object_list object_table">
-			<ObjectFieldList<Object> adds=adds.clone() />
+			<ObjectFieldList<Object> adds=adds.clone() sort />
 			<ObjectLinkValues items adds />
 		</ul>
+		{paging.map(|paging| view! { <Pager paging/> })}
+	}
+}
+
+///The page of a listing that's being shown, and a signal to request another one
+#[derive(Debug, Clone,Copy, PartialEq,Eq)]
+pub struct Paging {
+	pub request: RwSignal<PageRequest>,
+	pub info: PageInfo,
+}
+
+///Buttons to go to the previous/next page
+#[component]
+pub fn Pager(paging: Paging) -> impl IntoView {
+	let Paging { request, info } = paging;
+	let description = format!(
+		"page {} of {} ({} items)",
+		info.number + 1,
+		info.total_pages.max(1),
+		info.total_items,
+	);
+	
+	view! {
+		<div class="pager">
+			<button
+				disabled=info.is_first()
+				on:click=move |_event| request.update(|request| *request = request.previous())
+			>
+				previous
+			</button>
+			<span>{description}</span>
+			<button
+				disabled=info.is_last()
+				on:click=move |_event| request.update(|request| *request = request.next())
+			>
+				next
+			</button>
+		</div>
 	}
 }
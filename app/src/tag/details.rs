@@ -6,6 +6,7 @@ use crate::utils;
 #[cfg(feature="ssr")]
 use sea_orm::*;
 
+use crate::entry::player::Source;
 // use crate::entry::search::EntryOverview;
 
 
@@ -15,6 +16,7 @@ pub fn Routes() -> impl IntoView {
 		<Route path="/:id" view=TagContext>
 			<utils::RouteAlias to="about" />
 			<Route path="about" view=TagInfo/>
+			<Route path="player" view=Player/>
 			// <Route path="entries" view=Entries/>
 		</Route>
 	}
@@ -37,6 +39,9 @@ pub fn Sidebar() -> impl IntoView {
 				<li>
 					<A href="about">About</A>
 				</li>
+				<li>
+					<A href="player">Player</A>
+				</li>
 				// <li>
 				// 	<A href="entries">Entries</A>
 				// </li>
@@ -68,6 +73,16 @@ pub fn TagInfo() -> impl IntoView {
 	}.into()
 }
 
+#[component]
+pub fn Player() -> impl IntoView {
+	let tag = crate::model!(tag);
+	let source = Source::Tag(tag.get_untracked().id);
+	
+	view! {
+		<crate::entry::player::Player source />
+	}.into()
+}
+
 
 // #[server]
 // pub async fn get_entries(feed_id: i32) -> Result<Vec<EntryOverview>, ServerFnError> {
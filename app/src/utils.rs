@@ -380,6 +380,8 @@ let entry = model!(entry);
 macro_rules! model {
 	($entity:ident) => {
 		{
+			//Using ? instead breaks type inference for the function calling this macro
+			#[allow(clippy::question_mark)]
 			let Some(model) = ::leptos::use_context::<::leptos::RwSignal<$entity::Model>>() else {
 				return None;
 			};
@@ -408,6 +410,8 @@ let object = object!(CustomType);
 macro_rules! object {
 	($object:ty) => {
 		{
+			//Using ? instead breaks type inference for the function calling this macro
+			#[allow(clippy::question_mark)]
 			let Some(model) = ::leptos::use_context::<::leptos::RwSignal<$object>>() else {
 				return None;
 			};
@@ -3,4 +3,6 @@ pub mod entry;
 pub mod feed;
 pub mod fetch_entry;
 pub mod tag;
-pub mod feed_tag;
\ No newline at end of file
+pub mod feed_tag;
+pub mod batch;
+pub mod saved_query;
\ No newline at end of file
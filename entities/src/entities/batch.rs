@@ -0,0 +1,79 @@
+#[cfg(feature = "orm")]
+use sea_orm::entity::prelude::*;
+use ff_macros::{Object, FieldList};
+use serde::{Deserialize, Serialize};
+
+use crate::time_fields as time;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, bevy_reflect::Reflect)]
+#[reflect(from_reflect = false)]
+#[cfg_attr(feature="orm", derive(EnumIter, DeriveActiveEnum) )]
+#[cfg_attr(feature="orm", sea_orm(rs_type = "String", db_type = "String(Some(20))") )]
+pub enum Status {
+	#[cfg_attr(feature="orm", sea_orm(string_value = "RUNNING") )]
+	Running,
+	#[cfg_attr(feature="orm", sea_orm(string_value = "FINISHED") )]
+	Finished,
+	#[cfg_attr(feature="orm", sea_orm(string_value = "CANCELLED") )]
+	Cancelled,
+	///The server stopped while it was still running
+	#[cfg_attr(feature="orm", sea_orm(string_value = "INTERRUPTED") )]
+	Interrupted,
+}
+
+impl Status {
+	pub fn is_running(&self) -> bool {
+		matches!(self, Self::Running)
+	}
+}
+
+impl std::fmt::Display for Status {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let str = match self {
+			Self::Running => "⏳ Running",
+			Self::Finished => "✅ Finished",
+			Self::Cancelled => "❌ Cancelled",
+			Self::Interrupted => "❌ Interrupted",
+		};
+		write!(f, "{str}")
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object, FieldList, bevy_reflect::Reflect)]
+#[reflect(from_reflect = false)]
+#[cfg_attr(feature="orm", derive(DeriveEntityModel) )]
+#[cfg_attr(feature="orm", sea_orm(table_name = "batch") )]
+pub struct Model {
+	pub status: Status,
+	///Amount of feeds that were queued to be fetched
+	pub total: i32,
+	#[cfg_attr(feature="orm", sea_orm(primary_key) )]
+	pub id: i32,
+	pub created_at: time::PrimitiveDateTime,
+	pub updated_at: time::PrimitiveDateTime,
+}
+
+
+pub type Ref = ff_object::ObjRef<Model>;
+
+
+cfg_if::cfg_if! { if #[cfg(feature = "orm")] {
+
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(has_many = "super::fetch::Entity")]
+	Fetch,
+}
+
+impl Related<super::fetch::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Fetch.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+
+}}
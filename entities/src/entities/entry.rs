@@ -17,7 +17,11 @@ pub struct Model {
 	///Just the content
 	pub embed_url: Option<String>,
 	pub viewed: bool,
+	///When it was last marked as viewed
+	pub viewed_at: time::OptionDateTime,
 	pub feed_entry_id: String,
+	///Shared by entries for the same content, even when they're from different feeds
+	pub canonical_id: Option<String>,
 	pub feed_id: i32,
 	pub produced_date: time::Date,
 	pub produced_time: time::OptionTime,
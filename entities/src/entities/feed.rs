@@ -14,6 +14,10 @@ pub struct Model {
 	pub url: String,
 	pub name: String,
 	pub strategy: String,
+	///Minutes between scheduled fetches, uses the configured default if missing. 0 or less disables scheduled fetching.
+	pub refresh_interval: Option<i32>,
+	///Overrides for the configuration of the strategy, as a JSON object
+	pub strategy_options: Option<String>,
 	#[cfg_attr(feature="orm", sea_orm(primary_key) )]
 	pub id: i32,
 	pub created_at: time::PrimitiveDateTime,
@@ -19,6 +19,12 @@ pub enum Status {
 	ParseError,
 	#[cfg_attr(feature="orm", sea_orm(string_value = "ENTRY_UPDATE_ERROR") )]
 	EntryUpdateError,
+	///The source reported nothing changed since the previous fetch
+	#[cfg_attr(feature="orm", sea_orm(string_value = "NOT_MODIFIED") )]
+	NotModified,
+	///Fetching or parsing took longer than allowed
+	#[cfg_attr(feature="orm", sea_orm(string_value = "TIMEOUT") )]
+	Timeout,
 }
 
 impl std::fmt::Display for Status {
@@ -28,6 +34,8 @@ impl std::fmt::Display for Status {
 			Self::FetchError => "❌ Error Fetching",
 			Self::ParseError => "❌ Error Parsing",
 			Self::EntryUpdateError => "❌ Error Updating Entries",
+			Self::NotModified => "✅ Not Modified",
+			Self::Timeout => "❌ Timed Out",
 		};
 		write!(f, "{str}")
 	}
@@ -43,8 +51,14 @@ pub struct Model {
 	pub content: Option<String>,
 	pub error: Option<String>,
 	pub log: String,
+	///ETag header of the response, used for conditional fetching
+	pub etag: Option<String>,
+	///Last-Modified header of the response, used for conditional fetching
+	pub last_modified: Option<String>,
 	pub strategy: String,
 	pub feed_id: i32,
+	///The batch this fetch was a part of, if any
+	pub batch_id: Option<i32>,
 	#[cfg_attr(feature="orm", sea_orm(primary_key) )]
 	pub id: i32,
 	pub created_at: time::PrimitiveDateTime,
@@ -72,6 +86,14 @@ pub enum Relation {
 		on_delete = "NoAction"
 	)]
 	Feed,
+	#[sea_orm(
+		belongs_to = "super::batch::Entity",
+		from = "Column::BatchId",
+		to = "super::batch::Column::Id",
+		on_update = "NoAction",
+		on_delete = "NoAction"
+	)]
+	Batch,
 }
 
 impl Related<super::entry::Entity> for Entity {
@@ -89,6 +111,12 @@ impl Related<super::feed::Entity> for Entity {
 	}
 }
 
+impl Related<super::batch::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Batch.def()
+	}
+}
+
 impl Related<super::fetch_entry::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::FetchEntry.def()
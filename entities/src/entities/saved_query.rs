@@ -0,0 +1,37 @@
+#[cfg(feature = "orm")]
+use sea_orm::entity::prelude::*;
+use ff_macros::{Object, FieldList};
+use serde::{Deserialize, Serialize};
+
+use crate::time_fields as time;
+
+
+///A named search query for entries, a.k.a. an output feed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object, FieldList, bevy_reflect::Reflect)]
+#[reflect(from_reflect = false)]
+#[cfg_attr(feature="orm", derive(DeriveEntityModel) )]
+#[cfg_attr(feature="orm", sea_orm(table_name = "saved_query") )]
+pub struct Model {
+	pub name: String,
+	///The query, serialized as JSON
+	pub query: String,
+	#[cfg_attr(feature="orm", sea_orm(primary_key) )]
+	pub id: i32,
+	pub created_at: time::PrimitiveDateTime,
+	pub updated_at: time::PrimitiveDateTime,
+}
+
+
+pub type Ref = ff_object::ObjRef<Model>;
+
+
+cfg_if::cfg_if! { if #[cfg(feature = "orm")] {
+
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+
+}}
@@ -4,5 +4,7 @@ pub use crate::entities::fetch;
 pub use crate::entities::fetch_entry;
 pub use crate::entities::tag;
 pub use crate::entities::feed_tag;
+pub use crate::entities::batch;
+pub use crate::entities::saved_query;
 
 pub use crate::time_fields as time_fields;
\ No newline at end of file
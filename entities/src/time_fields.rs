@@ -85,4 +85,24 @@ impl Display for OptionTime {
 		};
 		write!(f, "{str}")
 	}
-}
\ No newline at end of file
+}
+
+#[derive(
+	Clone, Debug, Default, PartialEq, Eq,
+	From, Into,
+	Serialize, Deserialize,
+	Reflect
+)]
+#[reflect_value]
+#[cfg_attr(feature="orm", derive(DeriveValueType) )]
+pub struct OptionDateTime(pub Option<time::PrimitiveDateTime>);
+
+impl Display for OptionDateTime {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let str = match self.0 {
+			Some(date_time) => date_time.format(DATE_TIME_FORMAT).expect("format should be fine"),
+			None => "".to_string(),
+		};
+		write!(f, "{str}")
+	}
+}
@@ -0,0 +1,242 @@
+use proc_macro2::{TokenStream, Span};
+use quote::{quote, quote_spanned};
+use syn::{DeriveInput, Generics, Ident, Index, Member, Type, spanned::Spanned};
+use proc_macro_error::{
+	abort,
+	emit_error,
+};
+
+use attribute_derive::FromAttr;
+
+#[derive(FromAttr, Default)]
+#[attribute(ident = filter)]
+struct FilterAttributes {
+	///Defaults to the struct name in snake_case
+	name: Option<String>,
+	description: Option<String>,
+}
+
+#[derive(FromAttr, Default)]
+#[attribute(ident = argument)]
+struct ArgumentAttributes {
+	///Defaults to the field name
+	name: Option<String>,
+	description: Option<String>,
+}
+
+
+struct Argument {
+	member: Member,
+	ty: Type,
+	name: String,
+	description: Option<String>,
+	span: Span,
+}
+
+///How to construct the struct
+enum Shape {
+	Named,
+	Unnamed,
+	Unit,
+}
+
+
+pub struct BuildFilterDerive {
+	struct_name: Ident,
+	generics: Generics,
+	name: String,
+	description: Option<String>,
+	shape: Shape,
+	arguments: Vec<Argument>,
+}
+
+fn snake_case(name: &str) -> String {
+	let mut snake = String::new();
+	for (index, char) in name.chars().enumerate() {
+		if char.is_uppercase() {
+			if index != 0 {
+				snake.push('_');
+			}
+			snake.extend(char.to_lowercase());
+		} else {
+			snake.push(char);
+		}
+	}
+	snake
+}
+
+fn option_tokens(option: &Option<String>) -> TokenStream {
+	match option {
+		Some(string) => quote!{ ::core::option::Option::Some(#string) },
+		None => quote!{ ::core::option::Option::None },
+	}
+}
+
+fn option_string_tokens(option: &Option<String>) -> TokenStream {
+	match option {
+		Some(string) => quote!{ ::core::option::Option::Some(::std::borrow::ToOwned::to_owned(#string)) },
+		None => quote!{ ::core::option::Option::None },
+	}
+}
+
+impl From<DeriveInput> for BuildFilterDerive {
+	fn from(derive_input: DeriveInput) -> Self {
+		let DeriveInput {ident: struct_name, data, attrs, generics, ..} = derive_input;
+		
+		//Attribute
+		
+		let attrs = FilterAttributes::from_attributes(attrs).unwrap_or_else(|error| {
+			emit_error!(error.to_compile_error(), error);
+			FilterAttributes::default()
+		});
+		let name = attrs.name.unwrap_or_else(|| snake_case(&struct_name.to_string()));
+		
+		//Fields
+		
+		let struct_data = match data {
+			syn::Data::Struct(struct_data) => struct_data,
+			syn::Data::Enum(enum_data) => abort!(enum_data.enum_token, "enums aren't supported"),
+			syn::Data::Union(union_data) => abort!(union_data.union_token, "unions aren't supported"),
+		};
+		
+		let shape = match struct_data.fields {
+			syn::Fields::Named(_) => Shape::Named,
+			syn::Fields::Unnamed(_) => Shape::Unnamed,
+			syn::Fields::Unit => Shape::Unit,
+		};
+		
+		let arguments = struct_data.fields.into_iter()
+			.enumerate()
+			.map(|(index, field)| {
+				let span = field.span();
+				let attrs = ArgumentAttributes::from_attributes(field.attrs).unwrap_or_else(|error| {
+					emit_error!(error.to_compile_error(), error);
+					ArgumentAttributes::default()
+				});
+				let (member, default_name) = match field.ident {
+					Some(ident) => {
+						let name = ident.to_string();
+						(Member::Named(ident), name)
+					},
+					None => (Member::Unnamed(Index::from(index)), index.to_string()),
+				};
+				Argument {
+					member,
+					ty: field.ty,
+					name: attrs.name.unwrap_or(default_name),
+					description: attrs.description,
+					span,
+				}
+			})
+			.collect();
+		
+		Self {
+			struct_name,
+			generics,
+			name,
+			description: attrs.description,
+			shape,
+			arguments,
+		}
+	}
+}
+
+impl BuildFilterDerive {
+	pub fn generate(self) -> TokenStream {
+		let Self {
+			struct_name,
+			generics,
+			name,
+			description,
+			shape,
+			arguments,
+		} = self;
+		let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+		
+		let description = option_tokens(&description);
+		let count = arguments.len();
+		
+		let builds = arguments.iter().enumerate().map(|(index, argument)| {
+			let Argument {member, ty, span, ..} = argument;
+			//Use type span to put errors of the field not implementing ArgumentValue on the type
+			let value = quote_spanned! {ty.span()=>
+				::ffilter::filter::argument_from_data::<#ty>(
+					args.next().expect("the amount of arguments was checked"),
+					#index
+				)?
+			};
+			match member {
+				Member::Named(ident) => quote_spanned! {*span=> #ident: #value, },
+				Member::Unnamed(_) => quote_spanned! {*span=> #value, },
+			}
+		});
+		let construct = match shape {
+			Shape::Named => quote!{ Self { #(#builds)* } },
+			Shape::Unnamed => quote!{ Self( #(#builds)* ) },
+			Shape::Unit => quote!{ Self },
+		};
+		
+		let descriptions = arguments.iter().map(|argument| {
+			let Argument {ty, name, description, ..} = argument;
+			let description = option_string_tokens(description);
+			quote_spanned! {ty.span()=>
+				::ff_object::describe::Described::custom_new(
+					<#ty as ::ffilter::filter::ArgumentValue>::TYPE,
+					::std::borrow::ToOwned::to_owned(#name),
+					#description
+				),
+			}
+		});
+		
+		let into_arguments = arguments.iter().map(|argument| {
+			let Argument {member, ty, name, description, ..} = argument;
+			let description = option_string_tokens(description);
+			quote_spanned! {ty.span()=>
+				::ff_object::describe::Described::custom_new(
+					::ffilter::filter::ArgumentValue::into_data(self.#member),
+					::std::borrow::ToOwned::to_owned(#name),
+					#description
+				),
+			}
+		});
+		
+		quote! {
+			impl #impl_generics ::ff_object::describe::Describe for #struct_name #ty_generics #where_clause {
+				const NAME: &'static str = #name;
+				const DESCRIPTION: ::core::option::Option<&'static str> = #description;
+			}
+			
+			impl #impl_generics ::ffilter::filter::Build for #struct_name #ty_generics #where_clause {
+				fn build(
+					args: ::std::vec::Vec<::ffilter::filter::ArgumentData>,
+					_filters: &::ffilter::filter_list::FilterLists
+				) -> ::core::result::Result<Self, ::ffilter::filter::ArgumentError> {
+					::ffilter::filter::check_argument_count(&args, #count)?;
+					#[allow(unused_mut, unused_variables)]
+					let mut args = args.into_iter();
+					::core::result::Result::Ok(#construct)
+				}
+				
+				fn describe_args() -> ::std::vec::Vec<::ff_object::describe::Described<::ffilter::filter::ArgumentType>> {
+					::std::vec![ #(#descriptions)* ]
+				}
+			}
+			
+			impl #impl_generics ::ffilter::filter::ReprArgument for #struct_name #ty_generics #where_clause {
+				fn into_arguments(self) -> ::std::vec::Vec<::ffilter::filter::Argument> {
+					::std::vec![ #(#into_arguments)* ]
+				}
+				
+				fn replace_from_args(
+					&mut self,
+					args: ::std::vec::Vec<::ffilter::filter::Argument>,
+					filters: &::ffilter::filter_list::FilterLists
+				) -> ::core::result::Result<(), ::ffilter::filter::ArgumentError> {
+					let args = args.into_iter().map(|arg| arg.data).collect();
+					*self = <Self as ::ffilter::filter::Build>::build(args, filters)?;
+					::core::result::Result::Ok(())
+				}
+			}
+		}
+	}
+}
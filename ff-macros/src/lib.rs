@@ -7,6 +7,7 @@ use proc_macro_error::proc_macro_error;
 
 mod object;
 mod field_list;
+mod build_filter;
 
 
 /**
@@ -47,4 +48,33 @@ pub fn field_list_derive(input: TokenStream) -> TokenStream {
 	field_list::FieldListDerive::from(input)
 		.generate()
 		.into()
+}
+
+
+/**
+Generates impls for [`Describe`](../ff_object/describe/trait.Describe.html),
+and [`Build`](../ffilter/filter/trait.Build.html) and [`ReprArgument`](../ffilter/filter/trait.ReprArgument.html) from `ffilter`,
+with an argument for each field, in order.
+Field types need to implement [`ArgumentValue`](../ffilter/filter/trait.ArgumentValue.html).
+
+Attributes:
+
+`filter` on struct:
+-	`name`: defaults to the struct name in snake_case
+-	`description`
+
+`argument` on fields:
+-	`name`: defaults to the field name
+-	`description`
+
+See the tests for example usage.
+
+*/
+#[proc_macro_error]
+#[proc_macro_derive(BuildFilter, attributes(filter, argument))]
+pub fn build_filter_derive(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	build_filter::BuildFilterDerive::from(input)
+		.generate()
+		.into()
 }
\ No newline at end of file
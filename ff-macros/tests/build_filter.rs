@@ -0,0 +1,126 @@
+use ff_object::describe::{Describe, Described};
+use ffilter::{
+	filter::{ArgumentData, ArgumentError, ArgumentType, Build, ReprArgument, StrategyName},
+	filter_list::FilterLists,
+};
+
+const FAILURES_PATH: &str = "tests/build_filter_failures/*.rs";
+
+use ff_macros::BuildFilter;
+
+
+fn build<Filter: Build>(args: Vec<ArgumentData>) -> Result<Filter, ArgumentError> {
+	Filter::build(args, &FilterLists::new())
+}
+
+fn described_types<Filter: Build>() -> Vec<(String, ArgumentType)> {
+	Filter::describe_args().into_iter()
+		.map(|described| (described.name, described.data))
+		.collect()
+}
+
+#[test]
+fn works() {
+	#[derive(BuildFilter, Debug, Default, PartialEq)]
+	#[filter(name = "test", description = "A test")]
+	struct Test {
+		#[argument(description = "Text to find")]
+		text: String,
+		#[argument(name = "amount")]
+		count: i64,
+		strategy: StrategyName,
+	}
+	
+	assert_eq!(Test::NAME, "test");
+	assert_eq!(Test::DESCRIPTION, Some("A test"));
+	
+	assert_eq!(described_types::<Test>(), vec![
+		("text".to_owned(), ArgumentType::String),
+		("amount".to_owned(), ArgumentType::Integer),
+		("strategy".to_owned(), ArgumentType::Strategy),
+	]);
+	let descriptions = Test::describe_args().into_iter()
+		.map(|described| described.description)
+		.collect::<Vec<_>>();
+	assert_eq!(descriptions, vec![Some("Text to find".to_owned()), None, None]);
+	
+	let args = vec![
+		ArgumentData::String("hello".to_owned()),
+		ArgumentData::Integer(3),
+		ArgumentData::Strategy("rss".to_owned()),
+	];
+	let test = build::<Test>(args.clone()).expect("arguments should be right");
+	assert_eq!(test, Test {
+		text: "hello".to_owned(),
+		count: 3,
+		strategy: StrategyName("rss".to_owned()),
+	});
+	
+	let arguments = test.into_arguments();
+	assert_eq!(arguments[1], Described::custom_new(ArgumentData::Integer(3), "amount".to_owned(), None));
+	let datas = arguments.iter().map(|argument| argument.data.clone()).collect::<Vec<_>>();
+	assert_eq!(datas, args);
+	
+	let mut replaced = Test::default();
+	replaced.replace_from_args(arguments, &FilterLists::new()).expect("arguments should be right");
+	assert_eq!(replaced.count, 3);
+}
+
+#[test]
+fn wrong_arguments() {
+	#[derive(BuildFilter, Debug)]
+	struct Test {
+		_bool: bool,
+		_text: String,
+	}
+	
+	let too_few = build::<Test>(vec![ArgumentData::Bool(true)]);
+	assert!(matches!(too_few, Err(ArgumentError::WrongCount { expected: 2, found: 1 })));
+	
+	let wrong_type = build::<Test>(vec![ArgumentData::Bool(true), ArgumentData::Integer(1)]);
+	assert!(matches!(
+		wrong_type,
+		Err(ArgumentError::WrongType { index: 1, expected: ArgumentType::String, found: ArgumentData::Integer(1) })
+	));
+}
+
+#[test]
+fn default_name() {
+	#[derive(BuildFilter)]
+	struct NameContainsText;
+	
+	assert_eq!(NameContainsText::NAME, "name_contains_text");
+	assert_eq!(NameContainsText::DESCRIPTION, None);
+}
+
+#[test]
+fn unit() {
+	#[derive(BuildFilter, Debug)]
+	struct TestUnit;
+	
+	assert_eq!(0, TestUnit::describe_args().len());
+	assert!(build::<TestUnit>(Vec::new()).is_ok());
+	assert!(build::<TestUnit>(vec![ArgumentData::Bool(false)]).is_err());
+	assert_eq!(0, TestUnit.into_arguments().len());
+}
+
+#[test]
+fn tuple() {
+	#[derive(BuildFilter, Debug)]
+	struct TestTuple(bool, #[argument(name = "text")] String);
+	
+	assert_eq!(described_types::<TestTuple>(), vec![
+		("0".to_owned(), ArgumentType::Bool),
+		("text".to_owned(), ArgumentType::String),
+	]);
+	let test = build::<TestTuple>(vec![ArgumentData::Bool(true), ArgumentData::String("a".to_owned())])
+		.expect("arguments should be right");
+	assert!(test.0);
+	assert_eq!(test.1, "a");
+}
+
+#[test]
+fn failures() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail(FAILURES_PATH);
+}
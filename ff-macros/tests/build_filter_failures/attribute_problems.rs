@@ -0,0 +1,17 @@
+use ff_macros::BuildFilter;
+
+#[derive(BuildFilter)]
+#[filter(title = "wrong")]
+struct WrongFilterProperty;
+
+#[derive(BuildFilter)]
+struct WrongArgumentProperty {
+	#[argument(kind = "bool")]
+	field: bool,
+}
+
+#[derive(BuildFilter)]
+#[filter(name = 5)]
+struct WrongValue;
+
+fn main() {}
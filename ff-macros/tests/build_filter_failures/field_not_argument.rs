@@ -0,0 +1,11 @@
+use ff_macros::BuildFilter;
+
+struct NotAnArgument;
+
+#[derive(BuildFilter)]
+struct Test {
+	fine: bool,
+	not_argument: NotAnArgument,
+}
+
+fn main() {}
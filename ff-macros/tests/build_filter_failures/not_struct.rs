@@ -0,0 +1,9 @@
+use ff_macros::BuildFilter;
+
+#[derive(BuildFilter)]
+enum NotStruct {
+	A,
+	B,
+}
+
+fn main() {}
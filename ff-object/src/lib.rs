@@ -6,6 +6,9 @@ pub use object_ref::ObjRef;
 
 pub mod describe;
 
+///Splitting listings up into pages
+pub mod page;
+
 ///Traits for representing object behaviour
 pub mod traits;
 pub use traits::Object;
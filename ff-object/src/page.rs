@@ -0,0 +1,104 @@
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature="orm")]
+use sea_orm::{
+	ConnectionTrait,
+	DbErr,
+	PaginatorTrait,
+	SelectorTrait,
+};
+
+
+///Which page of a listing to get, pages start at 0
+#[derive(Debug, Clone,Copy, PartialEq,Eq, Hash, Serialize, Deserialize)]
+pub struct PageRequest {
+	pub number: u64,
+	pub size: u64,
+}
+
+impl PageRequest {
+	pub const DEFAULT_SIZE: u64 = 50;
+	pub const MAX_SIZE: u64 = 1000;
+	
+	pub fn next(self) -> Self {
+		Self {
+			number: self.number.saturating_add(1),
+			..self
+		}
+	}
+	
+	pub fn previous(self) -> Self {
+		Self {
+			number: self.number.saturating_sub(1),
+			..self
+		}
+	}
+}
+
+impl Default for PageRequest {
+	fn default() -> Self {
+		Self {
+			number: 0,
+			size: Self::DEFAULT_SIZE,
+		}
+	}
+}
+
+///Where a [Page] is in the listing
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Serialize, Deserialize)]
+pub struct PageInfo {
+	pub number: u64,
+	pub size: u64,
+	pub total_items: u64,
+	pub total_pages: u64,
+}
+
+impl PageInfo {
+	pub fn is_first(&self) -> bool {
+		self.number == 0
+	}
+	
+	pub fn is_last(&self) -> bool {
+		self.number + 1 >= self.total_pages
+	}
+}
+
+///Part of a listing, with info on the whole listing
+#[derive(Debug, Clone, PartialEq,Eq, Serialize, Deserialize)]
+pub struct Page<Item> {
+	pub items: Vec<Item>,
+	pub info: PageInfo,
+}
+
+
+/**
+Gets the page `request` asks for of `query`, e.g. a [`Select`](sea_orm::Select) or [`View::query`](crate::View::query).
+The page size gets clamped to [PageRequest::MAX_SIZE].
+The order of `query` should end with a unique column like the id, otherwise rows can show up on multiple pages or none.
+*/
+#[cfg(feature="orm")]
+pub async fn fetch_page<'db, Connection, Query>(
+	query: Query,
+	request: PageRequest,
+	db: &'db Connection,
+) -> Result<Page<<Query::Selector as SelectorTrait>::Item>, DbErr>
+where
+	Connection: ConnectionTrait,
+	Query: PaginatorTrait<'db, Connection>,
+{
+	let size = request.size.clamp(1, PageRequest::MAX_SIZE);
+	let paginator = query.paginate(db, size);
+	
+	let totals = paginator.num_items_and_pages().await?;
+	let items = paginator.fetch_page(request.number).await?;
+	
+	Ok(Page {
+		items,
+		info: PageInfo {
+			number: request.number,
+			size,
+			total_items: totals.number_of_items,
+			total_pages: totals.number_of_pages,
+		},
+	})
+}
@@ -43,6 +43,9 @@ use sea_orm::{
 	FromQueryResult,
 	EntityTrait,
 	ColumnTrait,
+	QueryOrder,
+	Iterable,
+	PrimaryKeyToColumn,
 };
 
 /**
@@ -63,9 +66,11 @@ pub trait View: FromQueryResult {
 	///Specifies which columns this struct selects
 	fn columns() -> impl Iterator<Item = impl ColumnTrait>;
 	
-	///Modifies `query` to be sorted according to a standard
+	///Modifies `query` to be sorted according to a standard, by the primary key by default.
+	///Should end with a unique column, so rows with equal sort keys don't move between pages
 	fn order(query: Select<Self::Entity>) -> Select<Self::Entity> {
-		query
+		<Self::Entity as EntityTrait>::PrimaryKey::iter()
+			.fold(query, |query, key| query.order_by_asc(key.into_column()))
 	}
 	
 	
@@ -42,12 +42,13 @@ impl QueryFilter
 */
 
 
-use sea_orm::Select;
+use sea_orm::{Select, EntityTrait, QuerySelect, QueryTrait, sea_query::SelectStatement};
 use entities::prelude::*;
 use ff_object::traits::DynSer;
 use ff_object::describe::*;
 
 pub use crate::shared::*;
+use crate::filter_list::{FilterList, FilterLists, NotFoundError};
 
 
 
@@ -55,27 +56,70 @@ pub type Argument = Described<ArgumentData>;
 pub type FilterData = Described<Vec<Argument>>;
 
 
-pub type BuildFilterFn = fn(Vec<ArgumentData>) -> Result<Box<dyn Filter>, ArgumentError>;
+///An entity that [Filter]s can filter
+pub trait FilterTarget: EntityTrait + Default {
+	const TARGET: Target;
+	
+	///Column with the primary key, see [matching_ids]
+	fn id_column() -> Self::Column;
+	///The list with the filters for this entity
+	fn get_list(lists: &FilterLists) -> &FilterList<Self>;
+	fn get_list_mut(lists: &mut FilterLists) -> &mut FilterList<Self>;
+}
 
+impl FilterTarget for feed::Entity {
+	const TARGET: Target = Target::Feed;
+	
+	fn id_column() -> Self::Column {
+		feed::Column::Id
+	}
+	fn get_list(lists: &FilterLists) -> &FilterList<Self> {
+		&lists.feed
+	}
+	fn get_list_mut(lists: &mut FilterLists) -> &mut FilterList<Self> {
+		&mut lists.feed
+	}
+}
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct FilterInfo {
+impl FilterTarget for entry::Entity {
+	const TARGET: Target = Target::Entry;
+	
+	fn id_column() -> Self::Column {
+		entry::Column::Id
+	}
+	fn get_list(lists: &FilterLists) -> &FilterList<Self> {
+		&lists.entry
+	}
+	fn get_list_mut(lists: &mut FilterLists) -> &mut FilterList<Self> {
+		&mut lists.entry
+	}
+}
+
+
+pub type BuildFilterFn<Target> = fn(Vec<ArgumentData>, &FilterLists) -> Result<Box<dyn Filter<Target> + Send + Sync>, ArgumentError>;
+
+
+#[derive(Debug, Clone)]
+pub struct FilterInfo<Target: FilterTarget = feed::Entity> {
 	description: Described<()>,
-	build_fn: BuildFilterFn,
+	build_fn: BuildFilterFn<Target>,
+	//Only got read by the derived PartialEq, which can't be derived anymore because of the fn pointer
+	#[allow(dead_code)]
 	args_description: Vec<Described<ArgumentType>>
 }
 
-impl FilterInfo {
-	pub fn new<FilterType: Build + Filter + Describe + 'static>() -> Self {
+impl<Target: FilterTarget> FilterInfo<Target> {
+	pub fn new<FilterType: Build + Filter<Target> + Describe + Send + Sync + 'static>() -> Self {
 		Self {
 			description: Described::new_with_describer::<FilterType>(()),
-			build_fn: box_dyn_filter::<FilterType>,
+			build_fn: box_dyn_filter::<FilterType, Target>,
 			args_description: FilterType::describe_args()
 		}
 	}
 	
-	pub fn build(&self, args: Vec<ArgumentData>) -> Result<Box<dyn Filter>, ArgumentError> {
-		(self.build_fn)(args)
+	///`filters` is used to build nested filters
+	pub fn build(&self, args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Box<dyn Filter<Target> + Send + Sync>, ArgumentError> {
+		(self.build_fn)(args, filters)
 	}
 	
 	pub fn get_name(&self) -> &str{
@@ -85,25 +129,25 @@ impl FilterInfo {
 
 
 
-pub type Builder = FilterInfo;
+pub type Builder<Target = feed::Entity> = FilterInfo<Target>;
 
-///Create Self from a [`Vec`]`<`[`ArgumentData`]`>`
+///Create Self from a [`Vec`]`<`[`ArgumentData`]`>`, with `filters` to build the [nested filters](ArgumentData::Filter) in it
 pub trait Build: Sized {
-	fn build(args: Vec<ArgumentData>) -> Result<Self, ArgumentError>;
+	fn build(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Self, ArgumentError>;
 	fn describe_args() -> Vec<Described<ArgumentType>>;
 }
 
-fn box_dyn_filter<T: Build + Filter + 'static>(args: Vec<ArgumentData>) -> Result<Box<dyn Filter>, ArgumentError> {
-	Ok(Box::new(T::build(args)?))
+fn box_dyn_filter<T: Build + Filter<Target> + Send + Sync + 'static, Target: FilterTarget>(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Box<dyn Filter<Target> + Send + Sync>, ArgumentError> {
+	Ok(Box::new(T::build(args, filters)?))
 }
 
 ///Get a [`Builder`] from a type that implements that required traits
-pub trait GetBuilder {
-	fn get_builder() -> Builder;
+pub trait GetBuilder<Target: FilterTarget = feed::Entity> {
+	fn get_builder() -> Builder<Target>;
 }
 
-impl<This: Build + Filter + Describe + 'static> GetBuilder for This {
-	fn get_builder() -> Builder {
+impl<This: Build + Filter<Target> + Describe + Send + Sync + 'static, Target: FilterTarget> GetBuilder<Target> for This {
+	fn get_builder() -> Builder<Target> {
 		FilterInfo::new::<This>()
 	}
 }
@@ -121,15 +165,98 @@ pub enum ArgumentError {
 	#[error("Wrong argument type at index {index}: expected {expected:?}, found {found:?}")]
 	WrongType {
 		index: usize,
-		expected: ArgumentData,
+		expected: ArgumentType,
 		found: ArgumentData,
+	},
+	#[error("Unknown nested filter: {0}")]
+	UnknownFilter(#[from] NotFoundError),
+}
+
+
+/**
+A type that can be an argument of a filter, which is how [`ff_macros::BuildFilter`](../../ff_macros/derive.BuildFilter.html) knows how to build fields.
+
+Filters whose arguments contain other filters need [FilterLists] to be built, so they can't use this.
+*/
+pub trait ArgumentValue: Sized {
+	const TYPE: ArgumentType;
+	
+	fn into_data(self) -> ArgumentData;
+	///Gives `data` back if it's for a different type
+	fn from_data(data: ArgumentData) -> Result<Self, ArgumentData>;
+}
+
+macro_rules! argument_value {
+	($value:ty, $variant:ident) => {
+		impl ArgumentValue for $value {
+			const TYPE: ArgumentType = ArgumentType::$variant;
+			
+			fn into_data(self) -> ArgumentData {
+				ArgumentData::$variant(self)
+			}
+			fn from_data(data: ArgumentData) -> Result<Self, ArgumentData> {
+				match data {
+					ArgumentData::$variant(value) => Ok(value),
+					other => Err(other),
+				}
+			}
+		}
+	};
+}
+
+argument_value!(bool, Bool);
+argument_value!(tag::Ref, Tag);
+argument_value!(String, String);
+argument_value!(i64, Integer);
+argument_value!(time_fields::Date, Date);
+argument_value!(std::time::Duration, Duration);
+argument_value!(feed::Ref, Feed);
+
+///The name of a strategy, since a [String] argument is just text
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct StrategyName(pub String);
+
+impl ArgumentValue for StrategyName {
+	const TYPE: ArgumentType = ArgumentType::Strategy;
+	
+	fn into_data(self) -> ArgumentData {
+		ArgumentData::Strategy(self.0)
+	}
+	fn from_data(data: ArgumentData) -> Result<Self, ArgumentData> {
+		match data {
+			ArgumentData::Strategy(name) => Ok(Self(name)),
+			other => Err(other),
+		}
+	}
+}
+
+///Checks there are `expected` arguments, for [Build] implementations
+pub fn check_argument_count(args: &[ArgumentData], expected: usize) -> Result<(), ArgumentError> {
+	if args.len() == expected {
+		Ok(())
+	} else {
+		Err(ArgumentError::WrongCount {
+			expected,
+			found: args.len(),
+		})
 	}
 }
 
+///Converts the argument at `index`, for [Build] implementations
+pub fn argument_from_data<Value: ArgumentValue>(data: ArgumentData, index: usize) -> Result<Value, ArgumentError> {
+	Value::from_data(data).map_err(|found| ArgumentError::WrongType {
+		index,
+		expected: Value::TYPE,
+		found,
+	})
+}
+
 
 pub trait ReprArgument {
 	fn into_arguments(self) -> Vec<Argument>;
-	fn replace_from_args(&mut self, args: Vec<Argument>) -> Result<(), ArgumentError>;
+	///`filters` is used to build the [nested filters](ArgumentData::Filter) in `args`
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError>;
 }
 
 
@@ -139,7 +266,7 @@ Trait objects can't be moved due to not having a static size, which is required
 */
 pub trait DynReprArgument {
 	fn into_arguments(self: Box<Self>) -> Vec<Argument>;
-	fn replace_from_args(&mut self, args: Vec<Argument>) -> Result<(), ArgumentError>;
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError>;
 }
 
 impl<T: ReprArgument> DynReprArgument for T {
@@ -147,23 +274,47 @@ impl<T: ReprArgument> DynReprArgument for T {
 		<Self as ReprArgument>::into_arguments(*self)
 	}
 	
-	fn replace_from_args(&mut self, args: Vec<Argument>) -> Result<(), ArgumentError> {
-		<Self as ReprArgument>::replace_from_args(self, args)
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError> {
+		<Self as ReprArgument>::replace_from_args(self, args, filters)
 	}
 }
 
-pub trait DynFilterClone {
-	fn box_clone(&self) -> Box<dyn Filter>;
+pub trait DynFilterClone<Target: FilterTarget> {
+	fn box_clone(&self) -> Box<dyn Filter<Target>>;
 }
 
-impl<T: Clone + Filter + 'static> DynFilterClone for T {
-	fn box_clone(&self) -> Box<dyn Filter> {
+impl<T: Clone + Filter<Target> + 'static, Target: FilterTarget> DynFilterClone<Target> for T {
+	fn box_clone(&self) -> Box<dyn Filter<Target>> {
 		Box::new(self.clone())
 	}
 }
 
-pub trait Filter: DynSer + DynDescribe + DynFilterClone + DynReprArgument {
-	fn filter(&self, query: Select<feed::Entity>) -> Select<feed::Entity>;
+///Filters rows of `Target`, which is a feed by default
+pub trait Filter<Target: FilterTarget = feed::Entity>: DynSer + DynDescribe + DynFilterClone<Target> + DynReprArgument {
+	fn filter(&self, query: Select<Target>) -> Select<Target>;
+}
+
+///The transportable form of `filter`, for putting it in [ArgumentData::Filter]
+pub fn to_nested<Target: FilterTarget>(filter: &dyn Filter<Target>) -> NestedFilter {
+	NestedFilter {
+		name: filter.get_name().to_owned(),
+		arguments: filter.box_clone().into_arguments().into_iter()
+			.map(|argument| argument.data)
+			.collect(),
+	}
+}
+
+/**
+A query for the ids of the rows `filter` lets through.
+
+Filters may join other tables, which can conflict when applying multiple filters to the same query,
+so filters combining other filters should use this with something like [`in_subquery`](sea_orm::ColumnTrait::in_subquery) instead.
+*/
+pub fn matching_ids<Target: FilterTarget>(filter: &dyn Filter<Target>) -> SelectStatement {
+	filter.filter(Target::find())
+		.select_only()
+		.column(Target::id_column())
+		.into_query()
 }
 
 
@@ -173,7 +324,7 @@ mod tests {
 	
 	#[test]
 	fn filter_dyn() {
-		let a = crate::filters::Fetched::default();
-		let b: &dyn Filter = &a;
+		let a = crate::filters::Fetched;
+		let _b: &dyn Filter = &a;
 	}
 }
\ No newline at end of file
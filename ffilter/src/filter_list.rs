@@ -1,5 +1,7 @@
 use std::sync::Arc;
-use crate::filter::{Filter, Builder, GetBuilder};
+use entities::prelude::*;
+use crate::filter::{Filter, FilterTarget, Builder, GetBuilder, ArgumentError, NestedFilter};
+use crate::shared::ArgumentData;
 
 
 
@@ -8,48 +10,109 @@ use crate::filter::{Filter, Builder, GetBuilder};
 pub struct NotFoundError(String);
 
 
+///The filters for one [FilterTarget]
 #[derive(Default, Clone)]
-pub struct FilterList {
-	list: Vec<Arc<dyn Filter + Send + Sync>>,
-	builder_list: Vec<Builder>,
+pub struct FilterList<Target: FilterTarget = feed::Entity> {
+	list: Vec<Arc<dyn Filter<Target> + Send + Sync>>,
+	builder_list: Vec<Builder<Target>>,
 }
 
-impl FilterList {
+impl<Target: FilterTarget> FilterList<Target> {
 	pub fn new() -> Self {
 		Self::default()
 	}
 	
-	pub fn add(&mut self, filter: impl Filter + Send + Sync + 'static) {
+	pub fn add(&mut self, filter: impl Filter<Target> + Send + Sync + 'static) {
 		self.list.push(Arc::new(filter));
 	}
-	pub fn add_from_container(&mut self, filter: impl Into<Arc<dyn Filter + Send + Sync>>) {
+	pub fn add_from_container(&mut self, filter: impl Into<Arc<dyn Filter<Target> + Send + Sync>>) {
 		self.list.push(filter.into());
 	}
 	
-	pub fn add_builder<Buildable: GetBuilder>(&mut self) {
+	pub fn add_builder<Buildable: GetBuilder<Target>>(&mut self) {
 		self.builder_list.push(Buildable::get_builder());
 	}
 	
-	pub fn get_builder_by_name(&self, name: &str) -> Result<&Builder, NotFoundError> {
+	pub fn get_builder_by_name(&self, name: &str) -> Result<&Builder<Target>, NotFoundError> {
 		self.builder_list.iter()
 			.find(|f| f.get_name()==name)
 			.ok_or_else(|| NotFoundError(name.to_owned()))
 	}
 	
-	pub fn get_by_name(&self, name: &str) -> Result<&Arc<dyn Filter + Send + Sync>, NotFoundError> {
+	pub fn get_by_name(&self, name: &str) -> Result<&Arc<dyn Filter<Target> + Send + Sync>, NotFoundError> {
 		self.list.iter()
 			.find(|f| f.get_name()==name)
 			.ok_or_else(|| NotFoundError(name.to_owned()))
 	}
 	
-	pub fn iter_filters(&self) -> impl Iterator<Item = &(dyn Filter + Send + Sync)> {
+	pub fn iter_filters(&self) -> impl Iterator<Item = &(dyn Filter<Target> + Send + Sync)> {
 		self.list.iter().map(|s| s.as_ref())
 	}
 }
 
-impl std::fmt::Debug for FilterList {
+impl<Target: FilterTarget> std::fmt::Debug for FilterList<Target> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let names = self.iter_filters().map(|f| f.get_name()).collect::<Vec<_>>();
 		f.debug_struct("FilterList").field("names", &names).finish()
 	}
-}
\ No newline at end of file
+}
+
+
+///A [FilterList] for each [FilterTarget], since filters can contain filters for other targets
+#[derive(Debug, Default, Clone)]
+pub struct FilterLists {
+	pub feed: FilterList<feed::Entity>,
+	pub entry: FilterList<entry::Entity>,
+}
+
+impl FilterLists {
+	pub fn new() -> Self {
+		Self::default()
+	}
+	
+	pub fn get<Target: FilterTarget>(&self) -> &FilterList<Target> {
+		Target::get_list(self)
+	}
+	
+	pub fn get_mut<Target: FilterTarget>(&mut self) -> &mut FilterList<Target> {
+		Target::get_list_mut(self)
+	}
+	
+	///Adds `filter` and its builder to the list of its target
+	pub fn add<Target: FilterTarget, FilterType: GetBuilder<Target> + Filter<Target> + Send + Sync + 'static>(&mut self, filter: FilterType) {
+		let list = self.get_mut::<Target>();
+		list.add(filter);
+		list.add_builder::<FilterType>();
+	}
+	
+	///Builds `filter` using the builder with the same name for `Target`
+	pub fn build<Target: FilterTarget>(&self, filter: NestedFilter) -> Result<Box<dyn Filter<Target> + Send + Sync>, ArgumentError> {
+		let builder = self.get::<Target>().get_builder_by_name(&filter.name)?;
+		builder.build(filter.arguments, self)
+	}
+	
+	///Names of the filters in `filter` (including nested ones) that don't exist (anymore), in order of appearance
+	pub fn missing_filters<Target: FilterTarget>(&self, filter: &NestedFilter) -> Vec<String> {
+		let mut missing = Vec::new();
+		self.collect_missing::<Target>(filter, &mut missing);
+		missing
+	}
+	
+	fn collect_missing<Target: FilterTarget>(&self, filter: &NestedFilter, missing: &mut Vec<String>) {
+		if self.get::<Target>().get_builder_by_name(&filter.name).is_err() {
+			missing.push(filter.name.clone());
+		}
+		for argument in &filter.arguments {
+			match argument {
+				ArgumentData::Filter(child) => self.collect_missing::<Target>(child, missing),
+				ArgumentData::Filters(children) => {
+					for child in children {
+						self.collect_missing::<Target>(child, missing);
+					}
+				},
+				ArgumentData::FeedFilter(child) => self.collect_missing::<feed::Entity>(child, missing),
+				_ => {},
+			}
+		}
+	}
+}
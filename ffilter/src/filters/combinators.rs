@@ -0,0 +1,270 @@
+use std::sync::Arc;
+use entities::prelude::*;
+use ff_object::{describe::{Describe, Described}, traits::DynSer};
+use sea_orm::{prelude::Select, ColumnTrait, Condition, QueryFilter};
+
+use crate::{
+	filter::{Argument, ArgumentData, ArgumentError, ArgumentType, Build, Filter, FilterTarget, NestedFilter, ReprArgument, to_nested, matching_ids},
+	filter_list::FilterLists,
+};
+
+/*
+Children get applied through subqueries, see [matching_ids] for why.
+They work for any FilterTarget, with children of the same target.
+
+These can't derive Serialize, since building the children requires a FilterList.
+They serialize like a NestedFilter, but can't be deserialized.
+*/
+
+
+type Child<Target> = Arc<dyn Filter<Target> + Send + Sync>;
+
+fn build_child<Target: FilterTarget>(nested: NestedFilter, filters: &FilterLists) -> Result<Child<Target>, ArgumentError> {
+	Ok(Arc::from(filters.build::<Target>(nested)?))
+}
+
+pub(crate) fn single_argument(args: Vec<ArgumentData>) -> Result<ArgumentData, ArgumentError> {
+	//Can't move-destructure something without a const size for some reason
+	match <[ArgumentData; 1]>::try_from(args) {
+		Ok([arg]) => Ok(arg),
+		Err(original) => Err(
+			ArgumentError::WrongCount {
+				expected: 1,
+				found: original.len()
+			}
+		),
+	}
+}
+
+fn children_from_args<Target: FilterTarget>(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Vec<Child<Target>>, ArgumentError> {
+	match single_argument(args)? {
+		ArgumentData::Filters(nested) => nested.into_iter()
+			.map(|nested| build_child(nested, filters))
+			.collect(),
+		other => Err(
+			ArgumentError::WrongType {
+				index: 0,
+				expected: ArgumentType::Filters,
+				found: other
+			}
+		),
+	}
+}
+
+fn children_into_arguments<Target: FilterTarget>(children: &[Child<Target>]) -> Vec<Argument> {
+	let nested = children.iter()
+		.map(|child| to_nested(child.as_ref()))
+		.collect();
+	vec![
+		Described::custom_new(
+			ArgumentData::Filters(nested),
+			"filters".to_owned(),
+			None
+		)
+	]
+}
+
+fn describe_children_arg() -> Vec<Described<ArgumentType>> {
+	vec![
+		Described::custom_new(
+			ArgumentType::Filters,
+			"filters".to_owned(),
+			None
+		)
+	]
+}
+
+pub(crate) fn serialize_nested<Target: FilterTarget>(filter: &dyn Filter<Target>, serializer: &mut dyn erased_serde::Serializer) -> Result<(), erased_serde::Error> {
+	DynSer::serialize(&to_nested(filter), serializer)
+}
+
+pub(crate) fn cannot_deserialize() -> erased_serde::Error {
+	<erased_serde::Error as serde::de::Error>::custom("filters containing other filters need FilterLists to be created")
+}
+
+
+///Lets through the feeds that pass all of its filters, or every feed if it has none
+#[derive(Clone, Default)]
+pub struct And<Target: FilterTarget = feed::Entity> {
+	filters: Vec<Child<Target>>,
+}
+
+impl<Target: FilterTarget> Filter<Target> for And<Target> {
+	fn filter(&self, query: Select<Target>) -> Select<Target> {
+		self.filters.iter().fold(query, |query, child| {
+			query.filter(Target::id_column().in_subquery(matching_ids(child.as_ref())))
+		})
+	}
+}
+
+impl<Target: FilterTarget> Describe for And<Target> {
+	const NAME: &'static str = "and";
+}
+
+impl<Target: FilterTarget> Build for And<Target> {
+	fn build(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Self, ArgumentError> {
+		Ok(Self {
+			filters: children_from_args(args, filters)?,
+		})
+	}
+	
+	fn describe_args() -> Vec<Described<ArgumentType>> {
+		describe_children_arg()
+	}
+}
+
+impl<Target: FilterTarget> ReprArgument for And<Target> {
+	fn into_arguments(self) -> Vec<Argument> {
+		children_into_arguments(&self.filters)
+	}
+	
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError> {
+		let args = args.into_iter().map(|arg| arg.data).collect();
+		*self = Self::build(args, filters)?;
+		Ok(())
+	}
+}
+
+impl<Target: FilterTarget> DynSer for And<Target> {
+	fn deserialize_replace(&mut self, _deserializer: &mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error> {
+		Err(cannot_deserialize())
+	}
+	fn serialize(&self, serializer: &mut dyn erased_serde::Serializer) -> Result<(), erased_serde::Error> {
+		serialize_nested(self, serializer)
+	}
+}
+
+
+///Lets through the feeds that pass any of its filters, or no feeds if it has none
+#[derive(Clone, Default)]
+pub struct Or<Target: FilterTarget = feed::Entity> {
+	filters: Vec<Child<Target>>,
+}
+
+impl<Target: FilterTarget> Filter<Target> for Or<Target> {
+	fn filter(&self, query: Select<Target>) -> Select<Target> {
+		let condition = self.filters.iter().fold(Condition::any(), |condition, child| {
+			condition.add(Target::id_column().in_subquery(matching_ids(child.as_ref())))
+		});
+		query.filter(condition)
+	}
+}
+
+impl<Target: FilterTarget> Describe for Or<Target> {
+	const NAME: &'static str = "or";
+}
+
+impl<Target: FilterTarget> Build for Or<Target> {
+	fn build(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Self, ArgumentError> {
+		Ok(Self {
+			filters: children_from_args(args, filters)?,
+		})
+	}
+	
+	fn describe_args() -> Vec<Described<ArgumentType>> {
+		describe_children_arg()
+	}
+}
+
+impl<Target: FilterTarget> ReprArgument for Or<Target> {
+	fn into_arguments(self) -> Vec<Argument> {
+		children_into_arguments(&self.filters)
+	}
+	
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError> {
+		let args = args.into_iter().map(|arg| arg.data).collect();
+		*self = Self::build(args, filters)?;
+		Ok(())
+	}
+}
+
+impl<Target: FilterTarget> DynSer for Or<Target> {
+	fn deserialize_replace(&mut self, _deserializer: &mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error> {
+		Err(cannot_deserialize())
+	}
+	fn serialize(&self, serializer: &mut dyn erased_serde::Serializer) -> Result<(), erased_serde::Error> {
+		serialize_nested(self, serializer)
+	}
+}
+
+
+///Lets through the feeds that don't pass its filter
+#[derive(Clone)]
+pub struct Not<Target: FilterTarget = feed::Entity> {
+	filter: Child<Target>,
+}
+
+///Lets nothing through, since an empty [And] lets everything through
+impl<Target: FilterTarget> Default for Not<Target> {
+	fn default() -> Self {
+		Self {
+			filter: Arc::new(And::<Target>::default()),
+		}
+	}
+}
+
+impl<Target: FilterTarget> Filter<Target> for Not<Target> {
+	fn filter(&self, query: Select<Target>) -> Select<Target> {
+		query.filter(Target::id_column().not_in_subquery(matching_ids(self.filter.as_ref())))
+	}
+}
+
+impl<Target: FilterTarget> Describe for Not<Target> {
+	const NAME: &'static str = "not";
+}
+
+impl<Target: FilterTarget> Build for Not<Target> {
+	fn build(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Self, ArgumentError> {
+		let filter = match single_argument(args)? {
+			ArgumentData::Filter(nested) => build_child(nested, filters)?,
+			other => return Err(
+				ArgumentError::WrongType {
+					index: 0,
+					expected: ArgumentType::Filter,
+					found: other
+				}
+			),
+		};
+		
+		Ok(Self {
+			filter,
+		})
+	}
+	
+	fn describe_args() -> Vec<Described<ArgumentType>> {
+		vec![
+			Described::custom_new(
+				ArgumentType::Filter,
+				"filter".to_owned(),
+				None
+			)
+		]
+	}
+}
+
+impl<Target: FilterTarget> ReprArgument for Not<Target> {
+	fn into_arguments(self) -> Vec<Argument> {
+		vec![
+			Described::custom_new(
+				ArgumentData::Filter(to_nested(self.filter.as_ref())),
+				"filter".to_owned(),
+				None
+			)
+		]
+	}
+	
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError> {
+		let args = args.into_iter().map(|arg| arg.data).collect();
+		*self = Self::build(args, filters)?;
+		Ok(())
+	}
+}
+
+impl<Target: FilterTarget> DynSer for Not<Target> {
+	fn deserialize_replace(&mut self, _deserializer: &mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error> {
+		Err(cannot_deserialize())
+	}
+	fn serialize(&self, serializer: &mut dyn erased_serde::Serializer) -> Result<(), erased_serde::Error> {
+		serialize_nested(self, serializer)
+	}
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use entities::prelude::*;
+use ff_object::{describe::{Describe, Described}, traits::DynSer};
+use sea_orm::{prelude::Select, ColumnTrait, QueryFilter};
+
+use crate::{
+	filter::{Argument, ArgumentData, ArgumentError, ArgumentType, Build, Filter, ReprArgument, to_nested, matching_ids},
+	filters::{And, combinators::{single_argument, serialize_nested, cannot_deserialize}},
+	filter_list::FilterLists,
+};
+
+
+///Lets through the entries of feeds that pass a feed filter
+#[derive(Clone)]
+pub struct Feed {
+	filter: Arc<dyn Filter<feed::Entity> + Send + Sync>,
+}
+
+///Lets every entry through, since an empty [And] lets every feed through
+impl Default for Feed {
+	fn default() -> Self {
+		Self {
+			filter: Arc::new(And::<feed::Entity>::default()),
+		}
+	}
+}
+
+impl Filter<entry::Entity> for Feed {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		query.filter(entry::Column::FeedId.in_subquery(matching_ids(self.filter.as_ref())))
+	}
+}
+
+impl Describe for Feed {
+	const NAME: &'static str = "feed";
+	const DESCRIPTION: Option<&'static str> = Some("Entries of feeds matching a feed filter");
+}
+
+impl Build for Feed {
+	fn build(args: Vec<ArgumentData>, filters: &FilterLists) -> Result<Self, ArgumentError> {
+		let filter = match single_argument(args)? {
+			ArgumentData::FeedFilter(nested) => filters.build::<feed::Entity>(nested)?,
+			other => return Err(
+				ArgumentError::WrongType {
+					index: 0,
+					expected: ArgumentType::FeedFilter,
+					found: other
+				}
+			),
+		};
+		
+		Ok(Self {
+			filter: Arc::from(filter),
+		})
+	}
+	
+	fn describe_args() -> Vec<Described<ArgumentType>> {
+		vec![
+			Described::custom_new(
+				ArgumentType::FeedFilter,
+				"feed_filter".to_owned(),
+				None
+			)
+		]
+	}
+}
+
+impl ReprArgument for Feed {
+	fn into_arguments(self) -> Vec<Argument> {
+		vec![
+			Described::custom_new(
+				ArgumentData::FeedFilter(to_nested(self.filter.as_ref())),
+				"feed_filter".to_owned(),
+				None
+			)
+		]
+	}
+	
+	fn replace_from_args(&mut self, args: Vec<Argument>, filters: &FilterLists) -> Result<(), ArgumentError> {
+		let args = args.into_iter().map(|arg| arg.data).collect();
+		*self = Self::build(args, filters)?;
+		Ok(())
+	}
+}
+
+///Serializes like the [crate::shared::NestedFilter] it can be built from, but can't be deserialized since building needs [FilterLists]
+impl DynSer for Feed {
+	fn deserialize_replace(&mut self, _deserializer: &mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error> {
+		Err(cannot_deserialize())
+	}
+	fn serialize(&self, serializer: &mut dyn erased_serde::Serializer) -> Result<(), erased_serde::Error> {
+		serialize_nested::<entry::Entity>(self, serializer)
+	}
+}
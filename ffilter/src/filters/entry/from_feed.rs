@@ -0,0 +1,27 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the entries of a feed
+#[derive(Debug, Clone, Serialize, Deserialize, BuildFilter)]
+pub struct FromFeed {
+	feed: feed::Ref,
+}
+
+impl Default for FromFeed {
+	fn default() -> Self {
+		Self {
+			feed: feed::Ref::new(-1),
+		}
+	}
+}
+
+impl Filter<entry::Entity> for FromFeed {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		query.filter(entry::Column::FeedId.eq(self.feed.id()))
+	}
+}
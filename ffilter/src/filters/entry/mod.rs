@@ -0,0 +1,20 @@
+mod viewed;
+pub use viewed::Viewed;
+
+mod produced;
+pub use produced::ProducedBetween;
+
+mod title;
+pub use title::TitleContains;
+
+mod feed;
+pub use feed::Feed;
+
+mod tag;
+pub use tag::Tag;
+
+mod older_than;
+pub use older_than::OlderThan;
+
+mod from_feed;
+pub use from_feed::FromFeed;
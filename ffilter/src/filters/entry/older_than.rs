@@ -0,0 +1,22 @@
+use std::time::Duration;
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the entries produced longer ago than some time
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+#[filter(description = "Only looks at the date, not the time")]
+pub struct OlderThan {
+	duration: Duration,
+}
+
+impl Filter<entry::Entity> for OlderThan {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		let before = crate::filters::ago(self.duration).date();
+		query.filter(entry::Column::ProducedDate.lt(time_fields::Date(before)))
+	}
+}
@@ -0,0 +1,30 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the entries produced on or between 2 dates
+#[derive(Debug, Clone, Serialize, Deserialize, BuildFilter)]
+#[filter(description = "Both dates are included")]
+pub struct ProducedBetween {
+	from: time_fields::Date,
+	to: time_fields::Date,
+}
+
+impl Default for ProducedBetween {
+	fn default() -> Self {
+		Self {
+			from: time_fields::Date(time::Date::MIN),
+			to: time_fields::Date(time::Date::MAX),
+		}
+	}
+}
+
+impl Filter<entry::Entity> for ProducedBetween {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		query.filter(entry::Column::ProducedDate.between(self.from.clone(), self.to.clone()))
+	}
+}
@@ -0,0 +1,33 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, sea_query::Query, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the entries of feeds with a tag
+#[derive(Debug, Clone, Serialize, Deserialize, BuildFilter)]
+#[filter(description = "Entries of feeds with the tag")]
+pub struct Tag {
+	tag: tag::Ref,
+}
+
+impl Default for Tag {
+	fn default() -> Self {
+		Self {
+			tag: tag::Ref::new(-1),
+		}
+	}
+}
+
+impl Filter<entry::Entity> for Tag {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		let tagged_feeds = Query::select()
+			.column(feed_tag::Column::FeedId)
+			.from(feed_tag::Entity)
+			.and_where(feed_tag::Column::TagId.eq(self.tag.id()))
+			.to_owned();
+		query.filter(entry::Column::FeedId.in_subquery(tagged_feeds))
+	}
+}
@@ -0,0 +1,21 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, sea_query::Expr, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::{filter::Filter, filters::url_matches::like_pattern};
+
+
+///Lets through the entries with a title containing some text
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+#[filter(description = "Case insensitive for ASCII characters")]
+pub struct TitleContains {
+	#[argument(description = "* matches any text")]
+	text: String,
+}
+
+impl Filter<entry::Entity> for TitleContains {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		query.filter(Expr::col((entry::Entity, entry::Column::Name)).like(like_pattern(&format!("*{}*", self.text))))
+	}
+}
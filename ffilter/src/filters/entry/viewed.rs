@@ -0,0 +1,19 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the entries that have (not) been viewed
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+pub struct Viewed {
+	viewed: bool,
+}
+
+impl Filter<entry::Entity> for Viewed {
+	fn filter(&self, query: Select<entry::Entity>) -> Select<entry::Entity> {
+		query.filter(entry::Column::Viewed.eq(self.viewed))
+	}
+}
@@ -0,0 +1,29 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, sea_query::{Query, Expr, Func}, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the feeds with at least some amount of entries
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+pub struct EntryCount {
+	at_least: i64,
+}
+
+impl Filter for EntryCount {
+	fn filter(&self, query: Select<feed::Entity>) -> Select<feed::Entity> {
+		//Feeds without entries don't show up in the subquery
+		if self.at_least <= 0 {
+			return query;
+		}
+		let enough = Query::select()
+			.column(entry::Column::FeedId)
+			.from(entry::Entity)
+			.group_by_col(entry::Column::FeedId)
+			.and_having(Expr::expr(Func::count(Expr::col(entry::Column::Id))).gte(self.at_least))
+			.to_owned();
+		query.filter(feed::Column::Id.in_subquery(enough))
+	}
+}
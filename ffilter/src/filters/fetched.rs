@@ -1,14 +1,12 @@
 use entities::prelude::*;
-use ff_object::describe::{Describe, Described};
+use ff_macros::BuildFilter;
 use sea_orm::{prelude::Select, QuerySelect};
 use serde::{Deserialize, Serialize};
 
-use crate::filter::{
-	Argument, ArgumentData, ArgumentError, ArgumentType, Build, Filter, ReprArgument
-};
+use crate::filter::Filter;
 
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
 pub struct Fetched;
 
 impl Filter for Fetched {
@@ -20,28 +18,3 @@ impl Filter for Fetched {
 			.distinct()
 	}
 }
-
-impl Describe for Fetched {
-	const NAME: &'static str = "fetched";
-}
-
-impl Build for Fetched {
-	fn build(_args: Vec<ArgumentData>) -> Result<Self, ArgumentError> {
-		Ok(Fetched)
-	}
-	
-	fn describe_args() -> Vec<Described<ArgumentType>> {
-		Vec::new()
-	}
-}
-
-impl ReprArgument for Fetched {
-	fn into_arguments(self) -> Vec<Argument> {
-		Vec::new()
-	}
-
-	fn replace_from_args(&mut self, _args: Vec<Argument>) -> Result<(), ArgumentError>{
-		//do nothing, since we have no arguments
-		Ok(())
-	}
-}
\ No newline at end of file
@@ -1,3 +1,6 @@
+use std::time::Duration;
+use time::PrimitiveDateTime;
+
 mod fetched;
 pub use fetched::Fetched;
 
@@ -5,4 +8,39 @@ mod arg_test;
 pub use arg_test::ArgTest;
 
 mod tag;
-pub use tag::Tag;
\ No newline at end of file
+pub use tag::Tag;
+
+mod name_contains;
+pub use name_contains::NameContains;
+
+mod url_matches;
+pub use url_matches::UrlMatches;
+
+mod strategy;
+pub use strategy::Strategy;
+
+mod entry_count;
+pub use entry_count::EntryCount;
+
+mod not_fetched_for;
+pub use not_fetched_for::NotFetchedFor;
+
+mod combinators;
+pub use combinators::{And, Or, Not};
+
+///Filters for entries instead of feeds
+pub mod entry;
+
+
+///The current time like it's stored in the database, in UTC
+pub(crate) fn now() -> PrimitiveDateTime {
+	let now = time::OffsetDateTime::now_utc();
+	PrimitiveDateTime::new(now.date(), now.time())
+}
+
+///[now] minus `duration`, or the earliest time there is if that's further back
+pub(crate) fn ago(duration: Duration) -> PrimitiveDateTime {
+	time::Duration::try_from(duration).ok()
+		.and_then(|duration| now().checked_sub(duration))
+		.unwrap_or(PrimitiveDateTime::MIN)
+}
@@ -0,0 +1,21 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, sea_query::Expr, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::{filter::Filter, filters::url_matches::like_pattern};
+
+
+///Lets through the feeds with a name containing some text
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+#[filter(description = "Case insensitive for ASCII characters")]
+pub struct NameContains {
+	#[argument(description = "* matches any text")]
+	text: String,
+}
+
+impl Filter for NameContains {
+	fn filter(&self, query: Select<feed::Entity>) -> Select<feed::Entity> {
+		query.filter(Expr::col((feed::Entity, feed::Column::Name)).like(like_pattern(&format!("*{}*", self.text))))
+	}
+}
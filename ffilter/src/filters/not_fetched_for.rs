@@ -0,0 +1,26 @@
+use std::time::Duration;
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, sea_query::Query, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Lets through the feeds that haven't been fetched for some time, including those never fetched
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+pub struct NotFetchedFor {
+	duration: Duration,
+}
+
+impl Filter for NotFetchedFor {
+	fn filter(&self, query: Select<feed::Entity>) -> Select<feed::Entity> {
+		let since = crate::filters::ago(self.duration);
+		let recent = Query::select()
+			.column(fetch::Column::FeedId)
+			.from(fetch::Entity)
+			.and_where(fetch::Column::CreatedAt.gte(time_fields::PrimitiveDateTime(since)))
+			.to_owned();
+		query.filter(feed::Column::Id.not_in_subquery(recent))
+	}
+}
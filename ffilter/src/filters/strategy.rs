@@ -0,0 +1,19 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, ColumnTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::{Filter, StrategyName};
+
+
+///Lets through the feeds using a strategy
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+pub struct Strategy {
+	strategy: StrategyName,
+}
+
+impl Filter for Strategy {
+	fn filter(&self, query: Select<feed::Entity>) -> Select<feed::Entity> {
+		query.filter(feed::Column::Strategy.eq(&self.strategy.0))
+	}
+}
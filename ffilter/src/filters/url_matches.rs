@@ -0,0 +1,32 @@
+use entities::prelude::*;
+use ff_macros::BuildFilter;
+use sea_orm::{prelude::Select, sea_query::{Expr, LikeExpr}, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+use crate::filter::Filter;
+
+
+///Turns `*` into the wildcard of LIKE, and escapes the wildcards of LIKE
+pub(crate) fn like_pattern(pattern: &str) -> LikeExpr {
+	let escaped = pattern
+		.replace('\\', "\\\\")
+		.replace('%', "\\%")
+		.replace('_', "\\_")
+		.replace('*', "%");
+	LikeExpr::new(escaped).escape('\\')
+}
+
+
+///Lets through the feeds with a url matching a pattern
+#[derive(Debug, Default, Clone, Serialize, Deserialize, BuildFilter)]
+#[filter(description = "Case insensitive for ASCII characters")]
+pub struct UrlMatches {
+	#[argument(description = "* matches any text")]
+	pattern: String,
+}
+
+impl Filter for UrlMatches {
+	fn filter(&self, query: Select<feed::Entity>) -> Select<feed::Entity> {
+		query.filter(Expr::col((feed::Entity, feed::Column::Url)).like(like_pattern(&self.pattern)))
+	}
+}
@@ -7,6 +7,9 @@ For a high-level overview of the filter life-cycle, see [app::query](../app/quer
 */
 
 
+//So ff_macros::BuildFilter can refer to ::ffilter from inside this crate too
+extern crate self as ffilter;
+
 pub mod shared;
 
 cfg_if::cfg_if! { if #[cfg(feature = "server")] {
@@ -9,16 +9,54 @@ use entities::prelude::*;
 
 
 
+///What kind of rows a filter filters
+#[derive(Debug, Default, Clone,Copy, PartialEq,Eq, Hash, Serialize,Deserialize)]
+pub enum Target {
+	#[default]
+	Feed,
+	Entry,
+}
+
+///A filter with its arguments, which can in turn contain other filters
+#[derive(Debug, Clone, PartialEq,Eq, Serialize,Deserialize)]
+pub struct NestedFilter {
+	pub name: String,
+	#[serde(default)] //Default transport format errors on empty Vec
+	pub arguments: Vec<ArgumentData>,
+}
+
 #[derive(Debug, Clone, PartialEq,Eq, Serialize,Deserialize)]
 pub enum ArgumentData {
 	Bool(bool),
 	Tag(tag::Ref),
+	String(String),
+	Integer(i64),
+	Date(time_fields::Date),
+	Duration(std::time::Duration),
+	///The name of a strategy
+	Strategy(String),
+	Feed(feed::Ref),
+	///A filter with the same [Target] as the filter it's an argument of
+	Filter(NestedFilter),
+	///Filters with the same [Target] as the filter they're an argument of
+	Filters(Vec<NestedFilter>),
+	///A filter for [Target::Feed], whatever the target of the filter it's an argument of
+	FeedFilter(NestedFilter),
 }
 
 #[derive(Debug, Clone,Copy, PartialEq,Eq, Serialize,Deserialize)]
 pub enum ArgumentType {
 	Bool,
 	Tag,
+	String,
+	Integer,
+	Date,
+	Duration,
+	Strategy,
+	Feed,
+	Filter,
+	Filters,
+	FeedFilter,
 }
 
 impl From<ArgumentData> for ArgumentType {
@@ -28,6 +66,15 @@ impl From<ArgumentData> for ArgumentType {
 		match data {
 			D::Bool(_) => T::Bool,
 			D::Tag(_) => T::Tag,
+			D::String(_) => T::String,
+			D::Integer(_) => T::Integer,
+			D::Date(_) => T::Date,
+			D::Duration(_) => T::Duration,
+			D::Strategy(_) => T::Strategy,
+			D::Feed(_) => T::Feed,
+			D::Filter(_) => T::Filter,
+			D::Filters(_) => T::Filters,
+			D::FeedFilter(_) => T::FeedFilter,
 		}
 	}
 }
\ No newline at end of file
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{combine, not, nested};
+use entities::prelude::*;
+use ffilter::{
+	filter::to_nested,
+	filter_list::FilterLists,
+	shared::{ArgumentData, NestedFilter},
+};
+use sea_orm::*;
+
+struct Setup {
+	conn: DatabaseConnection,
+	filters: FilterLists,
+	///Tagged and fetched
+	both: i32,
+	tagged: i32,
+	fetched: i32,
+	neither: i32,
+	tag: i32,
+}
+
+async fn setup() -> Result<Setup, DbErr> {
+	let conn = common::db().await?;
+	
+	let tag = common::tag("Tag", &conn).await?;
+	
+	let both = common::feed("both", &conn).await?;
+	common::fetch(&both, &conn).await?;
+	//A second fetch shouldn't make it show up twice
+	common::fetch(&both, &conn).await?;
+	common::add_tag(&both, &tag, &conn).await?;
+	
+	let tagged = common::feed("tagged", &conn).await?;
+	common::add_tag(&tagged, &tag, &conn).await?;
+	
+	let fetched = common::feed("fetched", &conn).await?;
+	common::fetch(&fetched, &conn).await?;
+	
+	let neither = common::feed("neither", &conn).await?;
+	
+	Ok(Setup {
+		conn,
+		filters: common::filter_lists(),
+		both: both.id,
+		tagged: tagged.id,
+		fetched: fetched.id,
+		neither: neither.id,
+		tag: tag.id,
+	})
+}
+
+fn fetched() -> NestedFilter {
+	nested("fetched", Vec::new())
+}
+
+fn tagged(setup: &Setup) -> NestedFilter {
+	nested("tag", vec![ArgumentData::Tag(tag::Ref::new(setup.tag))])
+}
+
+async fn matching(setup: &Setup, filter: NestedFilter) -> Result<Vec<i32>, DbErr> {
+	common::matching::<feed::Entity>(&setup.filters, filter, &setup.conn).await
+}
+
+#[tokio::test]
+async fn and() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let both = matching(&setup, combine("and", vec![fetched(), tagged(&setup)])).await?;
+	assert_eq!(both, vec![setup.both]);
+	
+	let all = matching(&setup, combine("and", Vec::new())).await?;
+	assert_eq!(all, vec![setup.both, setup.tagged, setup.fetched, setup.neither]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn or() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let either = matching(&setup, combine("or", vec![fetched(), tagged(&setup)])).await?;
+	assert_eq!(either, vec![setup.both, setup.tagged, setup.fetched]);
+	
+	let none = matching(&setup, combine("or", Vec::new())).await?;
+	assert_eq!(none, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn not_filter() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let unfetched = matching(&setup, not(fetched())).await?;
+	assert_eq!(unfetched, vec![setup.tagged, setup.neither]);
+	
+	let only_fetched = matching(&setup, combine("and", vec![fetched(), not(tagged(&setup))])).await?;
+	assert_eq!(only_fetched, vec![setup.fetched]);
+	
+	let neither = matching(&setup, not(combine("or", vec![fetched(), tagged(&setup)]))).await?;
+	assert_eq!(neither, vec![setup.neither]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn nested_roundtrip() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let nested = combine("or", vec![not(fetched()), combine("and", vec![tagged(&setup)])]);
+	let filter = setup.filters.build::<feed::Entity>(nested.clone()).expect("filter should build");
+	assert_eq!(to_nested(filter.as_ref()), nested);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn unknown_nested() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let unknown = nested("unknown", Vec::new());
+	assert!(setup.filters.build::<feed::Entity>(not(unknown)).is_err());
+	
+	Ok(())
+}
@@ -0,0 +1,131 @@
+//Not all tests use all the stuff in here, so they generate false warnings
+#![allow(dead_code)]
+
+use entities::prelude::*;
+use ffilter::{
+	filter::FilterTarget,
+	filter_list::FilterLists,
+	filters::{self, And, Fetched, Not, Or},
+	shared::{ArgumentData, NestedFilter},
+};
+use sea_migration::{Migrator, MigratorTrait};
+use sea_orm::*;
+
+pub async fn db() -> Result<DatabaseConnection, DbErr> {
+	let conn = Database::connect("sqlite::memory:").await?;
+	Migrator::up(&conn, None).await?;
+	
+	Ok(conn)
+}
+
+///All the filters, like the server entrypoint has them
+pub fn filter_lists() -> FilterLists {
+	let mut lists = FilterLists::new();
+	
+	lists.add(Fetched);
+	lists.add(filters::Tag::default());
+	lists.add(filters::NameContains::default());
+	lists.add(filters::UrlMatches::default());
+	lists.add(filters::Strategy::default());
+	lists.add(filters::EntryCount::default());
+	lists.add(filters::NotFetchedFor::default());
+	lists.add(And::<feed::Entity>::default());
+	lists.add(Or::<feed::Entity>::default());
+	lists.add(Not::<feed::Entity>::default());
+	
+	lists.add(filters::entry::Viewed::default());
+	lists.add(filters::entry::ProducedBetween::default());
+	lists.add(filters::entry::TitleContains::default());
+	lists.add(filters::entry::Feed::default());
+	lists.add(filters::entry::Tag::default());
+	lists.add(filters::entry::OlderThan::default());
+	lists.add(filters::entry::FromFeed::default());
+	lists.add(And::<entry::Entity>::default());
+	lists.add(Or::<entry::Entity>::default());
+	lists.add(Not::<entry::Entity>::default());
+	
+	lists
+}
+
+pub async fn feed(name: &str, conn: &DatabaseConnection) -> Result<feed::Model, DbErr> {
+	let mut feed = feed::ActiveModel::new();
+	feed.name = Set(name.to_owned());
+	feed.url = Set(name.to_owned());
+	feed.strategy = Set("mock".to_owned());
+	feed.insert(conn).await
+}
+
+pub async fn fetch(feed: &feed::Model, conn: &DatabaseConnection) -> Result<(), DbErr> {
+	let mut fetch = fetch::ActiveModel::new();
+	fetch.feed_id = Set(feed.id);
+	fetch.url = Set(feed.url.clone());
+	fetch.strategy = Set(feed.strategy.clone());
+	fetch.status = Set(fetch::Status::Success);
+	fetch.log = Set(String::new());
+	fetch.insert(conn).await?;
+	Ok(())
+}
+
+pub async fn tag(title: &str, conn: &DatabaseConnection) -> Result<tag::Model, DbErr> {
+	let mut tag = tag::ActiveModel::new();
+	tag.title = Set(title.to_owned());
+	tag.insert(conn).await
+}
+
+pub async fn add_tag(feed: &feed::Model, tag: &tag::Model, conn: &DatabaseConnection) -> Result<(), DbErr> {
+	let mut feed_tag = feed_tag::ActiveModel::new();
+	feed_tag.feed_id = Set(feed.id);
+	feed_tag.tag_id = Set(tag.id);
+	feed_tag.insert(conn).await?;
+	Ok(())
+}
+
+pub async fn entry(
+	name: &str,
+	feed: &feed::Model,
+	produced: time::Date,
+	viewed: bool,
+	conn: &DatabaseConnection
+) -> Result<entry::Model, DbErr> {
+	let mut entry = entry::ActiveModel::new();
+	entry.name = Set(name.to_owned());
+	entry.view_url = Set(format!("{}/{name}", feed.url));
+	entry.feed_entry_id = Set(name.to_owned());
+	entry.feed_id = Set(feed.id);
+	entry.viewed = Set(viewed);
+	entry.produced_date = Set(time_fields::Date(produced));
+	entry.produced_time = Set(time_fields::OptionTime(None));
+	entry.insert(conn).await
+}
+
+
+pub fn nested(name: &str, arguments: Vec<ArgumentData>) -> NestedFilter {
+	NestedFilter {
+		name: name.to_owned(),
+		arguments,
+	}
+}
+
+pub fn combine(name: &str, filters: Vec<NestedFilter>) -> NestedFilter {
+	nested(name, vec![ArgumentData::Filters(filters)])
+}
+
+pub fn not(filter: NestedFilter) -> NestedFilter {
+	nested("not", vec![ArgumentData::Filter(filter)])
+}
+
+///Sorted ids of the rows `filter` lets through
+pub async fn matching<Target: FilterTarget>(
+	lists: &FilterLists,
+	filter: NestedFilter,
+	conn: &DatabaseConnection
+) -> Result<Vec<i32>, DbErr> {
+	let filter = lists.build::<Target>(filter).expect("filter should build");
+	let mut ids = filter.filter(Target::find())
+		.select_only()
+		.column(Target::id_column())
+		.into_tuple::<i32>()
+		.all(conn).await?;
+	ids.sort();
+	Ok(ids)
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use common::{combine, not, nested};
+use entities::prelude::*;
+use ffilter::{
+	filter_list::FilterLists,
+	shared::{ArgumentData, NestedFilter},
+};
+use sea_orm::*;
+use time::macros::date;
+
+struct Setup {
+	conn: DatabaseConnection,
+	filters: FilterLists,
+	tag: i32,
+	///From a tagged and fetched feed, viewed
+	old: i32,
+	///From a tagged and fetched feed
+	new: i32,
+	///From an untagged feed that wasn't fetched
+	other: i32,
+}
+
+async fn setup() -> Result<Setup, DbErr> {
+	let conn = common::db().await?;
+	
+	let tag = common::tag("Tag", &conn).await?;
+	
+	let tagged = common::feed("tagged", &conn).await?;
+	common::add_tag(&tagged, &tag, &conn).await?;
+	common::fetch(&tagged, &conn).await?;
+	let other_feed = common::feed("other", &conn).await?;
+	
+	let old = common::entry("Old Thing", &tagged, date!(2024-01-01), true, &conn).await?;
+	let new = common::entry("New thing", &tagged, date!(2024-06-15), false, &conn).await?;
+	let other = common::entry("Something else", &other_feed, date!(2024-06-30), false, &conn).await?;
+	
+	Ok(Setup {
+		conn,
+		filters: common::filter_lists(),
+		tag: tag.id,
+		old: old.id,
+		new: new.id,
+		other: other.id,
+	})
+}
+
+async fn matching(setup: &Setup, filter: NestedFilter) -> Result<Vec<i32>, DbErr> {
+	common::matching::<entry::Entity>(&setup.filters, filter, &setup.conn).await
+}
+
+#[tokio::test]
+async fn viewed() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let viewed = matching(&setup, nested("viewed", vec![ArgumentData::Bool(true)])).await?;
+	assert_eq!(viewed, vec![setup.old]);
+	
+	let unviewed = matching(&setup, nested("viewed", vec![ArgumentData::Bool(false)])).await?;
+	assert_eq!(unviewed, vec![setup.new, setup.other]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn produced_between() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let between = |from, to| nested("produced_between", vec![
+		ArgumentData::Date(time_fields::Date(from)),
+		ArgumentData::Date(time_fields::Date(to)),
+	]);
+	
+	let inclusive = matching(&setup, between(date!(2024-01-01), date!(2024-06-15))).await?;
+	assert_eq!(inclusive, vec![setup.old, setup.new]);
+	
+	let none = matching(&setup, between(date!(2023-01-01), date!(2023-12-31))).await?;
+	assert_eq!(none, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn title_contains() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let things = matching(&setup, nested("title_contains", vec![ArgumentData::String("thing".to_owned())])).await?;
+	assert_eq!(things, vec![setup.old, setup.new, setup.other]);
+	
+	let new = matching(&setup, nested("title_contains", vec![ArgumentData::String("new".to_owned())])).await?;
+	assert_eq!(new, vec![setup.new]);
+	
+	//% and _ are not wildcards
+	let percent = matching(&setup, nested("title_contains", vec![ArgumentData::String("%".to_owned())])).await?;
+	assert_eq!(percent, Vec::<i32>::new());
+	let underscore = matching(&setup, nested("title_contains", vec![ArgumentData::String("old_thing".to_owned())])).await?;
+	assert_eq!(underscore, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn feed_tag() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let tagged = matching(&setup, nested("tag", vec![ArgumentData::Tag(tag::Ref::new(setup.tag))])).await?;
+	assert_eq!(tagged, vec![setup.old, setup.new]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn feed_filter() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let fetched = nested("fetched", Vec::new());
+	
+	let from_fetched = matching(&setup, nested("feed", vec![ArgumentData::FeedFilter(fetched.clone())])).await?;
+	assert_eq!(from_fetched, vec![setup.old, setup.new]);
+	
+	let from_unfetched = matching(&setup, nested("feed", vec![ArgumentData::FeedFilter(not(fetched))])).await?;
+	assert_eq!(from_unfetched, vec![setup.other]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn combined() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let filter = combine("and", vec![
+		not(nested("viewed", vec![ArgumentData::Bool(true)])),
+		nested("tag", vec![ArgumentData::Tag(tag::Ref::new(setup.tag))]),
+	]);
+	let unviewed_tagged = matching(&setup, filter).await?;
+	assert_eq!(unviewed_tagged, vec![setup.new]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn older_than() -> Result<(), DbErr> {
+	let conn = common::db().await?;
+	let feed = common::feed("feed", &conn).await?;
+	let today = time::OffsetDateTime::now_utc().date();
+	let new = common::entry("new", &feed, today, false, &conn).await?;
+	let old = common::entry("old", &feed, today - time::Duration::days(10), false, &conn).await?;
+	let filters = common::filter_lists();
+	
+	let older_than = |days: u64| nested("older_than", vec![ArgumentData::Duration(std::time::Duration::from_secs(days*24*60*60))]);
+	
+	assert_eq!(common::matching::<entry::Entity>(&filters, older_than(0), &conn).await?, vec![old.id]);
+	assert_eq!(common::matching::<entry::Entity>(&filters, older_than(9), &conn).await?, vec![old.id]);
+	assert_eq!(common::matching::<entry::Entity>(&filters, older_than(10), &conn).await?, Vec::<i32>::new());
+	//Entries from today are never older
+	assert!(!common::matching::<entry::Entity>(&filters, older_than(0), &conn).await?.contains(&new.id));
+	//Longer ago than dates go
+	assert_eq!(common::matching::<entry::Entity>(&filters, older_than(1_000_000_000), &conn).await?, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn from_feed() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let other_feed = entry::Entity::find_by_id(setup.other)
+		.one(&setup.conn).await?
+		.expect("entry should exist")
+		.feed_id;
+	let from_other = matching(&setup, nested("from_feed", vec![ArgumentData::Feed(feed::Ref::new(other_feed))])).await?;
+	assert_eq!(from_other, vec![setup.other]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn wrong_target() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	//Feed filters can only be used through the feed filter
+	assert!(setup.filters.build::<entry::Entity>(nested("fetched", Vec::new())).is_err());
+	assert!(setup.filters.build::<entry::Entity>(nested("feed", vec![ArgumentData::Filter(nested("fetched", Vec::new()))])).is_err());
+	
+	Ok(())
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use std::time::Duration;
+use common::nested;
+use entities::prelude::*;
+use ffilter::{
+	filter_list::FilterLists,
+	shared::{ArgumentData, NestedFilter},
+};
+use sea_orm::*;
+
+struct Setup {
+	conn: DatabaseConnection,
+	filters: FilterLists,
+	///Fetched just now, with 2 entries
+	blog: i32,
+	///Fetched 10 days ago, with 1 entry
+	videos: i32,
+	///Never fetched
+	percent: i32,
+}
+
+async fn feed(name: &str, url: &str, strategy: &str, conn: &DatabaseConnection) -> Result<feed::Model, DbErr> {
+	let mut feed = feed::ActiveModel::new();
+	feed.name = Set(name.to_owned());
+	feed.url = Set(url.to_owned());
+	feed.strategy = Set(strategy.to_owned());
+	feed.insert(conn).await
+}
+
+async fn fetch_ago(feed: &feed::Model, ago: Duration, conn: &DatabaseConnection) -> Result<(), DbErr> {
+	let now = time::OffsetDateTime::now_utc() - ago;
+	let at = time_fields::PrimitiveDateTime(time::PrimitiveDateTime::new(now.date(), now.time()));
+	let mut fetch = fetch::ActiveModel::new();
+	fetch.feed_id = Set(feed.id);
+	fetch.url = Set(feed.url.clone());
+	fetch.strategy = Set(feed.strategy.clone());
+	fetch.status = Set(fetch::Status::Success);
+	fetch.log = Set(String::new());
+	fetch.created_at = Set(at.clone());
+	fetch.updated_at = Set(at);
+	fetch.insert(conn).await?;
+	Ok(())
+}
+
+const DAY: Duration = Duration::from_secs(24*60*60);
+
+async fn setup() -> Result<Setup, DbErr> {
+	let conn = common::db().await?;
+	
+	let blog = feed("My Blog", "https://blog.example.com/feed.xml", "rss", &conn).await?;
+	fetch_ago(&blog, Duration::ZERO, &conn).await?;
+	let today = time::OffsetDateTime::now_utc().date();
+	common::entry("first", &blog, today, false, &conn).await?;
+	common::entry("second", &blog, today, false, &conn).await?;
+	
+	let videos = feed("Videos", "https://www.youtube.com/@example", "yt-dlp", &conn).await?;
+	fetch_ago(&videos, DAY*10, &conn).await?;
+	common::entry("video", &videos, today, false, &conn).await?;
+	
+	let percent = feed("100% blog", "https://example.com/100%_feed", "rss", &conn).await?;
+	
+	Ok(Setup {
+		conn,
+		filters: common::filter_lists(),
+		blog: blog.id,
+		videos: videos.id,
+		percent: percent.id,
+	})
+}
+
+async fn matching(setup: &Setup, filter: NestedFilter) -> Result<Vec<i32>, DbErr> {
+	common::matching::<feed::Entity>(&setup.filters, filter, &setup.conn).await
+}
+
+fn string(name: &str, text: &str) -> NestedFilter {
+	nested(name, vec![ArgumentData::String(text.to_owned())])
+}
+
+#[tokio::test]
+async fn name_contains() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let blogs = matching(&setup, string("name_contains", "BLOG")).await?;
+	assert_eq!(blogs, vec![setup.blog, setup.percent]);
+	
+	//% and _ are not wildcards
+	let percent = matching(&setup, string("name_contains", "0% b")).await?;
+	assert_eq!(percent, vec![setup.percent]);
+	let not_wildcards = matching(&setup, string("name_contains", "my_blog")).await?;
+	assert_eq!(not_wildcards, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn url_matches() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let youtube = matching(&setup, string("url_matches", "https://*youtube.com/*")).await?;
+	assert_eq!(youtube, vec![setup.videos]);
+	
+	let example = matching(&setup, string("url_matches", "*example.com*")).await?;
+	assert_eq!(example, vec![setup.blog, setup.percent]);
+	
+	//Without a wildcard, the whole url has to match
+	let partial = matching(&setup, string("url_matches", "example.com")).await?;
+	assert_eq!(partial, Vec::<i32>::new());
+	
+	//% and _ are not wildcards
+	let literal = matching(&setup, string("url_matches", "*100%_feed")).await?;
+	assert_eq!(literal, vec![setup.percent]);
+	let not_wildcards = matching(&setup, string("url_matches", "*/%_eed*")).await?;
+	assert_eq!(not_wildcards, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn strategy() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let rss = matching(&setup, nested("strategy", vec![ArgumentData::Strategy("rss".to_owned())])).await?;
+	assert_eq!(rss, vec![setup.blog, setup.percent]);
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn entry_count() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let at_least = |count| nested("entry_count", vec![ArgumentData::Integer(count)]);
+	
+	assert_eq!(matching(&setup, at_least(0)).await?, vec![setup.blog, setup.videos, setup.percent]);
+	assert_eq!(matching(&setup, at_least(1)).await?, vec![setup.blog, setup.videos]);
+	assert_eq!(matching(&setup, at_least(2)).await?, vec![setup.blog]);
+	assert_eq!(matching(&setup, at_least(3)).await?, Vec::<i32>::new());
+	
+	Ok(())
+}
+
+#[tokio::test]
+async fn not_fetched_for() -> Result<(), DbErr> {
+	let setup = setup().await?;
+	
+	let not_fetched_for = |duration| nested("not_fetched_for", vec![ArgumentData::Duration(duration)]);
+	
+	let week = matching(&setup, not_fetched_for(DAY*7)).await?;
+	assert_eq!(week, vec![setup.videos, setup.percent]);
+	
+	let month = matching(&setup, not_fetched_for(DAY*30)).await?;
+	assert_eq!(month, vec![setup.percent]);
+	
+	//Longer ago than dates go
+	let forever = matching(&setup, not_fetched_for(Duration::MAX)).await?;
+	assert_eq!(forever, vec![setup.percent]);
+	
+	Ok(())
+}
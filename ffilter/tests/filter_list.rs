@@ -0,0 +1,41 @@
+mod common;
+
+use common::{combine, not, nested};
+use entities::prelude::*;
+use ffilter::shared::ArgumentData;
+
+#[test]
+fn no_missing_filters() {
+	let lists = common::filter_lists();
+	let filter = combine("and", vec![
+		nested("viewed", Vec::new()),
+		not(nested("feed", vec![ArgumentData::FeedFilter(nested("fetched", Vec::new()))])),
+	]);
+	
+	assert!(lists.missing_filters::<entry::Entity>(&filter).is_empty());
+}
+
+#[test]
+fn missing_nested_filters() {
+	let lists = common::filter_lists();
+	let filter = combine("or", vec![
+		nested("gone", Vec::new()),
+		not(nested("also_gone", Vec::new())),
+		nested("feed", vec![ArgumentData::FeedFilter(nested("feed_gone", Vec::new()))]),
+	]);
+	
+	assert_eq!(
+		lists.missing_filters::<entry::Entity>(&filter),
+		vec!["gone", "also_gone", "feed_gone"],
+	);
+}
+
+#[test]
+fn missing_for_target() {
+	let lists = common::filter_lists();
+	//Exists, but only for feeds
+	let filter = nested("fetched", Vec::new());
+	
+	assert!(lists.missing_filters::<feed::Entity>(&filter).is_empty());
+	assert_eq!(lists.missing_filters::<entry::Entity>(&filter), vec!["fetched"]);
+}
@@ -9,6 +9,13 @@ mod m20231219_000004_add_entries;
 mod m20240113_220905_tags;
 mod m20240115_131700_feedtag;
 mod m20240207_170000_filterless_tags;
+mod m20261018_120000_fetch_validators;
+mod m20261018_130000_feed_refresh_interval;
+mod m20261018_140000_batches;
+mod m20261018_150000_entry_canonical_id;
+mod m20261018_160000_saved_queries;
+mod m20261018_170000_entry_viewed_at;
+mod m20261018_180000_feed_strategy_options;
 
 pub struct Migrator;
 
@@ -23,6 +30,13 @@ impl MigratorTrait for Migrator {
 			Box::new(m20240113_220905_tags::Migration),
 			Box::new(m20240115_131700_feedtag::Migration),
 			Box::new(m20240207_170000_filterless_tags::Migration),
+			Box::new(m20261018_120000_fetch_validators::Migration),
+			Box::new(m20261018_130000_feed_refresh_interval::Migration),
+			Box::new(m20261018_140000_batches::Migration),
+			Box::new(m20261018_150000_entry_canonical_id::Migration),
+			Box::new(m20261018_160000_saved_queries::Migration),
+			Box::new(m20261018_170000_entry_viewed_at::Migration),
+			Box::new(m20261018_180000_feed_strategy_options::Migration),
 		]
 	}
 }
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20231219_000003_add_fetches::Iden as FetchIden;
+
+use super::utils::*;
+
+#[derive(Iden)]
+pub enum Iden {
+	Etag,
+	LastModified,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> DbRes {
+		//SQLite doesn't support multiple alterations in one statement, and SeaORM is rather transparent here
+		let mut tas = Table::alter();
+		tas
+			.table(FetchIden::Fetch)
+			.add_column(ColumnDef::new(Iden::Etag).string().null());
+		manager.alter_table(tas).await?;
+		let mut tas = Table::alter();
+		tas
+			.table(FetchIden::Fetch)
+			.add_column(ColumnDef::new(Iden::LastModified).string().null());
+		manager.alter_table(tas).await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(FetchIden::Fetch)
+			.drop_column(Iden::Etag);
+		manager.alter_table(tas).await?;
+		let mut tas = Table::alter();
+		tas
+			.table(FetchIden::Fetch)
+			.drop_column(Iden::LastModified);
+		manager.alter_table(tas).await
+	}
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::{
+	prelude::*,
+	sea_orm::{EnumIter, Iterable}
+};
+
+use super::utils::*;
+use super::m20231219_000003_add_fetches::Iden as FetchIden;
+
+#[derive(Iden)]
+pub enum Iden {
+	Batch,
+	Status,
+	Total,
+	BatchId,
+}
+
+#[derive(Iden,EnumIter)]
+enum Status {
+	Running,
+	Finished,
+	Cancelled,
+	Interrupted,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> DbRes {
+		add_table(
+			manager,
+			Table::create()
+				.table(Iden::Batch)
+				.col(ColumnDef::new(Iden::Status).enumeration(Iden::Status, Status::iter()).not_null())
+				.col(ColumnDef::new(Iden::Total).integer().not_null())
+		)
+		.await?;
+		
+		//SQLite can't add foreign keys to existing tables
+		let mut tas = Table::alter();
+		tas
+			.table(FetchIden::Fetch)
+			.add_column(ColumnDef::new(Iden::BatchId).integer().null());
+		manager.alter_table(tas).await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(FetchIden::Fetch)
+			.drop_column(Iden::BatchId);
+		manager.alter_table(tas).await?;
+		
+		remove_table(manager, Iden::Batch).await
+	}
+}
@@ -0,0 +1,161 @@
+use sea_orm_migration::{prelude::*, sea_orm::{ConnectionTrait, TransactionTrait}};
+use url::Url;
+
+use crate::m20231219_000004_add_entries::Iden as EntryIden;
+
+use super::utils::*;
+
+#[derive(Iden)]
+pub enum Iden {
+	CanonicalId,
+}
+
+const INDEX_NAME: &str = "entry_canonical_id";
+
+/*
+A frozen copy of acquire::canonical::canonical_url as it was when this migration got added.
+Changing the normalizer later shouldn't change what this migration does, newer entries get their id from the current one.
+*/
+
+const TRACKING_PARAMETERS: &[&str] = &["fbclid", "gclid"];
+
+const HOST_TRACKING_PARAMETERS: &[(&str, &[&str])] = &[
+	("youtube.com", &["si", "feature", "pp"]),
+	("m.youtube.com", &["si", "feature", "pp"]),
+	("music.youtube.com", &["si", "feature"]),
+	("youtu.be", &["si", "feature"]),
+	("open.spotify.com", &["si"]),
+	("twitter.com", &["s", "t", "ref_src"]),
+	("x.com", &["s", "t", "ref_src"]),
+	("instagram.com", &["igsh", "igshid"]),
+];
+
+fn is_tracking_parameter(host: &str, name: &str) -> bool {
+	if name.starts_with("utm_") || TRACKING_PARAMETERS.contains(&name) {
+		return true;
+	}
+	HOST_TRACKING_PARAMETERS.iter()
+		.any(|(tracking_host, parameters)| *tracking_host == host && parameters.contains(&name))
+}
+
+fn youtube_video_id(url: &Url, host: &str) -> Option<String> {
+	let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+	let id = match host {
+		"youtu.be" => segments.next()?.to_owned(),
+		"youtube.com" | "m.youtube.com" | "youtube-nocookie.com" => match segments.next()? {
+			"watch" => url.query_pairs().find(|(name, _)| name == "v")?.1.into_owned(),
+			"shorts" | "embed" | "live" => segments.next()?.to_owned(),
+			_ => return None,
+		},
+		_ => return None,
+	};
+	Some(id)
+}
+
+fn canonical_url(url: &str) -> String {
+	let url = url.trim();
+	let parsed = if url.contains("://") {
+		Url::parse(url)
+	} else {
+		Url::parse(&format!("https://{url}"))
+	};
+	let Ok(parsed) = parsed else {
+		return url.to_owned();
+	};
+	let Some(host) = parsed.host_str() else {
+		return url.to_owned();
+	};
+	let host = host.to_lowercase();
+	let host = host.strip_prefix("www.").unwrap_or(&host);
+	
+	if let Some(id) = youtube_video_id(&parsed, host) {
+		return format!("youtube:{id}");
+	}
+	
+	let mut canonical = host.to_owned();
+	if let Some(port) = parsed.port() {
+		canonical.push_str(&format!(":{port}"));
+	}
+	canonical.push_str(parsed.path().trim_end_matches('/'));
+	
+	let mut query = parsed.query_pairs()
+		.filter(|(name, _)| !is_tracking_parameter(host, name))
+		.map(|(name, value)| format!("{name}={value}"))
+		.collect::<Vec<_>>();
+	if !query.is_empty() {
+		query.sort();
+		canonical.push('?');
+		canonical.push_str(&query.join("&"));
+	}
+	if let Some(fragment) = parsed.fragment().filter(|fragment| !fragment.is_empty()) {
+		canonical.push('#');
+		canonical.push_str(fragment);
+	}
+	
+	canonical
+}
+
+///Gives the entries that are already there a canonical id, so they get recognised as duplicates as well
+async fn backfill(manager: &SchemaManager<'_>) -> DbRes {
+	let conn = manager.get_connection();
+	let backend = conn.get_database_backend();
+	
+	let select = Query::select()
+		.columns([UtilIdent::Id.into_iden(), EntryIden::ViewUrl.into_iden()])
+		.from(EntryIden::Entry)
+		.to_owned();
+	let rows = conn.query_all(backend.build(&select)).await?;
+	
+	let transaction = conn.begin().await?;
+	for row in rows {
+		let id: i32 = row.try_get_by_index(0)?;
+		let view_url: String = row.try_get_by_index(1)?;
+		
+		let update = Query::update()
+			.table(EntryIden::Entry)
+			.value(Iden::CanonicalId, canonical_url(&view_url))
+			.and_where(Expr::col(UtilIdent::Id).eq(id))
+			.to_owned();
+		transaction.execute(backend.build(&update)).await?;
+	}
+	transaction.commit().await
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(EntryIden::Entry)
+			.add_column(ColumnDef::new(Iden::CanonicalId).string().null());
+		manager.alter_table(tas).await?;
+		
+		manager.create_index(
+			Index::create()
+				.name(INDEX_NAME)
+				.table(EntryIden::Entry)
+				.col(Iden::CanonicalId)
+				.to_owned()
+		).await?;
+		
+		backfill(manager).await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> DbRes {
+		manager.drop_index(
+			Index::drop()
+				.name(INDEX_NAME)
+				.table(EntryIden::Entry)
+				.to_owned()
+		).await?;
+		
+		let mut tas = Table::alter();
+		tas
+			.table(EntryIden::Entry)
+			.drop_column(Iden::CanonicalId);
+		manager.alter_table(tas).await
+	}
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+
+use super::utils::*;
+
+#[derive(Iden)]
+pub enum Iden {
+	SavedQuery,
+	Name,
+	Query,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> DbRes {
+		add_table(
+			manager,
+			Table::create()
+				.table(Iden::SavedQuery)
+				.col(ColumnDef::new(Iden::Name).string().not_null())
+				//Serialized as JSON
+				.col(ColumnDef::new(Iden::Query).text().not_null())
+		)
+		.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> DbRes {
+		remove_table(manager, Iden::SavedQuery).await
+	}
+}
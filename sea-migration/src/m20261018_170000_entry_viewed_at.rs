@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20231219_000004_add_entries::Iden as EntryIden;
+
+use super::utils::*;
+
+#[derive(Iden)]
+pub enum Iden {
+	ViewedAt,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(EntryIden::Entry)
+			.add_column(ColumnDef::new(Iden::ViewedAt).timestamp().null());
+		manager.alter_table(tas).await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(EntryIden::Entry)
+			.drop_column(Iden::ViewedAt);
+		manager.alter_table(tas).await
+	}
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20231219_000002_add_feeds::Iden as FeedIden;
+
+use super::utils::*;
+
+#[derive(Iden)]
+pub enum Iden {
+	StrategyOptions,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(FeedIden::Feed)
+			.add_column(ColumnDef::new(Iden::StrategyOptions).text().null());
+		manager.alter_table(tas).await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> DbRes {
+		let mut tas = Table::alter();
+		tas
+			.table(FeedIden::Feed)
+			.drop_column(Iden::StrategyOptions);
+		manager.alter_table(tas).await
+	}
+}
@@ -1,13 +1,38 @@
+use entities::prelude::*;
+
 #[tokio::main]
 async fn main() {
 	let mut setup = server_setup::setup::Setup::default();
 	
 	setup.add_strategy(acquire::mock::MockStrat::default());
 	setup.add_strategy(acquire::yt_dlp::YtDlpStrategy::default());
+	setup.add_strategy(acquire::rss::RssStrategy::default());
+	setup.add_strategy(acquire::click_next::ClickNextStrategy::default());
+	//Every command.<name>.ron in the strategy configuration folder adds another instance, to run other commands
+	setup.add_strategy_with_instances(acquire::command::CommandStrategy::default());
 	
-	setup.add_filter(ffilter::filters::Fetched::default());
+	setup.add_filter(ffilter::filters::Fetched);
 	setup.add_filter(ffilter::filters::ArgTest::default());
 	setup.add_filter(ffilter::filters::Tag::default());
+	setup.add_filter(ffilter::filters::NameContains::default());
+	setup.add_filter(ffilter::filters::UrlMatches::default());
+	setup.add_filter(ffilter::filters::Strategy::default());
+	setup.add_filter(ffilter::filters::EntryCount::default());
+	setup.add_filter(ffilter::filters::NotFetchedFor::default());
+	setup.add_filter(ffilter::filters::And::<feed::Entity>::default());
+	setup.add_filter(ffilter::filters::Or::<feed::Entity>::default());
+	setup.add_filter(ffilter::filters::Not::<feed::Entity>::default());
+	
+	setup.add_filter(ffilter::filters::entry::Viewed::default());
+	setup.add_filter(ffilter::filters::entry::ProducedBetween::default());
+	setup.add_filter(ffilter::filters::entry::TitleContains::default());
+	setup.add_filter(ffilter::filters::entry::Feed::default());
+	setup.add_filter(ffilter::filters::entry::Tag::default());
+	setup.add_filter(ffilter::filters::entry::OlderThan::default());
+	setup.add_filter(ffilter::filters::entry::FromFeed::default());
+	setup.add_filter(ffilter::filters::And::<entry::Entity>::default());
+	setup.add_filter(ffilter::filters::Or::<entry::Entity>::default());
+	setup.add_filter(ffilter::filters::Not::<entry::Entity>::default());
 	
 	server_setup::run::<sea_migration::Migrator, _>(
 		app::app::App,
@@ -1,11 +1,17 @@
-use std::{fs, path::PathBuf};
+use std::{fs, path::PathBuf, time::Duration};
 
 use serde::Deserialize;
+use acquire::{limiter::Limits, retry::RetryPolicy};
 
 
 const ENVIRONMENT_VARIABLE_PREFIX: &str = "FUSTA_FEMAS_";
 const DEFAULT_DATABASE_FILE: &str = "content.db";
 const STRATEGY_CONFIG_FOLDER: &str = "strategy-config";
+const DEFAULT_REFRESH_INTERVAL_MINUTES: u64 = 60;
+const DEFAULT_SCHEDULER_PERIOD_MINUTES: u64 = 1;
+const DEFAULT_FETCH_TIMEOUT_SECONDS: u64 = 10 * 60;
+const DEFAULT_FETCH_RETRIES: u32 = 2;
+const DEFAULT_FETCH_RETRY_BACKOFF_SECONDS: u64 = 5;
 
 ///The users input to configure/change the settings. All optional where there are defaults.
 #[derive(Clone, Deserialize)]
@@ -14,6 +20,20 @@ pub struct Config {
 	pub data_path: Option<PathBuf>,
 	///The path at which the database file is located.
 	pub database_path: Option<PathBuf>,
+	///Minutes between scheduled fetches of feeds that don't specify their own interval
+	pub default_refresh_interval: Option<u64>,
+	///Minutes between checks for feeds that are due to be fetched, 0 to not fetch automatically
+	pub scheduler_period: Option<u64>,
+	///Maximum amount of fetches running at the same time
+	pub fetch_concurrency: Option<usize>,
+	///Maximum amount of fetches running at the same time for a single host
+	pub fetch_concurrency_per_host: Option<usize>,
+	///Seconds fetching and parsing a single feed can each take, 0 for no limit
+	pub fetch_timeout: Option<u64>,
+	///How often to try fetching again after a transient error, 0 to not retry
+	pub fetch_retries: Option<u32>,
+	///Seconds to wait before the first retry, doubles for every retry after that
+	pub fetch_retry_backoff: Option<u64>,
 }
 
 impl Config {
@@ -22,16 +42,37 @@ impl Config {
 	}
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+	#[error("{setting} is too large, {minutes} minutes doesn't fit in a duration")]
+	TooManyMinutes {
+		setting: &'static str,
+		minutes: u64,
+	},
+}
+
+fn from_minutes(setting: &'static str, minutes: u64) -> Result<Duration, SettingsError> {
+	minutes.checked_mul(60)
+		.map(Duration::from_secs)
+		.ok_or(SettingsError::TooManyMinutes { setting, minutes })
+}
+
 ///The actual settings to use, with the defaults filled in and resolved.
 #[derive(Debug)]
 pub struct Settings {
 	pub data_path: PathBuf,
 	pub database_url: String,
+	pub default_refresh_interval: Duration,
+	///[None] when feeds shouldn't get fetched automatically
+	pub scheduler_period: Option<Duration>,
+	pub fetch_limits: Limits,
+	pub fetch_timeout: Option<Duration>,
+	pub fetch_retry: RetryPolicy,
 }
 
 impl Settings {
-	pub fn load() -> Self {
-		Config::load().into()
+	pub fn load() -> Result<Self, SettingsError> {
+		Config::load().try_into()
 	}
 	
 	pub fn get_strategy_config_path(&self) -> PathBuf {
@@ -60,11 +101,20 @@ impl Settings {
 	}
 }
 
-impl From<Config> for Settings {
-	fn from(config: Config) -> Self {
+impl TryFrom<Config> for Settings {
+	type Error = SettingsError;
+	
+	fn try_from(config: Config) -> Result<Self, SettingsError> {
 		let Config {
 			data_path: maybe_data_path,
 			database_path: maybe_database_path, 
+			default_refresh_interval,
+			scheduler_period,
+			fetch_concurrency,
+			fetch_concurrency_per_host,
+			fetch_timeout,
+			fetch_retries,
+			fetch_retry_backoff,
 		} = config;
 		
 		let data_path = maybe_data_path.unwrap_or_else(|| {
@@ -85,9 +135,39 @@ impl From<Config> for Settings {
 		let db = db.replace('\\', "/");
 		let database_url = format!("sqlite://{db}?mode=rwc");
 		
-		Self {
+		let default_refresh_interval = from_minutes(
+			"default_refresh_interval",
+			default_refresh_interval.unwrap_or(DEFAULT_REFRESH_INTERVAL_MINUTES),
+		)?;
+		let scheduler_period = match scheduler_period.unwrap_or(DEFAULT_SCHEDULER_PERIOD_MINUTES) {
+			0 => None,
+			minutes => Some(from_minutes("scheduler_period", minutes)?),
+		};
+		
+		let default_limits = Limits::default();
+		let fetch_limits = Limits {
+			concurrency: fetch_concurrency.unwrap_or(default_limits.concurrency),
+			per_host: fetch_concurrency_per_host.unwrap_or(default_limits.per_host),
+		};
+		
+		let fetch_timeout = match fetch_timeout.unwrap_or(DEFAULT_FETCH_TIMEOUT_SECONDS) {
+			0 => None,
+			seconds => Some(Duration::from_secs(seconds)),
+		};
+		
+		let fetch_retry = RetryPolicy::new(
+			fetch_retries.unwrap_or(DEFAULT_FETCH_RETRIES).saturating_add(1),
+			Duration::from_secs(fetch_retry_backoff.unwrap_or(DEFAULT_FETCH_RETRY_BACKOFF_SECONDS)),
+		);
+		
+		Ok(Self {
 			data_path,
 			database_url,
-		}
+			default_refresh_interval,
+			scheduler_period,
+			fetch_limits,
+			fetch_timeout,
+			fetch_retry,
+		})
 	}
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+use std::{sync::Arc, time::Duration};
+use axum::{
+	extract::Path,
+	http::StatusCode,
+	response::sse::{Event, KeepAlive, Sse},
+	routing::get,
+	Extension, Router,
+};
+use futures::{Stream, stream};
+use tokio::sync::{RwLock, broadcast::{self, error::RecvError}};
+use acquire::{batch::{Batch, BatchStatusUpdate, BatchSnapshot}, batch_tracker::BatchTracker};
+
+
+///Name of the Server-Sent Event that contains a [BatchStatusUpdate] as JSON
+pub const BATCH_STATUS_EVENT: &str = "status";
+///Name of the Server-Sent Event that contains a [BatchSnapshot] as JSON, which replaces everything received before
+pub const BATCH_SNAPSHOT_EVENT: &str = "snapshot";
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+pub fn routes() -> Router {
+	Router::new()
+		.route("/events/batch/:id", get(batch_events))
+}
+
+enum Message {
+	Update(BatchStatusUpdate),
+	Snapshot(BatchSnapshot),
+}
+
+impl Message {
+	fn is_done(&self) -> bool {
+		match self {
+			Self::Update(update) => update.is_done(),
+			Self::Snapshot(snapshot) => snapshot.status.is_done(),
+		}
+	}
+	
+	fn into_event(self) -> Result<Event, axum::Error> {
+		match self {
+			Self::Update(update) => Event::default().event(BATCH_STATUS_EVENT).json_data(update),
+			Self::Snapshot(snapshot) => Event::default().event(BATCH_SNAPSHOT_EVENT).json_data(snapshot),
+		}
+	}
+}
+
+struct BatchStream {
+	receiver: broadcast::Receiver<BatchStatusUpdate>,
+	batch: Arc<RwLock<Batch>>,
+	///Whether the next message should be a snapshot, which is the case at the start and after missing updates
+	snapshot: bool,
+	finished: bool,
+}
+
+impl BatchStream {
+	async fn next_message(&mut self) -> Option<Message> {
+		loop {
+			if self.snapshot {
+				self.snapshot = false;
+				return Some(Message::Snapshot(self.batch.read().await.snapshot()));
+			}
+			match self.receiver.recv().await {
+				Ok(update) => return Some(Message::Update(update)),
+				//The missed updates had the latest fetches in them, which the client needs to know about
+				Err(RecvError::Lagged(missed)) => {
+					tracing::info!(missed, "Batch event listener lagged behind, sending a snapshot");
+					self.snapshot = true;
+				},
+				Err(RecvError::Closed) => return None,
+			}
+		}
+	}
+	
+	fn into_stream(self) -> impl Stream<Item = Result<Event, axum::Error>> {
+		stream::unfold(self, |mut state| async move {
+			if state.finished {
+				return None;
+			}
+			let message = state.next_message().await?;
+			//The browser reconnects to streams that end, so the client should stop listening after this
+			state.finished = message.is_done();
+			Some((message.into_event(), state))
+		})
+	}
+}
+
+///Streams the [BatchStatusUpdate]s of a batch, starting with a [BatchSnapshot] of its current state, until it's finished
+async fn batch_events(
+	Extension(tracker): Extension<BatchTracker>,
+	Path(id): Path<i32>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+	//Subscribe before getting the snapshot so no updates get missed in between
+	let receiver = tracker.subscribe(id).await.map_err(|_| StatusCode::NOT_FOUND)?;
+	let batch = tracker.get_status(id).await.map_err(|_| StatusCode::NOT_FOUND)?;
+	
+	let batch_stream = BatchStream {
+		receiver,
+		batch,
+		snapshot: true,
+		finished: false,
+	};
+	
+	Ok(Sse::new(batch_stream.into_stream()).keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL)))
+}
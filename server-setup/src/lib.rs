@@ -10,14 +10,17 @@ use leptos::*;
 use leptos_axum::{generate_route_list, LeptosRoutes};
 use sea_orm_migration::MigratorTrait;
 use tower::ServiceExt;
+use acquire::{batch_tracker::BatchTracker, scheduler::Scheduler, limiter::Limiter};
 use tower_http::services::ServeDir;
-use tracing::level_filters::LevelFilter;
+use tracing::{level_filters::LevelFilter, Instrument};
 use tracing_subscriber::{fmt, EnvFilter, registry, prelude::*};
 
 
 
 mod config;
 pub mod setup;
+pub mod events;
+pub mod opml;
 
 
 const DEFAULT_LOG_FILTER: &str = "debug,hyper=info,sqlx=warn";
@@ -104,7 +107,13 @@ pub async fn run<Migrator: MigratorTrait, View>(app: fn() -> View, mut setup: se
 	
 	tracing::info!(?setup);
 	
-	let settings = config::Settings::load();
+	let settings = match config::Settings::load() {
+		Ok(settings) => settings,
+		Err(err) => {
+			tracing::error!(?err, "Error loading settings");
+			panic!("{1}: {:?}", err, "settings should be valid");
+		}
+	};
 	tracing::info!(?settings);
 	
 	let res = settings.ensure_folders_exist();
@@ -129,6 +138,11 @@ pub async fn run<Migrator: MigratorTrait, View>(app: fn() -> View, mut setup: se
 	//Keep migrations as a generic/function parameter to prevent recompilation whenever migrations change
 	Migrator::up(&db_conn, None).await.expect("failed running database migrations");
 	
+	let interrupted = BatchTracker::mark_interrupted(&db_conn).await.expect("failed marking interrupted batches");
+	if interrupted > 0 {
+		tracing::warn!(interrupted, "Marked batches that were running when the server stopped as interrupted");
+	}
+	
 	
 	
 	// A path of `None` means it uses environment values, see
@@ -137,10 +151,24 @@ pub async fn run<Migrator: MigratorTrait, View>(app: fn() -> View, mut setup: se
 	let leptos_options = leptos_config.leptos_options;
 	let serve_address = leptos_options.site_addr;
 	
-	let router = setup_leptos_routing(app, leptos_options)
-		.layer(Extension(db_conn));
+	let mut strat_list = setup.take_strategy_list();
+	strat_list.set_timeout(settings.fetch_timeout);
+	strat_list.set_retry_policy(settings.fetch_retry);
+	let batch_tracker = BatchTracker::new(Limiter::new(settings.fetch_limits));
+	
+	match settings.scheduler_period {
+		Some(period) => {
+			let scheduler = Scheduler::new(settings.default_refresh_interval, period);
+			let scheduler_future = scheduler.run(batch_tracker.clone(), db_conn.clone(), strat_list.clone());
+			tokio::spawn(scheduler_future.instrument(tracing::info_span!("scheduler")));
+		},
+		None => tracing::info!("Scheduled fetching is disabled"),
+	}
 	
-	let router = setup.extend(router);
+	let router = setup_leptos_routing(app, leptos_options);
+	let router = setup.extend_with(router, strat_list, batch_tracker)
+		//After extending, so the routes added there can also use it
+		.layer(Extension(db_conn));
 	
 	drop(setup_span_guard);
 	
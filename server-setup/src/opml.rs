@@ -0,0 +1,84 @@
+use axum::{
+	extract::Multipart,
+	http::{StatusCode, header},
+	response::{IntoResponse, Redirect, Response},
+	routing::{get, post},
+	Extension, Router,
+};
+use sea_orm::DatabaseConnection;
+use acquire::{opml, StrategyList};
+
+
+const EXPORT_FILE_NAME: &str = "fusta-femas.opml";
+///Name of the file field in the upload form
+const UPLOAD_FIELD: &str = "opml";
+///Where to go after uploading through the form
+const IMPORTED_REDIRECT: &str = "/feed/opml";
+
+pub fn routes() -> Router {
+	Router::new()
+		.route("/opml/export", get(export))
+		.route("/opml/import", post(import))
+}
+
+fn internal_error(err: impl std::fmt::Debug) -> Response {
+	tracing::error!(?err, "Error handling OPML");
+	StatusCode::INTERNAL_SERVER_ERROR.into_response()
+}
+
+///Downloads all feeds as OPML
+async fn export(Extension(db): Extension<DatabaseConnection>) -> Response {
+	match opml::export(&db).await {
+		Ok(document) => (
+			[
+				(header::CONTENT_TYPE, "text/x-opml; charset=utf-8".to_owned()),
+				(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{EXPORT_FILE_NAME}\"")),
+			],
+			document,
+		).into_response(),
+		Err(err) => internal_error(err),
+	}
+}
+
+///Imports the OPML file uploaded as multipart form data
+async fn import(
+	Extension(db): Extension<DatabaseConnection>,
+	Extension(strats): Extension<StrategyList>,
+	mut multipart: Multipart,
+) -> Response {
+	let document = loop {
+		let field = match multipart.next_field().await {
+			Ok(Some(field)) => field,
+			Ok(None) => return (StatusCode::BAD_REQUEST, format!("Missing the \"{UPLOAD_FIELD}\" field")).into_response(),
+			Err(err) => return err.into_response(),
+		};
+		if field.name() == Some(UPLOAD_FIELD) {
+			match field.text().await {
+				Ok(text) => break text,
+				Err(err) => return err.into_response(),
+			}
+		}
+	};
+	
+	let feeds = match opml::parse(&document) {
+		Ok(feeds) => feeds,
+		Err(err) => return (StatusCode::BAD_REQUEST, format!("Invalid OPML: {err}")).into_response(),
+	};
+	match opml::import(&db, &strats, feeds).await {
+		Ok(summary) => {
+			tracing::info!(%summary, "Imported OPML");
+			//Only the counts, the skipped urls could make the url too long
+			let counts = [
+				("created", summary.created),
+				("existing", summary.existing),
+				("tagged", summary.tagged),
+				("skipped", summary.skipped.len()),
+			];
+			match serde_urlencoded::to_string(counts) {
+				Ok(query) => Redirect::to(&format!("{IMPORTED_REDIRECT}?{query}")).into_response(),
+				Err(err) => internal_error(err),
+			}
+		},
+		Err(err) => internal_error(err),
+	}
+}
@@ -1,43 +1,22 @@
+use std::borrow::Cow;
 use axum::{
 	routing::Router,
 	Extension,
 };
 use acquire::{
 	batch_tracker::BatchTracker,
-	strategy::Strategy,
+	strategy::{Strategy, Named},
+	strategy_config::{ConfigFolder, ConfigError},
 	StrategyList,
 };
 use ffilter::{
-	filter::{Filter, GetBuilder}, filter_list::FilterList
+	filter::{Filter, FilterTarget, GetBuilder}, filter_list::FilterLists
 };
-use std::{fs::File, io::{Error as IoError, Write}};
 
 use super::config::Settings;
 
-
-const STRATEGY_CONFIG_FILE_EXTENSION: &str = "ron";
-
-
-pub fn strategy_serializer<Writer: Write>(writer: Writer) -> ron::Result<ron::Serializer<Writer>> {
-	use ron::*;
-	Serializer::new(
-		writer,
-		Some(ser::PrettyConfig::new()
-			.indentor("\t".into())
-			.struct_names(true)
-		),
-	)
-}
-
-//TODO just use anyhow at this point
-#[derive(Debug, thiserror::Error)]
-#[error(transparent)]
-pub enum StrategySaveLoadError {
-	Ron(#[from] ron::Error),
-	RonSpanned(#[from] ron::error::SpannedError),
-	Io(#[from] IoError),
-	Serde(#[from] erased_serde::Error)
-}
+///Makes another instance of a strategy type with the given name
+type InstanceFactory = Box<dyn Fn(String) -> Box<dyn Strategy + Send + Sync> + Send + Sync>;
 
 /**
 Hard-coded configuration stuff:
@@ -48,51 +27,93 @@ Hard-coded configuration stuff:
 #[derive(Default)]
 pub struct Setup {
 	pub strategies: Vec<Box<dyn Strategy + Send + Sync>>,
-	pub filters: FilterList,
+	pub filters: FilterLists,
+	///Where the strategy configurations got loaded from, so they can be edited while running
+	pub strategy_config: Option<ConfigFolder>,
+	///Strategy types that get more instances from the configuration folder, by the name of the original
+	instance_factories: Vec<(String, InstanceFactory)>,
 }
 
 impl Setup {
-	pub fn add_strategy(&mut self, strategy: impl Strategy + Send + 'static) {
+	///Panics if there already is a strategy with the same name, since feeds pick their strategy by name
+	pub fn add_strategy(&mut self, strategy: impl Strategy + 'static) {
+		let name = strategy.name();
+		if self.strategies.iter().any(|existing| existing.name() == name) {
+			panic!("strategy names should be unique, \"{name}\" got added twice");
+		}
 		self.strategies.push(Box::new(strategy));
 	}
-	pub fn add_filter<FilterType: GetBuilder + Filter + Send + Sync + 'static>(&mut self, filter: FilterType) {
+	///Adds another instance of a strategy type under `name`, with its own configuration file
+	pub fn add_named_strategy<S: Strategy + Clone + 'static>(&mut self, name: impl Into<Cow<'static, str>>, strategy: S) {
+		self.add_strategy(Named::new(name, strategy));
+	}
+	///Adds `strategy`, plus a [Named] instance of it for every `<name>.<instance>.ron` in the configuration folder.
+	///Those get found when [loading the configurations](Self::saveload_strategy_configurations), so new ones don't need recompiling.
+	pub fn add_strategy_with_instances<S: Strategy + Clone + 'static>(&mut self, strategy: S) {
+		let template = strategy.clone();
+		let factory: InstanceFactory = Box::new(move |name| Box::new(Named::new(name, template.clone())));
+		self.instance_factories.push((strategy.name().to_owned(), factory));
+		self.add_strategy(strategy);
+	}
+	///Adds a filter for its [FilterTarget], e.g. feeds or entries
+	pub fn add_filter<Target: FilterTarget, FilterType: GetBuilder<Target> + Filter<Target> + Send + Sync + 'static>(&mut self, filter: FilterType) {
 		self.filters.add(filter);
-		self.filters.add_builder::<FilterType>();
 	}
 	
 	
-	pub fn saveload_strategy_configurations(&mut self, settings: &Settings) -> Result<(), StrategySaveLoadError> {
-		let base_path = settings.get_strategy_config_path();
+	pub fn saveload_strategy_configurations(&mut self, settings: &Settings) -> Result<(), ConfigError> {
+		let folder = ConfigFolder::new(settings.get_strategy_config_path());
+		self.add_configured_instances(&folder)?;
 		
 		for strat in &mut self.strategies {
-			let mut path = base_path.join(strat.name());
-			path.set_extension(STRATEGY_CONFIG_FILE_EXTENSION);
-			if path.try_exists()? {
-				let text = std::fs::read_to_string(path)?;
-				let mut deserializer = ron::Deserializer::from_str(&text)?;
-				let mut erased = <dyn erased_serde::Deserializer>::erase(&mut deserializer);
-				strat.deserialize_replace(&mut erased)?;
-			} else {
-				let file = File::create(path)?;
-				let mut serializer = strategy_serializer(file)?;
-				let mut erased = <dyn erased_serde::Serializer>::erase(&mut serializer);
-				strat.serialize(&mut erased)?;
+			if !folder.load(strat.as_mut())? {
+				folder.save(strat.as_ref())?;
 			}
 		}
 		
+		self.strategy_config = Some(folder);
+		Ok(())
+	}
+	
+	///Adds the instances with a file in `folder` of the types added by [Self::add_strategy_with_instances]
+	pub fn add_configured_instances(&mut self, folder: &ConfigFolder) -> Result<(), ConfigError> {
+		for (name, factory) in &self.instance_factories {
+			for instance in folder.instance_names(name)? {
+				//Could already be added by hand, or be found for two types when names contain dots
+				if !self.strategies.iter().any(|existing| existing.name() == instance) {
+					self.strategies.push(factory(instance));
+				}
+			}
+		}
 		Ok(())
 	}
 	
-	pub fn extend(self, router: Router) -> Router {
+	///Moves the strategies into a [StrategyList]
+	pub fn take_strategy_list(&mut self) -> StrategyList {
 		let mut strat_list = StrategyList::new();
-		for strat in self.strategies {
+		for strat in self.strategies.drain(..) {
 			strat_list.add_from_container(strat);
 		}
-		
+		strat_list
+	}
+	
+	pub fn extend(mut self, router: Router) -> Router {
+		let strat_list = self.take_strategy_list();
+		self.extend_with(router, strat_list, BatchTracker::default())
+	}
+	
+	///Like [Self::extend], but with a [StrategyList] and [BatchTracker] that are also used elsewhere
+	pub fn extend_with(self, router: Router, strat_list: StrategyList, batch_tracker: BatchTracker) -> Router {
+		let router = match self.strategy_config {
+			Some(folder) => router.layer(Extension(folder)),
+			None => router,
+		};
 		router
+			.merge(super::events::routes())
+			.merge(super::opml::routes())
 			.layer(Extension(strat_list))
 			.layer(Extension(self.filters))
-			.layer(Extension(BatchTracker::default()))
+			.layer(Extension(batch_tracker))
 	}
 	
 	pub fn extend_fn(self) -> impl FnOnce(Router) -> Router {
@@ -106,13 +127,18 @@ impl std::fmt::Debug for Setup {
 			.map(|s| s.name())
 			.collect::<Vec<_>>();
 		
-		let filter_names = self.filters.iter_filters()
+		let feed_filter_names = self.filters.feed.iter_filters()
+			.map(|filter| filter.get_name())
+			.collect::<Vec<_>>();
+		
+		let entry_filter_names = self.filters.entry.iter_filters()
 			.map(|filter| filter.get_name())
 			.collect::<Vec<_>>();
 		
 		f.debug_struct("Setup")
 			.field("strategy_names", &strategy_names)
-			.field("filter_names", &filter_names)
+			.field("feed_filter_names", &feed_filter_names)
+			.field("entry_filter_names", &entry_filter_names)
 			.finish()
 	}
 }
\ No newline at end of file